@@ -13,6 +13,7 @@ use super::{Amf0Marker, Amf0ReadError, Amf0Value};
 /// it very cheap to use.
 pub struct Amf0Decoder<'a> {
     cursor: Cursor<&'a [u8]>,
+    lenient_object_end: bool,
 }
 
 impl<'a> Amf0Decoder<'a> {
@@ -20,18 +21,44 @@ impl<'a> Amf0Decoder<'a> {
     pub const fn new(buff: &'a [u8]) -> Self {
         Self {
             cursor: Cursor::new(buff),
+            lenient_object_end: false,
         }
     }
 
+    /// Set whether a missing object end marker should be tolerated.
+    ///
+    /// Some encoders omit or malform the object end marker (`0x00 0x00 0x09`).
+    /// When enabled, reaching the end of the buffer while reading an object is
+    /// treated as the end of the object, and the properties read so far are
+    /// returned. A property that is cut off by the end of the buffer is
+    /// discarded.
+    ///
+    /// Disabled by default, in which case this is an error.
+    pub const fn with_lenient_object_end(mut self, lenient: bool) -> Self {
+        self.lenient_object_end = lenient;
+        self
+    }
+
     /// Check if the decoder has reached the end of the AMF0 data.
     pub const fn is_empty(&self) -> bool {
         self.cursor.get_ref().len() == self.cursor.position() as usize
     }
 
+    /// Returns the number of bytes left to decode.
+    pub const fn remaining(&self) -> usize {
+        self.cursor.get_ref().len().saturating_sub(self.cursor.position() as usize)
+    }
+
     fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], Amf0ReadError> {
-        let pos = self.cursor.position();
+        // The length comes from the data, so it is checked before it is used.
+        if len > self.remaining() {
+            return Err(Amf0ReadError::UnexpectedEof);
+        }
+
+        let pos = self.cursor.position() as usize;
+        let bytes = &self.cursor.get_ref()[pos..pos + len];
         self.cursor.seek(SeekFrom::Current(len as i64))?;
-        Ok(&self.cursor.get_ref()[pos as usize..pos as usize + len])
+        Ok(bytes)
     }
 
     /// Read all the encoded values from the decoder.
@@ -57,7 +84,12 @@ impl<'a> Amf0Decoder<'a> {
             Amf0Marker::Object => Ok(Amf0Value::Object(self.read_object()?.into())),
             Amf0Marker::Null => Ok(Amf0Value::Null),
             Amf0Marker::EcmaArray => Ok(Amf0Value::Object(self.read_ecma_array()?.into())),
+            Amf0Marker::StrictArray => Ok(Amf0Value::StrictArray(self.read_strict_array()?.into())),
             Amf0Marker::LongString => Ok(Amf0Value::LongString(self.read_long_string()?)),
+            // These markers have no body, so they can be skipped.
+            Amf0Marker::MovieClipMarker | Amf0Marker::Unsupported | Amf0Marker::Recordset => {
+                Ok(Amf0Value::Unsupported(marker))
+            }
             _ => Err(Amf0ReadError::UnsupportedType(marker)),
         }
     }
@@ -76,6 +108,26 @@ impl<'a> Amf0Decoder<'a> {
         self.decode()
     }
 
+    /// Read an RTMP command from the decoder.
+    ///
+    /// Commands start with the command name and the transaction id, which are
+    /// returned along with the rest of the values: the command object followed
+    /// by any optional arguments.
+    ///
+    /// Returns [`Amf0ReadError::WrongType`] if the first value is not a string
+    /// or the second value is not a number.
+    pub fn decode_command(&mut self) -> Result<(Cow<'a, str>, f64, Vec<Amf0Value<'a>>), Amf0ReadError> {
+        let Amf0Value::String(name) = self.decode_with_type(Amf0Marker::String)? else {
+            unreachable!("the marker was checked");
+        };
+
+        let Amf0Value::Number(transaction_id) = self.decode_with_type(Amf0Marker::Number)? else {
+            unreachable!("the marker was checked");
+        };
+
+        Ok((name, transaction_id, self.decode_all()?))
+    }
+
     fn read_number(&mut self) -> Result<f64, Amf0ReadError> {
         Ok(self.cursor.read_f64::<BigEndian>()?)
     }
@@ -108,25 +160,49 @@ impl<'a> Amf0Decoder<'a> {
         let mut properties = Vec::new();
 
         loop {
+            if self.lenient_object_end && self.is_empty() {
+                break;
+            }
+
             let is_eof = self.is_read_object_eof()?;
 
             if is_eof {
                 break;
             }
 
-            let key = self.read_string()?;
-            let val = self.decode()?;
-
-            properties.push((key, val));
+            match self.read_property() {
+                Ok(property) => properties.push(property),
+                // Reading from the buffer can only fail with an io error when it ends early.
+                Err(Amf0ReadError::UnexpectedEof | Amf0ReadError::Io(_)) if self.lenient_object_end => {
+                    // The rest of the buffer is a truncated property, discard it.
+                    self.cursor.set_position(self.cursor.get_ref().len() as u64);
+                    break;
+                }
+                Err(err) => return Err(err),
+            }
         }
 
         Ok(properties)
     }
 
+    fn read_property(&mut self) -> Result<(Cow<'a, str>, Amf0Value<'a>), Amf0ReadError> {
+        let key = self.read_string()?;
+        let val = self.decode()?;
+
+        Ok((key, val))
+    }
+
     fn read_ecma_array(&mut self) -> Result<Vec<(Cow<'a, str>, Amf0Value<'a>)>, Amf0ReadError> {
-        let len = self.cursor.read_u32::<BigEndian>()?;
+        let len = self.cursor.read_u32::<BigEndian>()? as usize;
 
-        let mut properties = Vec::new();
+        // Every property is at least 3 bytes (a 2 byte key length and a marker),
+        // so a count that cannot fit in the rest of the buffer is rejected
+        // before anything is allocated.
+        if len > self.remaining() / 3 {
+            return Err(Amf0ReadError::UnexpectedEof);
+        }
+
+        let mut properties = Vec::with_capacity(len);
 
         for _ in 0..len {
             let key = self.read_string()?;
@@ -141,6 +217,24 @@ impl<'a> Amf0Decoder<'a> {
         Ok(properties)
     }
 
+    fn read_strict_array(&mut self) -> Result<Vec<Amf0Value<'a>>, Amf0ReadError> {
+        let len = self.cursor.read_u32::<BigEndian>()? as usize;
+
+        // Every value is at least 1 byte (its marker), so a count that cannot fit
+        // in the rest of the buffer is rejected before anything is allocated.
+        if len > self.remaining() {
+            return Err(Amf0ReadError::UnexpectedEof);
+        }
+
+        let mut values = Vec::with_capacity(len);
+
+        for _ in 0..len {
+            values.push(self.decode()?);
+        }
+
+        Ok(values)
+    }
+
     fn read_long_string(&mut self) -> Result<Cow<'a, str>, Amf0ReadError> {
         let l = self.cursor.read_u32::<BigEndian>()?;
 
@@ -167,6 +261,7 @@ impl<'a> Iterator for Amf0Decoder<'a> {
 #[cfg_attr(all(test, coverage_nightly), coverage(off))]
 mod tests {
     use super::*;
+    use crate::Amf0Encoder;
 
     #[test]
     fn test_reader_bool() {
@@ -219,6 +314,44 @@ mod tests {
         assert_eq!(value, Amf0Value::Object(vec![("test".into(), Amf0Value::Null)].into()));
     }
 
+    #[test]
+    fn test_reader_object_missing_end() {
+        let mut amf0_object = vec![0x03, 0x00, 0x04]; // 1 property with 4 bytes
+        amf0_object.extend_from_slice(b"test");
+        amf0_object.extend_from_slice(&[0x05]); // null
+        amf0_object.extend_from_slice(&[0x00, 0x03]); // truncated key
+        amf0_object.extend_from_slice(b"ab");
+
+        // strict by default
+        let mut amf_reader = Amf0Decoder::new(&amf0_object[..8]);
+        let result = amf_reader.decode_with_type(Amf0Marker::Object);
+        assert!(matches!(result, Err(Amf0ReadError::Io(_))));
+
+        let mut amf_reader = Amf0Decoder::new(&amf0_object);
+        let result = amf_reader.decode_with_type(Amf0Marker::Object);
+        assert!(matches!(result, Err(Amf0ReadError::UnexpectedEof)));
+
+        // missing object end
+        let mut amf_reader = Amf0Decoder::new(&amf0_object[..8]).with_lenient_object_end(true);
+        let value = amf_reader.decode_with_type(Amf0Marker::Object).unwrap();
+        assert_eq!(value, Amf0Value::Object(vec![("test".into(), Amf0Value::Null)].into()));
+        assert!(amf_reader.is_empty());
+
+        // truncated property is discarded
+        let mut amf_reader = Amf0Decoder::new(&amf0_object).with_lenient_object_end(true);
+        let value = amf_reader.decode_with_type(Amf0Marker::Object).unwrap();
+        assert_eq!(value, Amf0Value::Object(vec![("test".into(), Amf0Value::Null)].into()));
+        assert!(amf_reader.is_empty());
+
+        // other errors are still reported
+        let mut amf0_object = vec![0x03, 0x00, 0x04]; // 1 property with 4 bytes
+        amf0_object.extend_from_slice(b"test");
+        amf0_object.push(Amf0Marker::Reference as u8);
+        let mut amf_reader = Amf0Decoder::new(&amf0_object).with_lenient_object_end(true);
+        let result = amf_reader.decode_with_type(Amf0Marker::Object);
+        assert!(matches!(result, Err(Amf0ReadError::UnsupportedType(Amf0Marker::Reference))));
+    }
+
     #[test]
     fn test_reader_ecma_array() {
         let mut amf0_object = vec![0x08, 0x00, 0x00, 0x00, 0x01]; // 1 property
@@ -232,6 +365,136 @@ mod tests {
         assert_eq!(value, Amf0Value::Object(vec![("test".into(), Amf0Value::Null)].into()));
     }
 
+    #[test]
+    fn test_reader_command_connect() {
+        let mut amf0_command = Vec::new();
+        Amf0Encoder::encode_string(&mut amf0_command, "connect").unwrap();
+        Amf0Encoder::encode_number(&mut amf0_command, 1.0).unwrap();
+        Amf0Encoder::encode_object(
+            &mut amf0_command,
+            &[
+                ("app".into(), Amf0Value::String("live".into())),
+                ("tcUrl".into(), Amf0Value::String("rtmp://localhost/live".into())),
+            ],
+        )
+        .unwrap();
+
+        let mut amf_reader = Amf0Decoder::new(&amf0_command);
+        let (name, transaction_id, args) = amf_reader.decode_command().unwrap();
+
+        assert_eq!(name, "connect");
+        assert_eq!(transaction_id, 1.0);
+        assert_eq!(
+            args,
+            vec![Amf0Value::Object(
+                vec![
+                    ("app".into(), Amf0Value::String("live".into())),
+                    ("tcUrl".into(), Amf0Value::String("rtmp://localhost/live".into())),
+                ]
+                .into()
+            )]
+        );
+        assert!(amf_reader.is_empty());
+    }
+
+    #[test]
+    fn test_reader_command_publish() {
+        let mut amf0_command = Vec::new();
+        Amf0Encoder::encode_string(&mut amf0_command, "publish").unwrap();
+        Amf0Encoder::encode_number(&mut amf0_command, 0.0).unwrap();
+        Amf0Encoder::encode_null(&mut amf0_command).unwrap();
+        Amf0Encoder::encode_string(&mut amf0_command, "stream_key").unwrap();
+        Amf0Encoder::encode_string(&mut amf0_command, "live").unwrap();
+
+        let mut amf_reader = Amf0Decoder::new(&amf0_command);
+        let (name, transaction_id, args) = amf_reader.decode_command().unwrap();
+
+        assert_eq!(name, "publish");
+        assert_eq!(transaction_id, 0.0);
+        assert_eq!(
+            args,
+            vec![
+                Amf0Value::Null,
+                Amf0Value::String("stream_key".into()),
+                Amf0Value::String("live".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reader_command_wrong_type() {
+        // The command name is not a string
+        let mut amf0_command = Vec::new();
+        Amf0Encoder::encode_number(&mut amf0_command, 1.0).unwrap();
+        Amf0Encoder::encode_number(&mut amf0_command, 1.0).unwrap();
+
+        let mut amf_reader = Amf0Decoder::new(&amf0_command);
+        assert!(matches!(
+            amf_reader.decode_command(),
+            Err(Amf0ReadError::WrongType(Amf0Marker::String, Amf0Marker::Number))
+        ));
+
+        // The transaction id is not a number
+        let mut amf0_command = Vec::new();
+        Amf0Encoder::encode_string(&mut amf0_command, "connect").unwrap();
+        Amf0Encoder::encode_string(&mut amf0_command, "1").unwrap();
+
+        let mut amf_reader = Amf0Decoder::new(&amf0_command);
+        assert!(matches!(
+            amf_reader.decode_command(),
+            Err(Amf0ReadError::WrongType(Amf0Marker::Number, Amf0Marker::String))
+        ));
+
+        // The command is missing the transaction id
+        let mut amf0_command = Vec::new();
+        Amf0Encoder::encode_string(&mut amf0_command, "connect").unwrap();
+
+        let mut amf_reader = Amf0Decoder::new(&amf0_command);
+        assert!(matches!(amf_reader.decode_command(), Err(Amf0ReadError::Io(_))));
+    }
+
+    #[test]
+    fn test_reader_strict_array() {
+        let amf0_strict_array = vec![0x0a, 0x00, 0x00, 0x00, 0x02, 0x00, 0x3f, 0xf0, 0, 0, 0, 0, 0, 0, 0x05];
+        let mut amf_reader = Amf0Decoder::new(&amf0_strict_array);
+        let value = amf_reader.decode_with_type(Amf0Marker::StrictArray).unwrap();
+        assert_eq!(
+            value,
+            Amf0Value::StrictArray(vec![Amf0Value::Number(1.0), Amf0Value::Null].into())
+        );
+        assert!(amf_reader.is_empty());
+    }
+
+    #[test]
+    fn test_reader_declared_length_too_long() {
+        let cases: [&[u8]; 6] = [
+            // string declaring 65535 bytes
+            &[Amf0Marker::String as u8, 0xff, 0xff, b'a', b'b'],
+            // long string declaring 4294967295 bytes
+            &[Amf0Marker::LongString as u8, 0xff, 0xff, 0xff, 0xff, b'a', b'b'],
+            // ecma array declaring 4294967295 properties
+            &[Amf0Marker::EcmaArray as u8, 0xff, 0xff, 0xff, 0xff, 0x00, 0x01, b'a', 0x05],
+            // ecma array declaring 2 properties with room for 1
+            &[Amf0Marker::EcmaArray as u8, 0x00, 0x00, 0x00, 0x02, 0x00, 0x01, b'a', 0x05],
+            // strict array declaring 4294967295 values
+            &[Amf0Marker::StrictArray as u8, 0xff, 0xff, 0xff, 0xff, 0x05],
+            // object key declaring 65535 bytes
+            &[Amf0Marker::Object as u8, 0xff, 0xff, b'a', b'b'],
+        ];
+
+        for case in cases {
+            let mut amf_reader = Amf0Decoder::new(case);
+            let result = amf_reader.decode();
+            assert!(matches!(result, Err(Amf0ReadError::UnexpectedEof)), "{case:?}: {result:?}");
+        }
+
+        // the declared length fits exactly
+        let mut amf_reader = Amf0Decoder::new(&[Amf0Marker::String as u8, 0x00, 0x02, b'a', b'b']);
+        assert_eq!(amf_reader.remaining(), 5);
+        assert_eq!(amf_reader.decode().unwrap(), Amf0Value::String("ab".into()));
+        assert_eq!(amf_reader.remaining(), 0);
+    }
+
     #[test]
     fn test_reader_multi_value() {
         let mut amf0_multi = vec![0x00];
@@ -255,6 +518,34 @@ mod tests {
         assert_eq!(values[3], Amf0Value::Object(vec![("test".into(), Amf0Value::Null)].into()));
     }
 
+    #[test]
+    fn test_reader_unsupported_markers() {
+        let mut amf0_multi = vec![
+            Amf0Marker::Unsupported as u8,
+            Amf0Marker::Null as u8,
+            Amf0Marker::MovieClipMarker as u8,
+            Amf0Marker::Recordset as u8,
+        ];
+        amf0_multi.extend_from_slice(&[0x03, 0x00, 0x04]); // 1 property with 4 bytes
+        amf0_multi.extend_from_slice(b"test");
+        amf0_multi.push(Amf0Marker::Unsupported as u8);
+        amf0_multi.extend_from_slice(&[0x00, 0x00, 0x09]); // object end (0x00 0x00 0x09)
+
+        let mut amf_reader = Amf0Decoder::new(&amf0_multi);
+        let values = amf_reader.decode_all().unwrap();
+
+        assert_eq!(
+            values,
+            vec![
+                Amf0Value::Unsupported(Amf0Marker::Unsupported),
+                Amf0Value::Null,
+                Amf0Value::Unsupported(Amf0Marker::MovieClipMarker),
+                Amf0Value::Unsupported(Amf0Marker::Recordset),
+                Amf0Value::Object(vec![("test".into(), Amf0Value::Unsupported(Amf0Marker::Unsupported))].into()),
+            ]
+        );
+    }
+
     #[test]
     fn test_reader_iterator() {
         let mut amf0_multi = vec![0x00];
@@ -275,10 +566,10 @@ mod tests {
 
     #[test]
     fn test_reader_invalid_marker() {
-        let amf0_unsupported_marker = vec![Amf0Marker::Unsupported as u8];
+        let amf0_unsupported_marker = vec![Amf0Marker::Reference as u8];
         let mut amf_reader = Amf0Decoder::new(&amf0_unsupported_marker);
         let result = amf_reader.decode();
 
-        assert!(matches!(result, Err(Amf0ReadError::UnsupportedType(Amf0Marker::Unsupported))));
+        assert!(matches!(result, Err(Amf0ReadError::UnsupportedType(Amf0Marker::Reference))));
     }
 }
@@ -2,6 +2,8 @@ use std::borrow::Cow;
 
 use num_derive::FromPrimitive;
 
+use crate::{Amf0ObjectEncoding, Amf0WriteOptions};
+
 /// AMF0 marker types.
 /// Defined in amf0_spec_121207.pdf section 2.1
 #[derive(Debug, PartialEq, Eq, Clone, Copy, FromPrimitive)]
@@ -67,8 +69,16 @@ pub enum Amf0Value<'a> {
     Null,
     /// Undefined Type defined section 2.8
     ObjectEnd,
+    /// StrictArray Type defined section 2.12
+    StrictArray(Cow<'a, [Amf0Value<'a>]>),
     /// LongString Type defined section 2.14
     LongString(Cow<'a, str>),
+    /// A value with no body that is not supported, with its marker.
+    ///
+    /// This is used for the unsupported marker defined section 2.15, and the
+    /// reserved movieclip and recordset markers (sections 2.6 and 2.16), so
+    /// they can be skipped when decoding. It cannot be encoded.
+    Unsupported(Amf0Marker),
 }
 
 impl Amf0Value<'_> {
@@ -81,7 +91,96 @@ impl Amf0Value<'_> {
             Self::Object(_) => Amf0Marker::Object,
             Self::Null => Amf0Marker::Null,
             Self::ObjectEnd => Amf0Marker::ObjectEnd,
+            Self::StrictArray(_) => Amf0Marker::StrictArray,
             Self::LongString(_) => Amf0Marker::LongString,
+            Self::Unsupported(marker) => *marker,
+        }
+    }
+
+    /// Get the number of bytes this value is encoded as, including its marker.
+    ///
+    /// This is the exact length written by
+    /// [`Amf0Encoder::encode`](crate::Amf0Encoder::encode), which encodes
+    /// objects as anonymous objects, so it can be used to pre-allocate the
+    /// output buffer. Use [`encoded_len_with_options`](Self::encoded_len_with_options)
+    /// for the length written with other options. Values that cannot be
+    /// encoded, such as the object end, report the length defined by the spec.
+    pub fn encoded_len(&self) -> usize {
+        self.encoded_len_with_options(&Amf0WriteOptions::default())
+    }
+
+    /// Get the number of bytes this value is encoded as with the given
+    /// options, the exact length written by
+    /// [`Amf0Encoder::encode_with_options`](crate::Amf0Encoder::encode_with_options).
+    pub fn encoded_len_with_options(&self, options: &Amf0WriteOptions) -> usize {
+        let encoded_len = |value: &Self| value.encoded_len_with_options(options);
+
+        match self {
+            // marker + 8 byte number
+            Self::Number(_) => 1 + 8,
+            // marker + 1 byte boolean
+            Self::Boolean(_) => 1 + 1,
+            // marker + 2 byte length + string
+            Self::String(s) => 1 + 2 + s.len(),
+            // marker + (4 byte count for ECMA arrays) + (2 byte key length + key + value) for each
+            // property + object end
+            Self::Object(o) => {
+                let count_len = match options.object_encoding {
+                    Amf0ObjectEncoding::Object => 0,
+                    Amf0ObjectEncoding::EcmaArray => 4,
+                };
+
+                1 + count_len + o.iter().map(|(k, v)| 2 + k.len() + encoded_len(v)).sum::<usize>() + 3
+            }
+            Self::Null => 1,
+            // the object end marker is 3 bytes (0x00 0x00 0x09)
+            Self::ObjectEnd => 3,
+            // marker + 4 byte count + values
+            Self::StrictArray(a) => 1 + 4 + a.iter().map(encoded_len).sum::<usize>(),
+            // marker + 4 byte length + string
+            Self::LongString(s) => 1 + 4 + s.len(),
+            Self::Unsupported(_) => 1,
+        }
+    }
+
+    /// Get the value of a property if this value is an object.
+    ///
+    /// Returns `None` if this value is not an object or the key does not exist.
+    pub fn get(&self, key: &str) -> Option<&Self> {
+        match self {
+            Self::Object(o) => o.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Get the value of a string property if this value is an object.
+    ///
+    /// Both [`Amf0Value::String`] and [`Amf0Value::LongString`] are accepted.
+    /// Returns `None` if the key does not exist or the property is not a string.
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        match self.get(key)? {
+            Self::String(s) | Self::LongString(s) => Some(s.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// Get the value of a number property if this value is an object.
+    ///
+    /// Returns `None` if the key does not exist or the property is not a number.
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        match self.get(key)? {
+            Self::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Get the value of a boolean property if this value is an object.
+    ///
+    /// Returns `None` if the key does not exist or the property is not a boolean.
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        match self.get(key)? {
+            Self::Boolean(b) => Some(*b),
+            _ => None,
         }
     }
 
@@ -95,6 +194,8 @@ impl Amf0Value<'_> {
             Self::Boolean(b) => Amf0Value::Boolean(*b),
             Self::Null => Amf0Value::Null,
             Self::ObjectEnd => Amf0Value::ObjectEnd,
+            Self::StrictArray(a) => Amf0Value::StrictArray(a.iter().map(|v| v.to_owned()).collect()),
+            Self::Unsupported(marker) => Amf0Value::Unsupported(*marker),
         }
     }
 }
@@ -118,7 +219,12 @@ mod tests {
             ),
             (Amf0Value::Null, Amf0Marker::Null),
             (Amf0Value::ObjectEnd, Amf0Marker::ObjectEnd),
+            (
+                Amf0Value::StrictArray(Cow::Borrowed(&[Amf0Value::Null])),
+                Amf0Marker::StrictArray,
+            ),
             (Amf0Value::LongString(Cow::Borrowed("test")), Amf0Marker::LongString),
+            (Amf0Value::Unsupported(Amf0Marker::Recordset), Amf0Marker::Recordset),
         ];
 
         for (value, marker) in cases {
@@ -160,6 +266,47 @@ mod tests {
         let value = Amf0Value::ObjectEnd;
         let owned = value.to_owned();
         assert_eq!(owned, Amf0Value::ObjectEnd);
+
+        let value = Amf0Value::Unsupported(Amf0Marker::Unsupported);
+        let owned = value.to_owned();
+        assert_eq!(owned, Amf0Value::Unsupported(Amf0Marker::Unsupported));
+    }
+
+    #[test]
+    fn test_object_lookup() {
+        let value = Amf0Value::Object(Cow::Borrowed(&[
+            (Cow::Borrowed("app"), Amf0Value::String(Cow::Borrowed("live"))),
+            (
+                Cow::Borrowed("tcUrl"),
+                Amf0Value::LongString(Cow::Borrowed("rtmp://localhost/live")),
+            ),
+            (Cow::Borrowed("audioCodecs"), Amf0Value::Number(3191.0)),
+            (Cow::Borrowed("fpad"), Amf0Value::Boolean(false)),
+            (Cow::Borrowed("pageUrl"), Amf0Value::Null),
+        ]));
+
+        // present keys
+        assert_eq!(value.get("pageUrl"), Some(&Amf0Value::Null));
+        assert_eq!(value.get_str("app"), Some("live"));
+        assert_eq!(value.get_str("tcUrl"), Some("rtmp://localhost/live"));
+        assert_eq!(value.get_f64("audioCodecs"), Some(3191.0));
+        assert_eq!(value.get_bool("fpad"), Some(false));
+
+        // absent keys
+        assert_eq!(value.get("flashVer"), None);
+        assert_eq!(value.get_str("flashVer"), None);
+        assert_eq!(value.get_f64("videoCodecs"), None);
+        assert_eq!(value.get_bool("capabilities"), None);
+
+        // type mismatches
+        assert_eq!(value.get_str("audioCodecs"), None);
+        assert_eq!(value.get_f64("app"), None);
+        assert_eq!(value.get_bool("pageUrl"), None);
+
+        // not an object
+        let value = Amf0Value::String(Cow::Borrowed("app"));
+        assert_eq!(value.get("app"), None);
+        assert_eq!(value.get_str("app"), None);
     }
 
     #[test]
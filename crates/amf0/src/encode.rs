@@ -6,24 +6,120 @@ use byteorder::{BigEndian, WriteBytesExt};
 use super::define::Amf0Marker;
 use super::{Amf0Value, Amf0WriteError};
 
+/// The container type used to encode [`Amf0Value::Object`] values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Amf0ObjectEncoding {
+    /// Anonymous object, defined section 2.5
+    #[default]
+    Object,
+    /// ECMA array, defined section 2.10
+    EcmaArray,
+}
+
+/// Options for [`Amf0Encoder::encode_with_options`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Amf0WriteOptions {
+    /// The container type used for [`Amf0Value::Object`] values, including
+    /// nested ones.
+    ///
+    /// This defaults to [`Amf0ObjectEncoding::Object`], so the default options
+    /// encode the same bytes as [`Amf0Encoder::encode`]. For `onMetaData`, use
+    /// [`Amf0ObjectEncoding::EcmaArray`] instead, which is what ffmpeg and OBS
+    /// write and what the most players accept.
+    pub object_encoding: Amf0ObjectEncoding,
+}
+
+/// The options used by the encoders that do not take options.
+const OBJECT_OPTIONS: Amf0WriteOptions = Amf0WriteOptions {
+    object_encoding: Amf0ObjectEncoding::Object,
+};
+
 /// AMF0 encoder.
 ///
 /// Allows for encoding an AMF0 to some writer.
+///
+/// Any [`io::Write`] can be used as the writer, including sockets. Every
+/// write has [`write_all`](io::Write::write_all) semantics, so partial and
+/// interrupted writes are retried until the whole value is written. Errors
+/// returned by the writer are reported as [`Amf0WriteError::Io`], including
+/// [`io::ErrorKind::WriteZero`] when the writer stops accepting data. If an
+/// error occurs part of the value may already have been written.
 pub struct Amf0Encoder;
 
 impl Amf0Encoder {
     /// Encode a generic AMF0 value
+    ///
+    /// Every value can be encoded except [`Amf0Value::ObjectEnd`] and
+    /// [`Amf0Value::Unsupported`], which return
+    /// [`Amf0WriteError::UnsupportedType`].
+    ///
+    /// Objects are encoded as anonymous objects, use
+    /// [`encode_with_options`](Self::encode_with_options) to encode them as
+    /// ECMA arrays.
     pub fn encode(writer: &mut impl io::Write, value: &Amf0Value) -> Result<(), Amf0WriteError> {
+        Self::encode_with_options(writer, value, &OBJECT_OPTIONS)
+    }
+
+    /// Encode a generic AMF0 value with the given options
+    pub fn encode_with_options(
+        writer: &mut impl io::Write,
+        value: &Amf0Value,
+        options: &Amf0WriteOptions,
+    ) -> Result<(), Amf0WriteError> {
         match value {
             Amf0Value::Boolean(val) => Self::encode_bool(writer, *val),
             Amf0Value::Null => Self::encode_null(writer),
             Amf0Value::Number(val) => Self::encode_number(writer, *val),
             Amf0Value::String(val) => Self::encode_string(writer, val),
-            Amf0Value::Object(val) => Self::encode_object(writer, val),
+            Amf0Value::LongString(val) => Self::encode_long_string(writer, val),
+            Amf0Value::StrictArray(val) => {
+                writer.write_u8(Amf0Marker::StrictArray as u8)?;
+                Self::encode_values(writer, val, options)
+            }
+            Amf0Value::Object(val) => match options.object_encoding {
+                Amf0ObjectEncoding::Object => {
+                    writer.write_u8(Amf0Marker::Object as u8)?;
+                    Self::encode_properties(writer, val, options)
+                }
+                Amf0ObjectEncoding::EcmaArray => Self::encode_ecma_array_with_options(writer, val, options),
+            },
             _ => Err(Amf0WriteError::UnsupportedType(value.marker())),
         }
     }
 
+    fn encode_values(
+        writer: &mut impl io::Write,
+        values: &[Amf0Value<'_>],
+        options: &Amf0WriteOptions,
+    ) -> Result<(), Amf0WriteError> {
+        let len = u32::try_from(values.len()).map_err(|_| Amf0WriteError::ArrayTooLong)?;
+        writer.write_u32::<BigEndian>(len)?;
+        for value in values {
+            Self::encode_with_options(writer, value, options)?;
+        }
+
+        Ok(())
+    }
+
+    fn encode_properties(
+        writer: &mut impl io::Write,
+        properties: &[(Cow<'_, str>, Amf0Value<'_>)],
+        options: &Amf0WriteOptions,
+    ) -> Result<(), Amf0WriteError> {
+        for (key, value) in properties {
+            if key.len() > (u16::MAX as usize) {
+                return Err(Amf0WriteError::NormalStringTooLong);
+            }
+
+            writer.write_u16::<BigEndian>(key.len() as u16)?;
+            writer.write_all(key.as_bytes())?;
+            Self::encode_with_options(writer, value, options)?;
+        }
+
+        Self::object_eof(writer)?;
+        Ok(())
+    }
+
     fn object_eof(writer: &mut impl io::Write) -> Result<(), Amf0WriteError> {
         writer.write_u24::<BigEndian>(Amf0Marker::ObjectEnd as u32)?;
         Ok(())
@@ -55,6 +151,18 @@ impl Amf0Encoder {
         Ok(())
     }
 
+    /// Encode an AMF0 long string
+    pub fn encode_long_string(writer: &mut impl io::Write, value: &str) -> Result<(), Amf0WriteError> {
+        if value.len() > (u32::MAX as usize) {
+            return Err(Amf0WriteError::LongStringTooLong);
+        }
+
+        writer.write_u8(Amf0Marker::LongString as u8)?;
+        writer.write_u32::<BigEndian>(value.len() as u32)?;
+        writer.write_all(value.as_bytes())?;
+        Ok(())
+    }
+
     /// Encode an AMF0 null
     pub fn encode_null(writer: &mut impl io::Write) -> Result<(), Amf0WriteError> {
         writer.write_u8(Amf0Marker::Null as u8)?;
@@ -67,14 +175,35 @@ impl Amf0Encoder {
         properties: &[(Cow<'_, str>, Amf0Value<'_>)],
     ) -> Result<(), Amf0WriteError> {
         writer.write_u8(Amf0Marker::Object as u8)?;
-        for (key, value) in properties {
-            writer.write_u16::<BigEndian>(key.len() as u16)?;
-            writer.write_all(key.as_bytes())?;
-            Self::encode(writer, value)?;
-        }
+        Self::encode_properties(writer, properties, &OBJECT_OPTIONS)
+    }
 
-        Self::object_eof(writer)?;
-        Ok(())
+    /// Encode an AMF0 strict array
+    pub fn encode_strict_array(writer: &mut impl io::Write, values: &[Amf0Value<'_>]) -> Result<(), Amf0WriteError> {
+        writer.write_u8(Amf0Marker::StrictArray as u8)?;
+        Self::encode_values(writer, values, &OBJECT_OPTIONS)
+    }
+
+    /// Encode an AMF0 ECMA array
+    pub fn encode_ecma_array(
+        writer: &mut impl io::Write,
+        properties: &[(Cow<'_, str>, Amf0Value<'_>)],
+    ) -> Result<(), Amf0WriteError> {
+        Self::encode_ecma_array_with_options(writer, properties, &OBJECT_OPTIONS)
+    }
+
+    /// Encode an AMF0 ECMA array, encoding the nested values with the given
+    /// options
+    pub fn encode_ecma_array_with_options(
+        writer: &mut impl io::Write,
+        properties: &[(Cow<'_, str>, Amf0Value<'_>)],
+        options: &Amf0WriteOptions,
+    ) -> Result<(), Amf0WriteError> {
+        let len = u32::try_from(properties.len()).map_err(|_| Amf0WriteError::ArrayTooLong)?;
+
+        writer.write_u8(Amf0Marker::EcmaArray as u8)?;
+        writer.write_u32::<BigEndian>(len)?;
+        Self::encode_properties(writer, properties, options)
     }
 }
 
@@ -82,6 +211,7 @@ impl Amf0Encoder {
 #[cfg_attr(all(test, coverage_nightly), coverage(off))]
 mod tests {
     use super::*;
+    use crate::Amf0Decoder;
 
     #[test]
     fn test_write_number() {
@@ -143,6 +273,147 @@ mod tests {
         assert_eq!(vec, amf0_object);
     }
 
+    #[test]
+    fn test_write_ecma_array() {
+        let mut amf0_ecma_array = vec![0x08, 0x00, 0x00, 0x00, 0x01, 0x00, 0x04];
+        amf0_ecma_array.extend_from_slice(b"test");
+        amf0_ecma_array.extend_from_slice(&[0x05]);
+        amf0_ecma_array.extend_from_slice(&[0x00, 0x00, 0x09]);
+
+        let mut vec = Vec::<u8>::new();
+
+        Amf0Encoder::encode_ecma_array(&mut vec, &[("test".into(), Amf0Value::Null)]).unwrap();
+
+        assert_eq!(vec, amf0_ecma_array);
+
+        // Nested objects follow the options
+        let properties = [("test".into(), Amf0Value::Object(Cow::Owned(Vec::new())))];
+        for (encoding, marker) in [
+            (Amf0ObjectEncoding::Object, Amf0Marker::Object),
+            (Amf0ObjectEncoding::EcmaArray, Amf0Marker::EcmaArray),
+        ] {
+            let mut vec = Vec::<u8>::new();
+            Amf0Encoder::encode_ecma_array_with_options(
+                &mut vec,
+                &properties,
+                &Amf0WriteOptions {
+                    object_encoding: encoding,
+                },
+            )
+            .unwrap();
+            assert_eq!(vec[11], marker as u8);
+        }
+    }
+
+    #[test]
+    fn test_write_long_string() {
+        let mut amf0_long_string = vec![0x0c, 0x00, 0x00, 0x00, 0x0b];
+        amf0_long_string.extend_from_slice(b"Hello World");
+
+        let mut vec = Vec::<u8>::new();
+
+        Amf0Encoder::encode_long_string(&mut vec, "Hello World").unwrap();
+
+        assert_eq!(vec, amf0_long_string);
+    }
+
+    #[test]
+    fn test_write_strict_array() {
+        let amf0_strict_array = vec![0x0a, 0x00, 0x00, 0x00, 0x02, 0x05, 0x01, 0x01];
+
+        let mut vec = Vec::<u8>::new();
+
+        Amf0Encoder::encode_strict_array(&mut vec, &[Amf0Value::Null, Amf0Value::Boolean(true)]).unwrap();
+
+        assert_eq!(vec, amf0_strict_array);
+    }
+
+    #[test]
+    fn test_encode_strict_array_of_objects() {
+        let track = |id: f64, name: &'static str, default: bool| {
+            Amf0Value::Object(Cow::Owned(vec![
+                ("id".into(), Amf0Value::Number(id)),
+                ("name".into(), Amf0Value::String(name.into())),
+                ("default".into(), Amf0Value::Boolean(default)),
+                ("language".into(), Amf0Value::Null),
+                (
+                    "tags".into(),
+                    Amf0Value::StrictArray(Cow::Owned(vec![Amf0Value::LongString(name.into())])),
+                ),
+            ]))
+        };
+
+        let tracks = Amf0Value::StrictArray(Cow::Owned(vec![
+            track(1.0, "video", true),
+            track(2.0, "audio", true),
+            track(3.0, "commentary", false),
+        ]));
+
+        let mut vec = Vec::<u8>::with_capacity(tracks.encoded_len());
+        Amf0Encoder::encode(&mut vec, &tracks).unwrap();
+        assert_eq!(vec.len(), tracks.encoded_len());
+        assert_eq!(&vec[..5], &[Amf0Marker::StrictArray as u8, 0x00, 0x00, 0x00, 0x03]);
+
+        let mut decoder = Amf0Decoder::new(&vec);
+        assert_eq!(decoder.decode().unwrap(), tracks);
+        assert!(decoder.is_empty());
+
+        // The objects in the array follow the options
+        let mut vec = Vec::<u8>::new();
+        Amf0Encoder::encode_with_options(
+            &mut vec,
+            &tracks,
+            &Amf0WriteOptions {
+                object_encoding: Amf0ObjectEncoding::EcmaArray,
+            },
+        )
+        .unwrap();
+        assert_eq!(vec[5], Amf0Marker::EcmaArray as u8);
+
+        let mut decoder = Amf0Decoder::new(&vec);
+        assert_eq!(decoder.decode().unwrap(), tracks);
+        assert!(decoder.is_empty());
+    }
+
+    #[test]
+    fn test_encode_with_options() {
+        let metadata = Amf0Value::Object(Cow::Owned(vec![
+            ("duration".into(), Amf0Value::Number(10.0)),
+            ("encoder".into(), Amf0Value::String("Lavf61.7.100".into())),
+            (
+                "nested".into(),
+                Amf0Value::Object(Cow::Owned(vec![("width".into(), Amf0Value::Number(1920.0))])),
+            ),
+        ]));
+
+        assert_eq!(Amf0WriteOptions::default().object_encoding, Amf0ObjectEncoding::Object);
+
+        let mut object = Vec::<u8>::new();
+        Amf0Encoder::encode_with_options(&mut object, &metadata, &Amf0WriteOptions::default()).unwrap();
+        assert_eq!(object[0], Amf0Marker::Object as u8);
+
+        // The same as the encoding without options
+        let mut vec = Vec::<u8>::new();
+        Amf0Encoder::encode(&mut vec, &metadata).unwrap();
+        assert_eq!(vec, object);
+
+        let mut ecma_array = Vec::<u8>::new();
+        let ecma_array_options = Amf0WriteOptions {
+            object_encoding: Amf0ObjectEncoding::EcmaArray,
+        };
+        Amf0Encoder::encode_with_options(&mut ecma_array, &metadata, &ecma_array_options).unwrap();
+        assert_eq!(ecma_array[0], Amf0Marker::EcmaArray as u8);
+        assert_eq!(&ecma_array[1..5], &[0x00, 0x00, 0x00, 0x03]);
+        // The nested object is also an ECMA array, with the 4 byte count
+        assert_eq!(ecma_array.len(), object.len() + 8);
+
+        for bytes in [&object, &ecma_array] {
+            let mut decoder = Amf0Decoder::new(bytes);
+            assert_eq!(decoder.decode().unwrap(), metadata);
+            assert!(decoder.is_empty());
+        }
+    }
+
     #[test]
     fn test_encode_boolean() {
         let amf0_boolean_true = vec![Amf0Marker::Boolean as u8, 0x01];
@@ -169,6 +440,105 @@ mod tests {
         assert_eq!(vec, amf0_object);
     }
 
+    #[test]
+    fn test_encode_nested_object_end() {
+        // A new value for every comparison, since the decoded value borrows the encoded buffer.
+        fn value<'a>() -> Amf0Value<'a> {
+            Amf0Value::Object(Cow::Owned(vec![
+                (
+                    "outer".into(),
+                    Amf0Value::Object(Cow::Owned(vec![("inner".into(), Amf0Value::Boolean(true))])),
+                ),
+                ("after".into(), Amf0Value::Null),
+            ]))
+        }
+
+        // ECMA arrays are prefixed with their number of properties, the root has two and the nested object one.
+        for (encoding, marker, root_count, nested_count) in [
+            (Amf0ObjectEncoding::Object, Amf0Marker::Object, &[][..], &[][..]),
+            (
+                Amf0ObjectEncoding::EcmaArray,
+                Amf0Marker::EcmaArray,
+                &[0x00, 0x00, 0x00, 0x02][..],
+                &[0x00, 0x00, 0x00, 0x01][..],
+            ),
+        ] {
+            let mut expected = vec![marker as u8];
+            expected.extend_from_slice(root_count);
+            expected.extend_from_slice(&[0x00, 0x05]);
+            expected.extend_from_slice(b"outer");
+            expected.push(marker as u8);
+            expected.extend_from_slice(nested_count);
+            expected.extend_from_slice(&[0x00, 0x05]);
+            expected.extend_from_slice(b"inner");
+            expected.extend_from_slice(&[Amf0Marker::Boolean as u8, 0x01]);
+            // The end of the nested object, before the next property of the root.
+            expected.extend_from_slice(&[0x00, 0x00, 0x09]);
+            expected.extend_from_slice(&[0x00, 0x05]);
+            expected.extend_from_slice(b"after");
+            expected.push(Amf0Marker::Null as u8);
+            // The end of the root.
+            expected.extend_from_slice(&[0x00, 0x00, 0x09]);
+
+            let mut vec = Vec::<u8>::new();
+            Amf0Encoder::encode_with_options(
+                &mut vec,
+                &value(),
+                &Amf0WriteOptions {
+                    object_encoding: encoding,
+                },
+            )
+            .unwrap();
+            assert_eq!(vec, expected);
+
+            let mut decoder = Amf0Decoder::new(&vec);
+            assert_eq!(decoder.decode().unwrap(), value());
+            assert!(decoder.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_encoded_len() {
+        let values = [
+            Amf0Value::Number(772.161),
+            Amf0Value::Boolean(true),
+            Amf0Value::String(Cow::Borrowed("")),
+            Amf0Value::String(Cow::Borrowed("Hello World")),
+            Amf0Value::Null,
+            Amf0Value::LongString(Cow::Borrowed("test")),
+            Amf0Value::StrictArray(Cow::Owned(vec![])),
+            Amf0Value::StrictArray(Cow::Owned(vec![Amf0Value::Number(1.0), Amf0Value::Null])),
+            Amf0Value::Object(Cow::Owned(vec![])),
+            Amf0Value::Object(Cow::Owned(vec![
+                ("duration".into(), Amf0Value::Number(10.0)),
+                ("encoder".into(), Amf0Value::String("Lavf61.7.100".into())),
+                ("stereo".into(), Amf0Value::Boolean(true)),
+                ("".into(), Amf0Value::Null),
+                (
+                    "nested".into(),
+                    Amf0Value::Object(Cow::Owned(vec![("width".into(), Amf0Value::Number(1920.0))])),
+                ),
+            ])),
+        ];
+
+        for value in values {
+            let mut vec = Vec::<u8>::new();
+            Amf0Encoder::encode(&mut vec, &value).unwrap();
+            assert_eq!(value.encoded_len(), vec.len(), "{value:?}");
+
+            for object_encoding in [Amf0ObjectEncoding::Object, Amf0ObjectEncoding::EcmaArray] {
+                let options = Amf0WriteOptions { object_encoding };
+                let mut vec = Vec::<u8>::new();
+                Amf0Encoder::encode_with_options(&mut vec, &value, &options).unwrap();
+                assert_eq!(value.encoded_len_with_options(&options), vec.len(), "{value:?}");
+            }
+        }
+
+        // Values the encoder does not support use the length from the spec
+        assert_eq!(Amf0Value::ObjectEnd.encoded_len(), 3);
+        assert_eq!(Amf0Value::Unsupported(Amf0Marker::Unsupported).encoded_len(), 1);
+    }
+
     #[test]
     fn test_encode_generic_error_unsupported_type() {
         let mut writer = Vec::<u8>::new();
@@ -177,6 +547,17 @@ mod tests {
         assert!(matches!(result, Err(Amf0WriteError::UnsupportedType(_))));
     }
 
+    #[test]
+    fn test_encode_unsupported_value() {
+        let mut writer = Vec::<u8>::new();
+        let result = Amf0Encoder::encode(&mut writer, &Amf0Value::Unsupported(Amf0Marker::MovieClipMarker));
+        assert!(matches!(
+            result,
+            Err(Amf0WriteError::UnsupportedType(Amf0Marker::MovieClipMarker))
+        ));
+        assert!(writer.is_empty());
+    }
+
     #[test]
     fn test_encode_string_too_long() {
         let long_string = "a".repeat(u16::MAX as usize + 1);
@@ -184,4 +565,124 @@ mod tests {
         let result = Amf0Encoder::encode_string(&mut writer, &long_string);
         assert!(matches!(result, Err(Amf0WriteError::NormalStringTooLong)));
     }
+
+    #[test]
+    fn test_encode_object_key_too_long() {
+        let long_key = "a".repeat(u16::MAX as usize + 1);
+        let mut writer = Vec::<u8>::new();
+        let result = Amf0Encoder::encode_object(&mut writer, &[(long_key.into(), Amf0Value::Null)]);
+        assert!(matches!(result, Err(Amf0WriteError::NormalStringTooLong)));
+    }
+
+    /// A writer that accepts `capacity` bytes and then fails.
+    struct FailingWriter {
+        capacity: usize,
+        written: Vec<u8>,
+    }
+
+    impl io::Write for FailingWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.written.len() >= self.capacity {
+                return Err(io::Error::new(io::ErrorKind::BrokenPipe, "writer closed"));
+            }
+
+            let len = buf.len().min(self.capacity - self.written.len());
+            self.written.extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_encode_failing_writer() {
+        let mut writer = FailingWriter {
+            capacity: 0,
+            written: Vec::new(),
+        };
+        let result = Amf0Encoder::encode_number(&mut writer, 1.0);
+        assert!(matches!(result, Err(Amf0WriteError::Io(ref e)) if e.kind() == io::ErrorKind::BrokenPipe));
+
+        // partial write in the middle of an object
+        let mut writer = FailingWriter {
+            capacity: 5,
+            written: Vec::new(),
+        };
+        let result = Amf0Encoder::encode(
+            &mut writer,
+            &Amf0Value::Object(vec![("test".into(), Amf0Value::String("value".into()))].into()),
+        );
+        match result {
+            Err(Amf0WriteError::Io(e)) => {
+                assert_eq!(e.kind(), io::ErrorKind::BrokenPipe);
+                assert_eq!(e.to_string(), "writer closed");
+            }
+            r => panic!("unexpected result: {:?}", r),
+        }
+        assert_eq!(writer.written, vec![Amf0Marker::Object as u8, 0x00, 0x04, b't', b'e']);
+    }
+
+    /// A writer that accepts at most `chunk` bytes per write, and is interrupted
+    /// before every other write. Once `capacity` bytes are written it accepts no more.
+    struct TrickleWriter {
+        chunk: usize,
+        capacity: usize,
+        interrupt: bool,
+        written: Vec<u8>,
+    }
+
+    impl io::Write for TrickleWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+
+            let len = buf.len().min(self.chunk).min(self.capacity - self.written.len());
+            self.written.extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_encode_partial_writes() {
+        let value = Amf0Value::Object(
+            vec![
+                ("code".into(), Amf0Value::String("NetStream.Publish.Start".into())),
+                ("level".into(), Amf0Value::String("status".into())),
+                ("clientid".into(), Amf0Value::Number(1.0)),
+            ]
+            .into(),
+        );
+
+        let mut expected = Vec::new();
+        Amf0Encoder::encode(&mut expected, &value).unwrap();
+
+        // Short and interrupted writes are retried
+        let mut writer = TrickleWriter {
+            chunk: 3,
+            capacity: usize::MAX,
+            interrupt: false,
+            written: Vec::new(),
+        };
+        Amf0Encoder::encode(&mut writer, &value).unwrap();
+        assert_eq!(writer.written, expected);
+
+        // The writer stops accepting data in the middle of the value
+        let mut writer = TrickleWriter {
+            chunk: 3,
+            capacity: 10,
+            interrupt: false,
+            written: Vec::new(),
+        };
+        let result = Amf0Encoder::encode(&mut writer, &value);
+        assert!(matches!(result, Err(Amf0WriteError::Io(ref e)) if e.kind() == io::ErrorKind::WriteZero));
+        assert_eq!(writer.written, expected[..10]);
+    }
 }
@@ -17,6 +17,9 @@ pub enum Amf0ReadError {
     /// An IO error occurred.
     #[error("io error: {0}")]
     Io(#[from] io::Error),
+    /// A length prefix declared more data than is left in the buffer.
+    #[error("unexpected end of input")]
+    UnexpectedEof,
     /// A wrong type was encountered. Created when using
     /// `Amf0Decoder::next_with_type` and the next value is not the expected
     /// type.
@@ -30,6 +33,12 @@ pub enum Amf0WriteError {
     /// A normal string was too long.
     #[error("normal string too long")]
     NormalStringTooLong,
+    /// A long string was too long.
+    #[error("long string too long")]
+    LongStringTooLong,
+    /// An array had more elements than fit in its 4 byte count.
+    #[error("array too long")]
+    ArrayTooLong,
     /// An IO error occurred.
     #[error("io error: {0}")]
     Io(#[from] io::Error),
@@ -69,6 +78,7 @@ mod tests {
                 Amf0ReadError::Io(Cursor::new(Vec::<u8>::new()).read_u8().unwrap_err()),
                 "io error: failed to fill whole buffer",
             ),
+            (Amf0ReadError::UnexpectedEof, "unexpected end of input"),
         ];
 
         for (err, expected) in cases {
@@ -88,6 +98,8 @@ mod tests {
                 "io error: failed to fill whole buffer",
             ),
             (Amf0WriteError::NormalStringTooLong, "normal string too long"),
+            (Amf0WriteError::LongStringTooLong, "long string too long"),
+            (Amf0WriteError::ArrayTooLong, "array too long"),
         ];
 
         for (err, expected) in cases {
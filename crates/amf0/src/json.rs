@@ -0,0 +1,133 @@
+//! Conversions between [`Amf0Value`] and [`serde_json::Value`].
+//!
+//! The conversions are lossy in some cases:
+//! - AMF0 only has doubles, so JSON integers are converted to [`Amf0Value::Number`].
+//!   Integers larger than 2^53 lose precision. Numbers without a fractional part are
+//!   converted back to JSON integers, so `1.0` becomes `1`.
+//! - Non finite numbers cannot be represented in JSON and are converted to `null`.
+//! - Values without a JSON representation, such as [`Amf0Value::ObjectEnd`] and
+//!   [`Amf0Value::Unsupported`], are converted to `null`. AMF0 dates are not supported
+//!   by the decoder, so they cannot be converted either.
+//! - ECMA arrays are decoded as objects, so they become JSON objects and not arrays.
+//! - JSON objects do not keep duplicate keys, the last value wins. The order of the
+//!   keys is only kept if `serde_json` is built with the `preserve_order` feature.
+//! - [`Amf0Value::String`] and [`Amf0Value::LongString`] both become JSON strings. JSON
+//!   strings become a long string if they are too long for a string.
+
+use std::borrow::Cow;
+
+use crate::Amf0Value;
+
+/// The largest integer a double can represent exactly.
+const MAX_SAFE_INTEGER: f64 = 9007199254740992.0;
+
+impl From<serde_json::Value> for Amf0Value<'static> {
+    fn from(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => Self::Null,
+            serde_json::Value::Bool(b) => Self::Boolean(b),
+            // Without the `arbitrary_precision` feature every JSON number fits into a double.
+            serde_json::Value::Number(n) => Self::Number(n.as_f64().unwrap_or(f64::NAN)),
+            serde_json::Value::String(s) if s.len() > u16::MAX as usize => Self::LongString(Cow::Owned(s)),
+            serde_json::Value::String(s) => Self::String(Cow::Owned(s)),
+            serde_json::Value::Array(a) => Self::StrictArray(a.into_iter().map(Self::from).collect()),
+            serde_json::Value::Object(o) => {
+                Self::Object(o.into_iter().map(|(k, v)| (Cow::Owned(k), Self::from(v))).collect())
+            }
+        }
+    }
+}
+
+impl From<&Amf0Value<'_>> for serde_json::Value {
+    fn from(value: &Amf0Value<'_>) -> Self {
+        match value {
+            Amf0Value::Number(n) if n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER => Self::from(*n as i64),
+            Amf0Value::Number(n) => serde_json::Number::from_f64(*n).map_or(Self::Null, Self::Number),
+            Amf0Value::Boolean(b) => Self::Bool(*b),
+            Amf0Value::String(s) | Amf0Value::LongString(s) => Self::String(s.to_string()),
+            Amf0Value::Object(o) => Self::Object(o.iter().map(|(k, v)| (k.to_string(), Self::from(v))).collect()),
+            Amf0Value::StrictArray(a) => Self::Array(a.iter().map(Self::from).collect()),
+            Amf0Value::Null | Amf0Value::ObjectEnd | Amf0Value::Unsupported(_) => Self::Null,
+        }
+    }
+}
+
+impl From<Amf0Value<'_>> for serde_json::Value {
+    fn from(value: Amf0Value<'_>) -> Self {
+        Self::from(&value)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(test, coverage_nightly), coverage(off))]
+mod tests {
+    use std::borrow::Cow;
+
+    use serde_json::json;
+
+    use crate::{Amf0Marker, Amf0Value};
+
+    #[test]
+    fn test_json_round_trip() {
+        let metadata = json!({
+            "duration": 0,
+            "width": 1280,
+            "height": 720,
+            "framerate": 29.97,
+            "videocodecid": 7,
+            "stereo": true,
+            "encoder": "obs-output module (libobs version 30.0.2)",
+            "tags": ["live", "gaming"],
+            "nested": { "empty": [], "null": null },
+        });
+
+        let value = Amf0Value::from(metadata.clone());
+        assert_eq!(value.get_f64("width"), Some(1280.0));
+        assert_eq!(value.get_f64("framerate"), Some(29.97));
+        assert_eq!(value.get_bool("stereo"), Some(true));
+        assert_eq!(value.get_str("encoder"), Some("obs-output module (libobs version 30.0.2)"));
+        assert_eq!(
+            value.get("tags"),
+            Some(&Amf0Value::StrictArray(Cow::Owned(vec![
+                Amf0Value::String(Cow::Borrowed("live")),
+                Amf0Value::String(Cow::Borrowed("gaming")),
+            ])))
+        );
+        assert_eq!(value.get("nested").and_then(|n| n.get("null")), Some(&Amf0Value::Null));
+
+        assert_eq!(serde_json::Value::from(&value), metadata);
+        assert_eq!(serde_json::Value::from(value), metadata);
+
+        for scalar in [json!(null), json!(false), json!(-3), json!(0.5), json!("")] {
+            assert_eq!(serde_json::Value::from(Amf0Value::from(scalar.clone())), scalar);
+        }
+    }
+
+    #[test]
+    fn test_json_lossy() {
+        // Doubles without a fractional part become integers.
+        assert_eq!(serde_json::Value::from(Amf0Value::Number(1.0)), json!(1));
+        assert_eq!(serde_json::Value::from(Amf0Value::Number(1e300)), json!(1e300));
+        assert_eq!(serde_json::Value::from(Amf0Value::Number(f64::NAN)), json!(null));
+        assert_eq!(serde_json::Value::from(Amf0Value::Number(f64::INFINITY)), json!(null));
+
+        assert_eq!(serde_json::Value::from(Amf0Value::ObjectEnd), json!(null));
+        assert_eq!(serde_json::Value::from(Amf0Value::Unsupported(Amf0Marker::Date)), json!(null));
+        assert_eq!(
+            serde_json::Value::from(Amf0Value::LongString(Cow::Borrowed("long"))),
+            json!("long")
+        );
+
+        // Duplicate keys are collapsed, the last value wins.
+        let object = Amf0Value::Object(Cow::Owned(vec![
+            (Cow::Borrowed("key"), Amf0Value::Number(1.0)),
+            (Cow::Borrowed("key"), Amf0Value::Number(2.0)),
+        ]));
+        assert_eq!(serde_json::Value::from(object), json!({ "key": 2 }));
+
+        // Strings that do not fit into a string are converted to a long string.
+        let long = "a".repeat(u16::MAX as usize + 1);
+        assert!(matches!(Amf0Value::from(json!(long)), Amf0Value::LongString(_)));
+        assert!(matches!(Amf0Value::from(json!("short")), Amf0Value::String(_)));
+    }
+}
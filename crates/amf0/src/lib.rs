@@ -33,8 +33,10 @@ mod decode;
 mod define;
 mod encode;
 mod errors;
+#[cfg(feature = "serde_json")]
+mod json;
 
 pub use crate::decode::Amf0Decoder;
 pub use crate::define::{Amf0Marker, Amf0Value};
-pub use crate::encode::Amf0Encoder;
+pub use crate::encode::{Amf0Encoder, Amf0ObjectEncoding, Amf0WriteOptions};
 pub use crate::errors::{Amf0ReadError, Amf0WriteError};
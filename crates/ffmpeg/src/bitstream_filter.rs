@@ -0,0 +1,229 @@
+use std::ffi::CString;
+
+use crate::error::{FfmpegError, FfmpegErrorCode};
+use crate::ffi::*;
+use crate::packet::Packet;
+use crate::rational::Rational;
+use crate::smart_object::SmartPtr;
+use crate::stream::Stream;
+
+/// A bitstream filter. Used to modify the encoded data of packets without decoding them,
+/// for example `h264_mp4toannexb` converts H.264 packets from mp4 to Annex B.
+pub struct BitstreamFilter(SmartPtr<AVBSFContext>);
+
+/// Safety: `BitstreamFilter` is safe to send between threads.
+unsafe impl Send for BitstreamFilter {}
+
+impl BitstreamFilter {
+    /// Creates a new bitstream filter with the given name, for the packets of the given stream.
+    ///
+    /// Returns [`FfmpegError::NoFilter`] if there is no bitstream filter with that name.
+    pub fn new(name: &str, stream: &Stream<'_>) -> Result<Self, FfmpegError> {
+        let name = CString::new(name).or(Err(FfmpegError::Arguments("name must not contain a nul byte")))?;
+        let codec_params = stream
+            .codec_parameters()
+            .ok_or(FfmpegError::Arguments("stream has no codec parameters"))?;
+
+        // Safety: `av_bsf_get_by_name` is safe to call, `name` is a valid c string.
+        let filter = unsafe { av_bsf_get_by_name(name.as_ptr()) };
+        if filter.is_null() {
+            return Err(FfmpegError::NoFilter);
+        }
+
+        let mut context = std::ptr::null_mut();
+
+        // Safety: `filter` is a valid pointer, and `context` is a valid pointer to write to.
+        FfmpegErrorCode(unsafe { av_bsf_alloc(filter, &mut context) }).result()?;
+
+        let destructor = |ptr: &mut *mut AVBSFContext| {
+            // Safety: The pointer here is valid.
+            unsafe { av_bsf_free(ptr) };
+        };
+
+        // Safety: `context` is a valid pointer, and `destructor` has been setup to free the context.
+        let mut context = unsafe { SmartPtr::wrap_non_null(context, destructor) }.ok_or(FfmpegError::Alloc)?;
+
+        let context_mut = context.as_deref_mut_except();
+
+        // Safety: `par_in` is allocated by `av_bsf_alloc`, and `codec_params` is a valid pointer.
+        FfmpegErrorCode(unsafe { avcodec_parameters_copy(context_mut.par_in, codec_params) }).result()?;
+        context_mut.time_base_in = stream.time_base().into();
+
+        // Safety: `context` is a valid pointer.
+        FfmpegErrorCode(unsafe { av_bsf_init(context.as_mut_ptr()) }).result()?;
+
+        Ok(Self(context))
+    }
+
+    /// Returns a pointer to the bitstream filter context.
+    pub const fn as_ptr(&self) -> *const AVBSFContext {
+        self.0.as_ptr()
+    }
+
+    /// Returns a mutable pointer to the bitstream filter context.
+    pub const fn as_mut_ptr(&mut self) -> *mut AVBSFContext {
+        self.0.as_mut_ptr()
+    }
+
+    /// Returns the time base of the filtered packets.
+    pub fn time_base(&self) -> Rational {
+        self.0.as_deref_except().time_base_out.into()
+    }
+
+    /// Sends a packet to the bitstream filter.
+    pub fn send_packet(&mut self, packet: &Packet) -> Result<(), FfmpegError> {
+        // The filter takes ownership of the packet's data, so we give it a new reference.
+        let mut packet = packet.clone();
+
+        // Safety: `packet` is a valid pointer, and `self.0` is a valid pointer.
+        FfmpegErrorCode(unsafe { av_bsf_send_packet(self.0.as_mut_ptr(), packet.as_mut_ptr()) }).result()?;
+        Ok(())
+    }
+
+    /// Sends an end-of-file packet to the bitstream filter.
+    pub fn send_eof(&mut self) -> Result<(), FfmpegError> {
+        // Safety: `self.0` is a valid pointer.
+        FfmpegErrorCode(unsafe { av_bsf_send_packet(self.0.as_mut_ptr(), std::ptr::null_mut()) }).result()?;
+        Ok(())
+    }
+
+    /// Receives a filtered packet from the bitstream filter.
+    ///
+    /// Returns `Ok(None)` if more packets need to be sent, or the end of the stream was reached.
+    pub fn receive_packet(&mut self) -> Result<Option<Packet>, FfmpegError> {
+        let mut packet = Packet::new()?;
+
+        // Safety: `packet` is a valid pointer, and `self.0` is a valid pointer.
+        match FfmpegErrorCode(unsafe { av_bsf_receive_packet(self.0.as_mut_ptr(), packet.as_mut_ptr()) }) {
+            FfmpegErrorCode::Eagain | FfmpegErrorCode::Eof => Ok(None),
+            code if code.is_success() => Ok(Some(packet)),
+            code => Err(FfmpegError::Code(code)),
+        }
+    }
+
+    /// Sends a packet to the bitstream filter, and returns an iterator over the filtered packets
+    /// that are ready.
+    ///
+    /// If the packet cannot be sent, the iterator only yields the error.
+    pub fn filter(&mut self, packet: &Packet) -> impl Iterator<Item = Result<Packet, FfmpegError>> + '_ {
+        let error = self.send_packet(packet).err();
+        self.drain(error)
+    }
+
+    /// Sends an end-of-file packet to the bitstream filter, and returns an iterator over the
+    /// remaining filtered packets.
+    pub fn flush(&mut self) -> impl Iterator<Item = Result<Packet, FfmpegError>> + '_ {
+        let error = self.send_eof().err();
+        self.drain(error)
+    }
+
+    fn drain(&mut self, mut error: Option<FfmpegError>) -> impl Iterator<Item = Result<Packet, FfmpegError>> + '_ {
+        let sent = error.is_none();
+
+        std::iter::from_fn(move || match error.take() {
+            Some(err) => Some(Err(err)),
+            None if sent => self.receive_packet().transpose(),
+            None => None,
+        })
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(test, coverage_nightly), coverage(off))]
+mod tests {
+    use crate::AVMediaType;
+    use crate::bitstream_filter::BitstreamFilter;
+    use crate::error::FfmpegError;
+    use crate::io::Input;
+
+    #[test]
+    fn test_bitstream_filter() {
+        let mut input = Input::open("../../assets/avc_aac.mp4").expect("Failed to open valid file");
+        let (mut filter, stream_index, time_base) = {
+            let streams = input.streams();
+            let stream = streams.best(AVMediaType::Video).expect("Expected a video stream");
+            let filter = BitstreamFilter::new("h264_mp4toannexb", &stream).expect("Failed to create bitstream filter");
+            (filter, stream.index(), stream.time_base())
+        };
+
+        let mut filtered = Vec::new();
+        while let Some(packet) = input.receive_packet().expect("Failed to receive packet") {
+            if packet.stream_index() != stream_index {
+                continue;
+            }
+
+            filter.send_packet(&packet).expect("Failed to send packet");
+            while let Some(packet) = filter.receive_packet().expect("Failed to receive filtered packet") {
+                filtered.push(packet);
+            }
+        }
+
+        filter.send_eof().expect("Failed to send eof");
+        while let Some(packet) = filter.receive_packet().expect("Failed to receive filtered packet") {
+            filtered.push(packet);
+        }
+
+        assert!(!filtered.is_empty(), "Expected filtered packets");
+        for packet in &filtered {
+            assert!(
+                packet.data().starts_with(&[0, 0, 0, 1]) || packet.data().starts_with(&[0, 0, 1]),
+                "Expected an Annex B start code"
+            );
+        }
+
+        assert!(!filter.as_ptr().is_null());
+        assert!(!filter.as_mut_ptr().is_null());
+        assert_eq!(filter.time_base(), time_base);
+    }
+
+    #[test]
+    fn test_bitstream_filter_hevc() {
+        let mut input = Input::open("../../assets/hevc_aac_fragmented.mp4").expect("Failed to open valid file");
+        let (mut filter, stream_index) = {
+            let streams = input.streams();
+            let stream = streams.best(AVMediaType::Video).expect("Expected a video stream");
+            let filter = BitstreamFilter::new("hevc_mp4toannexb", &stream).expect("Failed to create bitstream filter");
+            (filter, stream.index())
+        };
+
+        let packet = std::iter::from_fn(|| input.receive_packet().expect("Failed to receive packet"))
+            .find(|packet| packet.stream_index() == stream_index)
+            .expect("Expected a video packet");
+        assert!(
+            !packet.data().starts_with(&[0, 0, 0, 1]),
+            "Expected length prefixed nal units"
+        );
+
+        let filtered = filter
+            .filter(&packet)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("Failed to filter packet");
+        assert_eq!(filtered.len(), 1);
+
+        // The parameter sets from the extradata are inserted before the first keyframe, starting with the VPS.
+        let data = filtered[0].data();
+        assert!(data.starts_with(&[0, 0, 0, 1]), "Expected an Annex B start code");
+        assert_eq!((data[4] >> 1) & 0x3f, 32);
+
+        assert_eq!(filter.flush().count(), 0);
+
+        // The filter does not accept packets after it was flushed.
+        assert!(filter.filter(&packet).collect::<Result<Vec<_>, _>>().is_err());
+    }
+
+    #[test]
+    fn test_bitstream_filter_not_found() {
+        let input = Input::open("../../assets/avc_aac.mp4").expect("Failed to open valid file");
+        let streams = input.streams();
+        let stream = streams.best(AVMediaType::Video).expect("Expected a video stream");
+
+        assert!(matches!(
+            BitstreamFilter::new("not_a_filter", &stream),
+            Err(FfmpegError::NoFilter)
+        ));
+        assert!(matches!(
+            BitstreamFilter::new("h264\0mp4toannexb", &stream),
+            Err(FfmpegError::Arguments(_))
+        ));
+    }
+}
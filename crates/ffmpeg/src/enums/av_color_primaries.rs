@@ -0,0 +1,105 @@
+use nutype_enum::nutype_enum;
+
+use crate::ffi::*;
+
+const _: () = {
+    assert!(std::mem::size_of::<AVColorPrimaries>() == std::mem::size_of_val(&AVCOL_PRI_UNSPECIFIED));
+};
+
+nutype_enum! {
+    /// Chromaticity coordinates of the source primaries, FFmpeg's `AVColorPrimaries`.
+    ///
+    /// The values are the `ColourPrimaries` code points of ISO/IEC 23091-4 / ITU-T H.273,
+    /// which are also used by H.264 and H.265.
+    ///
+    /// See the official FFmpeg documentation:
+    /// <https://ffmpeg.org/doxygen/trunk/pixfmt_8h.html>
+    pub enum AVColorPrimaries(i32) {
+        /// Reserved.
+        /// Corresponds to `AVCOL_PRI_RESERVED0`.
+        Reserved0 = AVCOL_PRI_RESERVED0 as _,
+
+        /// ITU-R BT.709, also used by sRGB.
+        /// Corresponds to `AVCOL_PRI_BT709`.
+        Bt709 = AVCOL_PRI_BT709 as _,
+
+        /// Unspecified.
+        /// Corresponds to `AVCOL_PRI_UNSPECIFIED`.
+        Unspecified = AVCOL_PRI_UNSPECIFIED as _,
+
+        /// Reserved.
+        /// Corresponds to `AVCOL_PRI_RESERVED`.
+        Reserved = AVCOL_PRI_RESERVED as _,
+
+        /// ITU-R BT.470 System M.
+        /// Corresponds to `AVCOL_PRI_BT470M`.
+        Bt470M = AVCOL_PRI_BT470M as _,
+
+        /// ITU-R BT.470 System B, G, also used by BT.601 625 and BT.1358 625.
+        /// Corresponds to `AVCOL_PRI_BT470BG`.
+        Bt470Bg = AVCOL_PRI_BT470BG as _,
+
+        /// SMPTE 170M, also used by BT.601 525 and BT.1358 525.
+        /// Corresponds to `AVCOL_PRI_SMPTE170M`.
+        Smpte170M = AVCOL_PRI_SMPTE170M as _,
+
+        /// SMPTE 240M, identical to SMPTE 170M.
+        /// Corresponds to `AVCOL_PRI_SMPTE240M`.
+        Smpte240M = AVCOL_PRI_SMPTE240M as _,
+
+        /// Generic film, colour filters using Illuminant C.
+        /// Corresponds to `AVCOL_PRI_FILM`.
+        Film = AVCOL_PRI_FILM as _,
+
+        /// ITU-R BT.2020 and BT.2100.
+        /// Corresponds to `AVCOL_PRI_BT2020`.
+        Bt2020 = AVCOL_PRI_BT2020 as _,
+
+        /// SMPTE ST 428-1 (CIE 1931 XYZ).
+        /// Corresponds to `AVCOL_PRI_SMPTE428`.
+        Smpte428 = AVCOL_PRI_SMPTE428 as _,
+
+        /// SMPTE ST 431-2 (DCI-P3).
+        /// Corresponds to `AVCOL_PRI_SMPTE431`.
+        Smpte431 = AVCOL_PRI_SMPTE431 as _,
+
+        /// SMPTE ST 432-1 (Display P3).
+        /// Corresponds to `AVCOL_PRI_SMPTE432`.
+        Smpte432 = AVCOL_PRI_SMPTE432 as _,
+
+        /// EBU Tech. 3213-E.
+        /// Corresponds to `AVCOL_PRI_EBU3213`.
+        Ebu3213 = AVCOL_PRI_EBU3213 as _,
+    }
+}
+
+impl AVColorPrimaries {
+    /// Returns the primaries for a `colour_primaries` code point of ISO/IEC 23091-4.
+    ///
+    /// Reserved code points, and code points that FFmpeg does not know, are mapped to
+    /// [`AVColorPrimaries::Unspecified`].
+    pub const fn from_code_point(code_point: u8) -> Self {
+        match code_point {
+            1 | 4..=12 | 22 => Self(code_point as i32),
+            _ => Self::Unspecified,
+        }
+    }
+}
+
+impl PartialEq<i32> for AVColorPrimaries {
+    fn eq(&self, other: &i32) -> bool {
+        self.0 == *other
+    }
+}
+
+impl From<u32> for AVColorPrimaries {
+    fn from(value: u32) -> Self {
+        AVColorPrimaries(value as i32)
+    }
+}
+
+impl From<AVColorPrimaries> for u32 {
+    fn from(value: AVColorPrimaries) -> Self {
+        value.0 as u32
+    }
+}
@@ -0,0 +1,52 @@
+use nutype_enum::nutype_enum;
+
+use crate::ffi::*;
+
+const _: () = {
+    assert!(std::mem::size_of::<AVColorRange>() == std::mem::size_of_val(&AVCOL_RANGE_UNSPECIFIED));
+};
+
+nutype_enum! {
+    /// The range of the luma and chroma sample values, FFmpeg's `AVColorRange`.
+    ///
+    /// See the official FFmpeg documentation:
+    /// <https://ffmpeg.org/doxygen/trunk/pixfmt_8h.html>
+    pub enum AVColorRange(i32) {
+        /// Unspecified.
+        /// Corresponds to `AVCOL_RANGE_UNSPECIFIED`.
+        Unspecified = AVCOL_RANGE_UNSPECIFIED as _,
+
+        /// Limited range, for example 16 to 235 for 8-bit luma. Also called TV or MPEG range.
+        /// Corresponds to `AVCOL_RANGE_MPEG`.
+        Mpeg = AVCOL_RANGE_MPEG as _,
+
+        /// Full range, for example 0 to 255 for 8-bit luma. Also called PC or JPEG range.
+        /// Corresponds to `AVCOL_RANGE_JPEG`.
+        Jpeg = AVCOL_RANGE_JPEG as _,
+    }
+}
+
+impl AVColorRange {
+    /// Returns the range for a `video_full_range_flag`, as signalled by H.264 and H.265.
+    pub const fn from_full_range(full_range: bool) -> Self {
+        if full_range { Self::Jpeg } else { Self::Mpeg }
+    }
+}
+
+impl PartialEq<i32> for AVColorRange {
+    fn eq(&self, other: &i32) -> bool {
+        self.0 == *other
+    }
+}
+
+impl From<u32> for AVColorRange {
+    fn from(value: u32) -> Self {
+        AVColorRange(value as i32)
+    }
+}
+
+impl From<AVColorRange> for u32 {
+    fn from(value: AVColorRange) -> Self {
+        value.0 as u32
+    }
+}
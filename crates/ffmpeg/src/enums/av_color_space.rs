@@ -0,0 +1,109 @@
+use nutype_enum::nutype_enum;
+
+use crate::ffi::*;
+
+const _: () = {
+    assert!(std::mem::size_of::<AVColorSpace>() == std::mem::size_of_val(&AVCOL_SPC_UNSPECIFIED));
+};
+
+nutype_enum! {
+    /// The matrix coefficients used to derive luma and chroma from RGB, FFmpeg's `AVColorSpace`.
+    ///
+    /// The values are the `MatrixCoefficients` code points of ISO/IEC 23091-4 / ITU-T H.273,
+    /// which are also used by H.264 and H.265.
+    ///
+    /// See the official FFmpeg documentation:
+    /// <https://ffmpeg.org/doxygen/trunk/pixfmt_8h.html>
+    pub enum AVColorSpace(i32) {
+        /// The identity matrix, used for RGB and GBR.
+        /// Corresponds to `AVCOL_SPC_RGB`.
+        Rgb = AVCOL_SPC_RGB as _,
+
+        /// ITU-R BT.709, also used by BT.1361.
+        /// Corresponds to `AVCOL_SPC_BT709`.
+        Bt709 = AVCOL_SPC_BT709 as _,
+
+        /// Unspecified.
+        /// Corresponds to `AVCOL_SPC_UNSPECIFIED`.
+        Unspecified = AVCOL_SPC_UNSPECIFIED as _,
+
+        /// Reserved.
+        /// Corresponds to `AVCOL_SPC_RESERVED`.
+        Reserved = AVCOL_SPC_RESERVED as _,
+
+        /// FCC Title 47 Code of Federal Regulations 73.682 (a)(20).
+        /// Corresponds to `AVCOL_SPC_FCC`.
+        Fcc = AVCOL_SPC_FCC as _,
+
+        /// ITU-R BT.470 System B, G, also used by BT.601 625 and BT.1358 625.
+        /// Corresponds to `AVCOL_SPC_BT470BG`.
+        Bt470Bg = AVCOL_SPC_BT470BG as _,
+
+        /// SMPTE 170M, also used by BT.601 525 and BT.1358 525.
+        /// Corresponds to `AVCOL_SPC_SMPTE170M`.
+        Smpte170M = AVCOL_SPC_SMPTE170M as _,
+
+        /// SMPTE 240M.
+        /// Corresponds to `AVCOL_SPC_SMPTE240M`.
+        Smpte240M = AVCOL_SPC_SMPTE240M as _,
+
+        /// YCgCo.
+        /// Corresponds to `AVCOL_SPC_YCGCO`.
+        YCgCo = AVCOL_SPC_YCGCO as _,
+
+        /// ITU-R BT.2020 non-constant luminance.
+        /// Corresponds to `AVCOL_SPC_BT2020_NCL`.
+        Bt2020Ncl = AVCOL_SPC_BT2020_NCL as _,
+
+        /// ITU-R BT.2020 constant luminance.
+        /// Corresponds to `AVCOL_SPC_BT2020_CL`.
+        Bt2020Cl = AVCOL_SPC_BT2020_CL as _,
+
+        /// SMPTE ST 2085.
+        /// Corresponds to `AVCOL_SPC_SMPTE2085`.
+        Smpte2085 = AVCOL_SPC_SMPTE2085 as _,
+
+        /// Chromaticity-derived non-constant luminance.
+        /// Corresponds to `AVCOL_SPC_CHROMA_DERIVED_NCL`.
+        ChromaDerivedNcl = AVCOL_SPC_CHROMA_DERIVED_NCL as _,
+
+        /// Chromaticity-derived constant luminance.
+        /// Corresponds to `AVCOL_SPC_CHROMA_DERIVED_CL`.
+        ChromaDerivedCl = AVCOL_SPC_CHROMA_DERIVED_CL as _,
+
+        /// ITU-R BT.2100 ICtCp.
+        /// Corresponds to `AVCOL_SPC_ICTCP`.
+        ICtCp = AVCOL_SPC_ICTCP as _,
+    }
+}
+
+impl AVColorSpace {
+    /// Returns the color space for a `matrix_coefficients` code point of ISO/IEC 23091-4.
+    ///
+    /// Reserved code points, and code points that FFmpeg does not know, are mapped to
+    /// [`AVColorSpace::Unspecified`].
+    pub const fn from_code_point(code_point: u8) -> Self {
+        match code_point {
+            0 | 1 | 4..=14 => Self(code_point as i32),
+            _ => Self::Unspecified,
+        }
+    }
+}
+
+impl PartialEq<i32> for AVColorSpace {
+    fn eq(&self, other: &i32) -> bool {
+        self.0 == *other
+    }
+}
+
+impl From<u32> for AVColorSpace {
+    fn from(value: u32) -> Self {
+        AVColorSpace(value as i32)
+    }
+}
+
+impl From<AVColorSpace> for u32 {
+    fn from(value: AVColorSpace) -> Self {
+        value.0 as u32
+    }
+}
@@ -0,0 +1,125 @@
+use nutype_enum::nutype_enum;
+
+use crate::ffi::*;
+
+const _: () = {
+    assert!(std::mem::size_of::<AVColorTransferCharacteristic>() == std::mem::size_of_val(&AVCOL_TRC_UNSPECIFIED));
+};
+
+nutype_enum! {
+    /// Opto-electronic transfer characteristics, FFmpeg's `AVColorTransferCharacteristic`.
+    ///
+    /// The values are the `TransferCharacteristics` code points of ISO/IEC 23091-4 / ITU-T H.273,
+    /// which are also used by H.264 and H.265.
+    ///
+    /// See the official FFmpeg documentation:
+    /// <https://ffmpeg.org/doxygen/trunk/pixfmt_8h.html>
+    pub enum AVColorTransferCharacteristic(i32) {
+        /// Reserved.
+        /// Corresponds to `AVCOL_TRC_RESERVED0`.
+        Reserved0 = AVCOL_TRC_RESERVED0 as _,
+
+        /// ITU-R BT.709, also used by BT.1361.
+        /// Corresponds to `AVCOL_TRC_BT709`.
+        Bt709 = AVCOL_TRC_BT709 as _,
+
+        /// Unspecified.
+        /// Corresponds to `AVCOL_TRC_UNSPECIFIED`.
+        Unspecified = AVCOL_TRC_UNSPECIFIED as _,
+
+        /// Reserved.
+        /// Corresponds to `AVCOL_TRC_RESERVED`.
+        Reserved = AVCOL_TRC_RESERVED as _,
+
+        /// Assumed display gamma 2.2, ITU-R BT.470 System M.
+        /// Corresponds to `AVCOL_TRC_GAMMA22`.
+        Gamma22 = AVCOL_TRC_GAMMA22 as _,
+
+        /// Assumed display gamma 2.8, ITU-R BT.470 System B, G.
+        /// Corresponds to `AVCOL_TRC_GAMMA28`.
+        Gamma28 = AVCOL_TRC_GAMMA28 as _,
+
+        /// SMPTE 170M, also used by BT.601 525 and 625.
+        /// Corresponds to `AVCOL_TRC_SMPTE170M`.
+        Smpte170M = AVCOL_TRC_SMPTE170M as _,
+
+        /// SMPTE 240M.
+        /// Corresponds to `AVCOL_TRC_SMPTE240M`.
+        Smpte240M = AVCOL_TRC_SMPTE240M as _,
+
+        /// Linear transfer characteristics.
+        /// Corresponds to `AVCOL_TRC_LINEAR`.
+        Linear = AVCOL_TRC_LINEAR as _,
+
+        /// Logarithmic transfer characteristic, 100:1 range.
+        /// Corresponds to `AVCOL_TRC_LOG`.
+        Log = AVCOL_TRC_LOG as _,
+
+        /// Logarithmic transfer characteristic, 100 * sqrt(10):1 range.
+        /// Corresponds to `AVCOL_TRC_LOG_SQRT`.
+        LogSqrt = AVCOL_TRC_LOG_SQRT as _,
+
+        /// IEC 61966-2-4 (xvYCC).
+        /// Corresponds to `AVCOL_TRC_IEC61966_2_4`.
+        Iec61966_2_4 = AVCOL_TRC_IEC61966_2_4 as _,
+
+        /// ITU-R BT.1361 extended colour gamut.
+        /// Corresponds to `AVCOL_TRC_BT1361_ECG`.
+        Bt1361Ecg = AVCOL_TRC_BT1361_ECG as _,
+
+        /// IEC 61966-2-1 (sRGB or sYCC).
+        /// Corresponds to `AVCOL_TRC_IEC61966_2_1`.
+        Iec61966_2_1 = AVCOL_TRC_IEC61966_2_1 as _,
+
+        /// ITU-R BT.2020 for 10-bit systems.
+        /// Corresponds to `AVCOL_TRC_BT2020_10`.
+        Bt2020_10 = AVCOL_TRC_BT2020_10 as _,
+
+        /// ITU-R BT.2020 for 12-bit systems.
+        /// Corresponds to `AVCOL_TRC_BT2020_12`.
+        Bt2020_12 = AVCOL_TRC_BT2020_12 as _,
+
+        /// SMPTE ST 2084 (PQ), also used by ITU-R BT.2100 for 10, 12 and 16-bit systems.
+        /// Corresponds to `AVCOL_TRC_SMPTE2084`.
+        Smpte2084 = AVCOL_TRC_SMPTE2084 as _,
+
+        /// SMPTE ST 428-1.
+        /// Corresponds to `AVCOL_TRC_SMPTE428`.
+        Smpte428 = AVCOL_TRC_SMPTE428 as _,
+
+        /// ARIB STD-B67 (HLG), also used by ITU-R BT.2100.
+        /// Corresponds to `AVCOL_TRC_ARIB_STD_B67`.
+        AribStdB67 = AVCOL_TRC_ARIB_STD_B67 as _,
+    }
+}
+
+impl AVColorTransferCharacteristic {
+    /// Returns the transfer characteristic for a `transfer_characteristics` code point of ISO/IEC 23091-4.
+    ///
+    /// Reserved code points, and code points that FFmpeg does not know, are mapped to
+    /// [`AVColorTransferCharacteristic::Unspecified`].
+    pub const fn from_code_point(code_point: u8) -> Self {
+        match code_point {
+            1 | 4..=18 => Self(code_point as i32),
+            _ => Self::Unspecified,
+        }
+    }
+}
+
+impl PartialEq<i32> for AVColorTransferCharacteristic {
+    fn eq(&self, other: &i32) -> bool {
+        self.0 == *other
+    }
+}
+
+impl From<u32> for AVColorTransferCharacteristic {
+    fn from(value: u32) -> Self {
+        AVColorTransferCharacteristic(value as i32)
+    }
+}
+
+impl From<AVColorTransferCharacteristic> for u32 {
+    fn from(value: AVColorTransferCharacteristic) -> Self {
+        value.0 as u32
+    }
+}
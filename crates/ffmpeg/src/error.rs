@@ -29,6 +29,9 @@ pub enum FfmpegError {
     /// An error that occurs when the arguments are invalid.
     #[error("invalid arguments: {0}")]
     Arguments(&'static str),
+    /// An error that occurs when an operation does not complete before its deadline.
+    #[error("operation timed out")]
+    Timeout,
 }
 
 nutype_enum! {
@@ -241,6 +244,7 @@ mod tests {
                 FfmpegError::Arguments("invalid argument example"),
                 "invalid arguments: invalid argument example",
             ),
+            (FfmpegError::Timeout, "operation timed out"),
         ];
 
         for (error, expected) in cases {
@@ -1,17 +1,28 @@
 use std::ffi::CStr;
 
-use super::internal::{Inner, InnerOptions, read_packet, seek};
+use super::internal::{
+    Deadline, Inner, InnerOptions, InterruptCallback, deadline_callback, interrupt_callback, read_packet,
+    read_packet_buffered, seek,
+};
+use crate::bitstream_filter::BitstreamFilter;
+use crate::codec::DecoderCodec;
 use crate::consts::{Const, DEFAULT_BUFFER_SIZE};
 use crate::dict::Dictionary;
 use crate::error::{FfmpegError, FfmpegErrorCode};
 use crate::ffi::*;
 use crate::packet::{Packet, Packets};
+use crate::rational::Rational;
 use crate::smart_object::SmartObject;
-use crate::stream::Streams;
+use crate::stream::{StreamInfo, Streams};
+use crate::{AVMediaType, AVSeekFlag, AVSeekWhence};
 
 /// Represents an input stream.
 pub struct Input<T: Send + Sync> {
     inner: SmartObject<Inner<T>>,
+    retry_policy: Option<RetryPolicy>,
+    stats: DemuxStats,
+    // This must be dropped after the context, which holds a pointer to it.
+    _interrupt_callback: Option<InterruptCallback>,
 }
 
 /// Safety: `Input` is safe to send between threads.
@@ -25,7 +36,55 @@ pub struct InputOptions<I: FnMut() -> bool> {
     /// The dictionary for the input stream.
     pub dictionary: Dictionary,
     /// The interrupt callback for the input stream.
+    ///
+    /// This is called periodically by ffmpeg during blocking operations, such as opening the input
+    /// or reading from it. Returning `true` aborts the operation with an error.
     pub interrupt_callback: Option<I>,
+    /// The policy used to retry reads after a transient failure.
+    /// When `None`, the first read error is returned immediately.
+    pub retry_policy: Option<RetryPolicy>,
+    /// The maximum duration of the input to analyze when probing stream information
+    /// (`analyzeduration`). When `None`, the ffmpeg default is used.
+    pub analyze_duration: Option<std::time::Duration>,
+    /// The maximum number of bytes to read when probing the format and stream information
+    /// (`probesize`). Must be at least 32. When `None`, the ffmpeg default is used.
+    pub probe_size: Option<usize>,
+    /// Whether to generate missing presentation timestamps (`fflags=+genpts`).
+    /// This sets [`AVFmtFlags::GenPts`](crate::AVFmtFlags::GenPts) on the format context, and is
+    /// kept alongside any other `fflags` in the dictionary.
+    pub generate_pts: bool,
+    /// The maximum total number of bytes to read from the input.
+    ///
+    /// Once the cap is reached, reading more data fails with
+    /// [`FfmpegErrorCode::Exit`](crate::error::FfmpegErrorCode::Exit), which aborts opening the input or
+    /// receiving packets. This bounds the resources used by untrusted inputs. Seeking back does not
+    /// reset the count. Only supported for readers, not when opening a path. When `None`, there is no cap.
+    pub max_read_bytes: Option<u64>,
+    /// The name of the decoder to use for all video streams, such as `hevc`.
+    ///
+    /// This overrides the codec detected by the demuxer when probing stream information, and the
+    /// codec id reported by the codec parameters of the streams. It applies to every video stream
+    /// of the input, it cannot be set for a single stream. The decoder is not checked against the
+    /// data of the streams. Returns [`FfmpegError::NoDecoder`] if there is no decoder with that name,
+    /// or [`FfmpegError::Arguments`] if it is not a video decoder. When `None`, the detected codec is used.
+    pub video_decoder: Option<String>,
+    /// The name of the decoder to use for all audio streams, such as `aac`.
+    ///
+    /// See [`InputOptions::video_decoder`] for how this is applied.
+    pub audio_decoder: Option<String>,
+}
+
+/// The decoders forced by [`InputOptions::video_decoder`] and [`InputOptions::audio_decoder`].
+#[derive(Default)]
+struct ForcedDecoders {
+    video: Option<DecoderCodec>,
+    audio: Option<DecoderCodec>,
+}
+
+impl ForcedDecoders {
+    const fn is_empty(&self) -> bool {
+        self.video.is_none() && self.audio.is_none()
+    }
 }
 
 /// Default implementation for `InputOptions`.
@@ -35,7 +94,222 @@ impl Default for InputOptions<fn() -> bool> {
             buffer_size: DEFAULT_BUFFER_SIZE,
             dictionary: Dictionary::new(),
             interrupt_callback: None,
+            retry_policy: None,
+            analyze_duration: None,
+            probe_size: None,
+            generate_pts: false,
+            max_read_bytes: None,
+            video_decoder: None,
+            audio_decoder: None,
+        }
+    }
+}
+
+impl<I: FnMut() -> bool> InputOptions<I> {
+    /// Sets the interrupt callback, see [`InputOptions::interrupt_callback`].
+    ///
+    /// This replaces any interrupt callback already set, and keeps every other option.
+    pub fn with_interrupt_callback<F: FnMut() -> bool>(self, interrupt_callback: F) -> InputOptions<F> {
+        InputOptions {
+            buffer_size: self.buffer_size,
+            dictionary: self.dictionary,
+            interrupt_callback: Some(interrupt_callback),
+            retry_policy: self.retry_policy,
+            analyze_duration: self.analyze_duration,
+            probe_size: self.probe_size,
+            generate_pts: self.generate_pts,
+            max_read_bytes: self.max_read_bytes,
+            video_decoder: self.video_decoder,
+            audio_decoder: self.audio_decoder,
+        }
+    }
+
+    /// Sets the interrupt callback to abort blocking operations once the context is done.
+    ///
+    /// This replaces any interrupt callback already set.
+    #[cfg(feature = "context")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "context")))]
+    pub fn with_context(self, ctx: scuffle_context::Context) -> InputOptions<impl FnMut() -> bool + Send + 'static> {
+        self.with_interrupt_callback(move || ctx.is_done())
+    }
+
+    /// Writes the typed demuxer options into the dictionary, overriding any value already set for them.
+    fn apply_demuxer_options(&mut self) -> Result<(), FfmpegError> {
+        if let Some(analyze_duration) = self.analyze_duration {
+            let micros = i64::try_from(analyze_duration.as_micros()).unwrap_or(i64::MAX);
+            self.dictionary.set("analyzeduration", micros.to_string())?;
+        }
+
+        if let Some(probe_size) = self.probe_size {
+            self.dictionary.set("probesize", probe_size.to_string())?;
+        }
+
+        if self.generate_pts {
+            let fflags = match self.dictionary.get("fflags") {
+                Some(fflags) => format!("{}+genpts", fflags.to_string_lossy()),
+                None => "+genpts".to_owned(),
+            };
+            self.dictionary.set("fflags", fflags)?;
+        }
+
+        Ok(())
+    }
+
+    /// Looks up the decoders forced by the options.
+    fn forced_decoders(&self) -> Result<ForcedDecoders, FfmpegError> {
+        fn find(
+            name: Option<&str>,
+            media_type: AVMediaType,
+            error: &'static str,
+        ) -> Result<Option<DecoderCodec>, FfmpegError> {
+            let Some(name) = name else {
+                return Ok(None);
+            };
+
+            let codec = DecoderCodec::by_name(name).ok_or(FfmpegError::NoDecoder)?;
+            // Safety: The codec was returned by ffmpeg, so the pointer is valid.
+            let codec_type = unsafe { (*codec.as_ptr()).type_ };
+            if AVMediaType(codec_type) != media_type {
+                return Err(FfmpegError::Arguments(error));
+            }
+
+            Ok(Some(codec))
+        }
+
+        Ok(ForcedDecoders {
+            video: find(
+                self.video_decoder.as_deref(),
+                AVMediaType::Video,
+                "video_decoder must be a video decoder",
+            )?,
+            audio: find(
+                self.audio_decoder.as_deref(),
+                AVMediaType::Audio,
+                "audio_decoder must be an audio decoder",
+            )?,
+        })
+    }
+}
+
+/// A policy for retrying reads from an input after a transient failure.
+///
+/// When [`Input::receive_packet`] fails with a retryable error, the error state of the
+/// I/O context is cleared so it can be read from again, and the read is retried after
+/// waiting for the backoff. If the input is seekable, a packet read while the reader failed
+/// may be incomplete, so it is read again from the last position instead of being returned.
+///
+/// The input is never reopened, the same reader or connection is read from again. This
+/// recovers from errors after which the reader can still be read, but not from a dropped
+/// network connection of an input opened with [`Input::open_with_options`], which has to be
+/// opened again.
+///
+/// The following errors are considered retryable:
+/// - `AVERROR(EIO)`, which is what any error returned by the underlying reader is reported as.
+/// - `AVERROR(EAGAIN)`
+/// - `AVERROR(ETIMEDOUT)`
+///
+/// End of file, invalid data and all other errors are never retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of consecutive retries before the error is returned.
+    pub max_retries: u32,
+    /// The time to wait before the first retry. This is doubled after every failed retry.
+    pub backoff: std::time::Duration,
+    /// Whether to seek back to the position of the last successful read before retrying.
+    /// This has no effect if the input is not seekable.
+    pub seek_to_last_position: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            backoff: std::time::Duration::from_millis(100),
+            seek_to_last_position: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns true if the error code is considered transient and the read should be retried.
+    pub const fn is_retryable(code: FfmpegErrorCode) -> bool {
+        code.0 == AVERROR(EIO) || code.0 == AVERROR(EAGAIN) || code.0 == AVERROR(ETIMEDOUT)
+    }
+
+    /// Returns the time to wait before the given retry attempt (starting at 0).
+    pub fn backoff_for(&self, attempt: u32) -> std::time::Duration {
+        self.backoff.saturating_mul(1u32.checked_shl(attempt).unwrap_or(u32::MAX))
+    }
+}
+
+/// A program of an input, such as a service of a multi-program MPEG-TS input.
+///
+/// See [`Input::programs`].
+#[derive(Debug, Clone)]
+pub struct Program {
+    /// The id of the program. For MPEG-TS this is the program number.
+    pub id: i32,
+    /// The metadata of the program, such as the `service_name`.
+    pub metadata: Dictionary,
+    /// The indices of the streams that belong to the program.
+    pub stream_indices: Vec<usize>,
+}
+
+/// The number of packets and bytes read from a single stream of an [`Input`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamStats {
+    /// The number of packets read from the stream.
+    pub packet_count: u64,
+    /// The number of bytes of packet data read from the stream.
+    pub byte_count: u64,
+}
+
+/// The packets and bytes read from an [`Input`] since it was opened, per stream.
+///
+/// See [`Input::stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DemuxStats {
+    streams: Vec<StreamStats>,
+}
+
+impl DemuxStats {
+    /// Returns the statistics of the stream at the given index.
+    ///
+    /// Returns `None` if no packet was read from a stream with that index yet.
+    pub fn stream(&self, stream_index: usize) -> Option<StreamStats> {
+        self.streams.get(stream_index).copied()
+    }
+
+    /// Returns the statistics of every stream, indexed by the stream index.
+    ///
+    /// Streams after the last stream a packet was read from are not included.
+    pub fn streams(&self) -> &[StreamStats] {
+        &self.streams
+    }
+
+    /// Returns the total number of packets read from all streams.
+    pub fn packet_count(&self) -> u64 {
+        self.streams.iter().map(|s| s.packet_count).sum()
+    }
+
+    /// Returns the total number of bytes of packet data read from all streams.
+    pub fn byte_count(&self) -> u64 {
+        self.streams.iter().map(|s| s.byte_count).sum()
+    }
+
+    fn record(&mut self, packet: &Packet) {
+        let Ok(index) = usize::try_from(packet.stream_index()) else {
+            return;
+        };
+
+        if index >= self.streams.len() {
+            // Streams can be added while reading, so the statistics grow as packets are seen.
+            self.streams.resize(index + 1, StreamStats::default());
         }
+
+        let stream = &mut self.streams[index];
+        stream.packet_count += 1;
+        stream.byte_count += packet.data().len() as u64;
     }
 }
 
@@ -46,18 +320,78 @@ impl<T: std::io::Read + Send + Sync> Input<T> {
     }
 
     /// Creates a new `Input` instance with custom options.
-    pub fn with_options(input: T, options: &mut InputOptions<impl FnMut() -> bool>) -> Result<Self, FfmpegError> {
+    ///
+    /// The interrupt callback is moved out of the options into the input.
+    pub fn with_options(
+        input: T,
+        options: &mut InputOptions<impl FnMut() -> bool + Send + 'static>,
+    ) -> Result<Self, FfmpegError> {
+        options.apply_demuxer_options()?;
+        let decoders = options.forced_decoders()?;
+
         Self::create_input(
             Inner::new(
                 input,
                 InnerOptions {
                     buffer_size: options.buffer_size,
                     read_fn: Some(read_packet::<T>),
+                    max_read_bytes: options.max_read_bytes,
                     ..Default::default()
                 },
             )?,
             None,
             &mut options.dictionary,
+            options.retry_policy,
+            options.interrupt_callback.take(),
+            decoders,
+        )
+    }
+
+    /// Creates a new `Input` instance from a buffered reader, with default options.
+    ///
+    /// See [`Input::from_bufread_with_options`].
+    pub fn from_bufread(input: T) -> Result<Self, FfmpegError>
+    where
+        T: std::io::BufRead,
+    {
+        Self::from_bufread_with_options(input, &mut InputOptions::default())
+    }
+
+    /// Creates a new `Input` instance from a buffered reader, with custom options.
+    ///
+    /// Instead of calling [`Read::read`](std::io::Read::read), ffmpeg's buffer is filled directly
+    /// from the buffer of the reader with [`BufRead::fill_buf`](std::io::BufRead::fill_buf) and
+    /// [`BufRead::consume`](std::io::BufRead::consume). This avoids an extra copy for readers whose
+    /// `read` copies through an intermediate buffer, and readers that already hold the data in
+    /// memory. It is not faster for a [`BufReader`](std::io::BufReader), whose `read` already
+    /// copies from its buffer, or bypasses it for reads larger than its capacity.
+    ///
+    /// The interrupt callback is moved out of the options into the input.
+    pub fn from_bufread_with_options(
+        input: T,
+        options: &mut InputOptions<impl FnMut() -> bool + Send + 'static>,
+    ) -> Result<Self, FfmpegError>
+    where
+        T: std::io::BufRead,
+    {
+        options.apply_demuxer_options()?;
+        let decoders = options.forced_decoders()?;
+
+        Self::create_input(
+            Inner::new(
+                input,
+                InnerOptions {
+                    buffer_size: options.buffer_size,
+                    read_fn: Some(read_packet_buffered::<T>),
+                    max_read_bytes: options.max_read_bytes,
+                    ..Default::default()
+                },
+            )?,
+            None,
+            &mut options.dictionary,
+            options.retry_policy,
+            options.interrupt_callback.take(),
+            decoders,
         )
     }
 
@@ -70,10 +404,16 @@ impl<T: std::io::Read + Send + Sync> Input<T> {
     }
 
     /// Creates a new `Input` instance with seekable options.
-    pub fn seekable_with_options(input: T, mut options: InputOptions<impl FnMut() -> bool>) -> Result<Self, FfmpegError>
+    pub fn seekable_with_options(
+        input: T,
+        mut options: InputOptions<impl FnMut() -> bool + Send + 'static>,
+    ) -> Result<Self, FfmpegError>
     where
         T: std::io::Seek,
     {
+        options.apply_demuxer_options()?;
+        let decoders = options.forced_decoders()?;
+
         Self::create_input(
             Inner::new(
                 input,
@@ -81,11 +421,15 @@ impl<T: std::io::Read + Send + Sync> Input<T> {
                     buffer_size: options.buffer_size,
                     read_fn: Some(read_packet::<T>),
                     seek_fn: Some(seek::<T>),
+                    max_read_bytes: options.max_read_bytes,
                     ..Default::default()
                 },
             )?,
             None,
             &mut options.dictionary,
+            options.retry_policy,
+            options.interrupt_callback.take(),
+            decoders,
         )
     }
 }
@@ -101,6 +445,29 @@ impl<T: Send + Sync> Input<T> {
         self.inner.inner_mut().context.as_mut_ptr()
     }
 
+    /// Consumes the `Input` and returns the underlying reader.
+    ///
+    /// The format context is closed before the reader is returned, so ffmpeg no longer
+    /// holds a pointer to it.
+    pub fn into_reader(self) -> T {
+        let Self {
+            inner,
+            _interrupt_callback: interrupt_callback,
+            ..
+        } = self;
+
+        // We close the context ourselves, so the destructor of the smart object must not run.
+        let mut inner = inner.into_inner();
+
+        // Safety: The context was opened by `avformat_open_input` and is only closed here.
+        unsafe { avformat_close_input(inner.context.as_mut()) };
+
+        // The context no longer references the interrupt callback or the reader.
+        drop(interrupt_callback);
+
+        *inner.data.take().expect("reader is always present")
+    }
+
     /// Returns the streams of the input stream.
     pub const fn streams(&self) -> Const<'_, Streams<'_>> {
         // Safety: See the documentation of `Streams::new`.
@@ -116,6 +483,156 @@ impl<T: Send + Sync> Input<T> {
         unsafe { Streams::new(self.inner.inner_mut().context.as_mut_ptr()) }
     }
 
+    /// Returns the average frame rate of the stream at the given index.
+    ///
+    /// See [`Stream::avg_frame_rate`](crate::stream::Stream::avg_frame_rate).
+    pub fn avg_frame_rate(&self, stream_index: usize) -> Option<Rational> {
+        self.streams().iter().nth(stream_index).map(|s| s.avg_frame_rate())
+    }
+
+    /// Returns the real (lowest common) frame rate of the stream at the given index.
+    ///
+    /// This differs from the average frame rate for variable frame rate content.
+    /// See [`Stream::r_frame_rate`](crate::stream::Stream::r_frame_rate).
+    pub fn r_frame_rate(&self, stream_index: usize) -> Option<Rational> {
+        self.streams().iter().nth(stream_index).map(|s| s.r_frame_rate())
+    }
+
+    /// Returns the frame rate of the stream at the given index as guessed by ffmpeg.
+    ///
+    /// See [`Stream::guess_frame_rate`](crate::stream::Stream::guess_frame_rate).
+    pub fn frame_rate(&self, stream_index: usize) -> Option<Rational> {
+        self.streams().iter().nth(stream_index).map(|s| s.guess_frame_rate())
+    }
+
+    /// Returns a summary of every stream of the input, in the order of the stream indices.
+    ///
+    /// See [`Stream::info`](crate::stream::Stream::info).
+    pub fn stream_infos(&self) -> Vec<StreamInfo> {
+        self.streams().iter().filter_map(|s| s.info()).collect()
+    }
+
+    /// Returns the overall bitrate of the input in bits per second, as reported by the container.
+    ///
+    /// Returns `None` if the bitrate is unknown. See [`Input::estimated_bit_rate`] to fall back to
+    /// an estimate from the size and duration of the input.
+    pub fn bit_rate(&self) -> Option<i64> {
+        let bit_rate = self.inner.inner_ref().context.as_deref_except().bit_rate;
+        (bit_rate > 0).then_some(bit_rate)
+    }
+
+    /// Returns the overall bitrate of the input in bits per second.
+    ///
+    /// If the container does not report a bitrate, it is estimated from the size and the duration
+    /// of the input. Returns `None` if either of them is unknown, for example when the reader
+    /// is not seekable.
+    pub fn estimated_bit_rate(&mut self) -> Option<i64> {
+        if let Some(bit_rate) = self.bit_rate() {
+            return Some(bit_rate);
+        }
+
+        let context = self.inner.inner_mut().context.as_deref_mut_except();
+
+        // The duration is `AV_NOPTS_VALUE` when it is unknown, which is negative.
+        let duration = context.duration;
+        if duration <= 0 || context.pb.is_null() {
+            return None;
+        }
+
+        // Safety: `pb` is a valid pointer owned by the context.
+        let size = unsafe { avio_size(context.pb) };
+        if size <= 0 {
+            return None;
+        }
+
+        // The duration is in `AV_TIME_BASE` units (microseconds).
+        let bit_rate = size as i128 * 8 * AV_TIME_BASE as i128 / duration as i128;
+        i64::try_from(bit_rate).ok()
+    }
+
+    /// Returns the programs of the input, along with the streams that belong to each of them.
+    ///
+    /// Only some formats have programs, such as MPEG-TS. This is empty for inputs without them.
+    pub fn programs(&self) -> Vec<Program> {
+        let context = self.inner.inner_ref().context.as_deref_except();
+        if context.programs.is_null() {
+            return Vec::new();
+        }
+
+        // Safety: `programs` is a valid array of `nb_programs` pointers.
+        let programs = unsafe { std::slice::from_raw_parts(context.programs, context.nb_programs as usize) };
+
+        programs
+            .iter()
+            .filter_map(|&program| {
+                // Safety: The programs in the array are either null or valid pointers.
+                let program = unsafe { program.as_ref() }?;
+
+                let stream_indices = if program.stream_index.is_null() {
+                    Vec::new()
+                } else {
+                    // Safety: `stream_index` is a valid array of `nb_stream_indexes` indices.
+                    unsafe { std::slice::from_raw_parts(program.stream_index, program.nb_stream_indexes as usize) }
+                        .iter()
+                        .map(|&index| index as usize)
+                        .collect()
+                };
+
+                // Safety: The metadata is owned by the program, which outlives this borrow.
+                // The dictionary is copied so the returned program does not reference the context.
+                let metadata = unsafe { Dictionary::from_ptr_ref(program.metadata) }.clone();
+
+                Some(Program {
+                    id: program.id,
+                    metadata,
+                    stream_indices,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the attached pictures of the input, such as the cover art of an audio file.
+    ///
+    /// These are not returned by [`Input::receive_packet`], see
+    /// [`Stream::attached_pic`](crate::stream::Stream::attached_pic).
+    pub fn attached_pictures(&self) -> Vec<Packet> {
+        self.streams().iter().filter_map(|s| s.attached_pic()).collect()
+    }
+
+    /// Returns the index of the subtitle stream with the forced disposition, such as subtitles
+    /// that only translate foreign dialogue and are shown even when subtitles are disabled.
+    ///
+    /// If a language is given, only streams whose `language` metadata matches it (ignoring case)
+    /// are considered, usually an ISO 639-2 code such as `eng`. If multiple streams match, the
+    /// first one is returned. Returns `None` if there is no such stream.
+    pub fn forced_subtitle_stream(&self, language: Option<&str>) -> Option<usize> {
+        let streams = self.streams();
+        let stream = streams.iter().find(|stream| {
+            let is_subtitle = stream
+                .codec_parameters()
+                .is_some_and(|params| AVMediaType(params.codec_type) == AVMediaType::Subtitle);
+            let is_forced = stream.disposition() & AV_DISPOSITION_FORCED as i32 != 0;
+            let matches_language = language.is_none_or(|language| {
+                stream
+                    .metadata()
+                    .get("language")
+                    .is_some_and(|l| l.to_bytes().eq_ignore_ascii_case(language.as_bytes()))
+            });
+
+            is_subtitle && is_forced && matches_language
+        })?;
+
+        Some(stream.index() as usize)
+    }
+
+    /// Returns the number of packets and bytes read from each stream since the input was opened.
+    ///
+    /// Only packets returned by [`Input::receive_packet`] and the functions built on it are counted,
+    /// packets read directly through [`Input::packets`] are not.
+    pub fn stats(&self) -> DemuxStats {
+        self.stats.clone()
+    }
+
     /// Returns the packets of the input stream.
     pub const fn packets(&mut self) -> Packets<'_> {
         // Safety: See the documentation of `Packets::new`.
@@ -123,11 +640,284 @@ impl<T: Send + Sync> Input<T> {
     }
 
     /// Receives a packet from the input stream.
+    ///
+    /// Returns `Ok(None)` only at the end of the input. If the reader reports that no data is
+    /// available yet (by returning [`std::io::ErrorKind::WouldBlock`]), this returns
+    /// [`FfmpegErrorCode::Eagain`] and can be called again once more data is available.
+    ///
+    /// If the input was created with a [`RetryPolicy`], retryable errors are retried
+    /// according to the policy before being returned.
     pub fn receive_packet(&mut self) -> Result<Option<Packet>, FfmpegError> {
-        self.packets().receive()
+        let result = self.receive_packet_uncounted();
+        if let Ok(Some(packet)) = &result {
+            self.stats.record(packet);
+        }
+
+        result
+    }
+
+    fn receive_packet_uncounted(&mut self) -> Result<Option<Packet>, FfmpegError> {
+        let Some(policy) = self.retry_policy else {
+            let position = self.io_position();
+            let result = self.packets().receive();
+
+            // Demuxers often report a failed read as the end of the file, so we check the
+            // error state of the io context to only return `Ok(None)` at the real end of the file.
+            return match (result, self.io_error()) {
+                (result @ Ok(Some(_)), _) => result,
+                (_, Some(FfmpegErrorCode::Eagain)) | (Err(FfmpegError::Code(FfmpegErrorCode::Eagain)), None) => {
+                    // Clear the error so the input can be read from again once data is available.
+                    self.reset_io(position);
+                    Err(FfmpegError::Code(FfmpegErrorCode::Eagain))
+                }
+                (_, Some(code)) => Err(FfmpegError::Code(code)),
+                (result, None) => result,
+            };
+        };
+
+        let position = self.io_position().filter(|_| policy.seek_to_last_position);
+        let mut attempt = 0;
+
+        loop {
+            let result = self.packets().receive();
+
+            // Demuxers often report a failed read as the end of the file, so we check the
+            // error state of the io context as well as the returned error.
+            let code = match (&result, self.io_error()) {
+                // The packet may have been cut short by the failed read, so it is read again if we can seek back.
+                (Ok(Some(_)), Some(code)) if RetryPolicy::is_retryable(code) && position.is_some() && self.is_seekable() => {
+                    code
+                }
+                (Ok(Some(_)), _) => return result,
+                (_, Some(code)) => code,
+                (Err(FfmpegError::Code(code)), None) => *code,
+                _ => return result,
+            };
+
+            if attempt >= policy.max_retries || !RetryPolicy::is_retryable(code) {
+                return Err(FfmpegError::Code(code));
+            }
+
+            std::thread::sleep(policy.backoff_for(attempt));
+            attempt += 1;
+
+            self.reset_io(position);
+        }
+    }
+
+    /// Receives a packet from the input stream, giving up once `timeout` has passed.
+    ///
+    /// This behaves like [`Input::receive_packet`], but while the reader reports that no data is
+    /// available, the read is retried until the timeout. Blocking io done by ffmpeg itself, such as
+    /// reading from a network url, is aborted through the interrupt callback once the timeout has passed.
+    /// A reader passed to [`Input::new`] which blocks cannot be interrupted, and should use its own read
+    /// timeout and return [`std::io::ErrorKind::WouldBlock`] instead.
+    ///
+    /// Returns [`FfmpegError::Timeout`] if no packet was received in time, after which this can be
+    /// called again to keep waiting, or the input can be dropped to reconnect.
+    pub fn receive_packet_timeout(&mut self, timeout: std::time::Duration) -> Result<Option<Packet>, FfmpegError> {
+        const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(10);
+
+        let position = self.io_position();
+        let context = self.inner.inner_mut().context.as_deref_mut_except();
+        let mut deadline = Deadline {
+            deadline: std::time::Instant::now() + timeout,
+            previous: context.interrupt_callback,
+            expired: false,
+        };
+
+        context.interrupt_callback = AVIOInterruptCB {
+            callback: Some(deadline_callback),
+            opaque: (&raw mut deadline) as *mut libc::c_void,
+        };
+
+        let result = loop {
+            match self.receive_packet() {
+                Err(_) if deadline.expired => break Err(FfmpegError::Timeout),
+                Err(FfmpegError::Code(FfmpegErrorCode::Eagain)) => {
+                    let remaining = deadline.deadline.saturating_duration_since(std::time::Instant::now());
+                    if remaining.is_zero() {
+                        break Err(FfmpegError::Timeout);
+                    }
+
+                    std::thread::sleep(remaining.min(POLL_INTERVAL));
+                }
+                result => break result,
+            }
+        };
+
+        // The deadline is dropped at the end of this function, so the previous callback must be restored.
+        self.inner.inner_mut().context.as_deref_mut_except().interrupt_callback = deadline.previous;
+
+        if matches!(result, Err(FfmpegError::Timeout)) {
+            // An interrupted read leaves `AVERROR_EXIT` in the io context, which has to be cleared
+            // for the next call to read from it again.
+            self.reset_io(position);
+        }
+
+        result
+    }
+
+    /// Returns the packets of a single stream, optionally passed through a bitstream filter.
+    ///
+    /// The returned [`ElementaryStream`] yields the packets, and implements [`std::io::Read`] to read
+    /// their data back to back. For example `h264_mp4toannexb` or `hevc_mp4toannexb` can be used
+    /// to extract the Annex B video of an mp4 file. Packets of other streams are discarded.
+    pub fn elementary_stream(
+        &mut self,
+        stream_index: usize,
+        bitstream_filter: Option<&str>,
+    ) -> Result<ElementaryStream<'_, T>, FfmpegError> {
+        let filter = {
+            let streams = self.streams();
+            let stream = streams.iter().nth(stream_index).ok_or(FfmpegError::NoStream)?;
+            bitstream_filter.map(|name| BitstreamFilter::new(name, &stream)).transpose()?
+        };
+
+        Ok(ElementaryStream {
+            input: self,
+            stream_index: stream_index as i32,
+            filter,
+            eof: false,
+            current: None,
+            offset: 0,
+        })
+    }
+
+    /// Returns `true` if the io context of the input supports seeking.
+    ///
+    /// This is `true` for files and urls that support seeking, and for readers opened with
+    /// [`Input::seekable`], and `false` for streamed inputs, such as readers opened with [`Input::new`].
+    /// Seeking a streamed input fails, or is emulated by reading and discarding data.
+    pub fn is_seekable(&self) -> bool {
+        // Safety: `pb` is either null or a valid pointer owned by the context.
+        let Some(pb) = (unsafe { self.inner.inner_ref().context.as_deref_except().pb.as_ref() }) else {
+            return false;
+        };
+
+        pb.seekable & AVIO_SEEKABLE_NORMAL as i32 != 0
+    }
+
+    /// Reads the whole input and returns the `(pts, position)` of every keyframe of the given stream,
+    /// in the order they appear in the input.
+    ///
+    /// Keyframes without a timestamp or byte position are skipped. This reads the input to the end,
+    /// use [`Input::seek_to_keyframe`] with the returned index to seek back into it.
+    pub fn build_keyframe_index(&mut self, stream_index: usize) -> Result<Vec<(i64, i64)>, FfmpegError> {
+        let mut index = Vec::new();
+
+        while let Some(packet) = self.receive_packet()? {
+            if packet.stream_index() as usize != stream_index || !packet.is_key() {
+                continue;
+            }
+
+            if let (Some(pts), Some(pos)) = (packet.pts().or(packet.dts()), packet.pos()) {
+                index.push((pts, pos));
+            }
+        }
+
+        Ok(index)
+    }
+
+    /// Seeks to the byte position of the last keyframe in `index` with a pts less than or equal to `pts`.
+    ///
+    /// The index should be built with [`Input::build_keyframe_index`]. Returns the pts of the keyframe
+    /// that was seeked to, or `None` if there is no keyframe at or before `pts`, in which case the
+    /// input is left unchanged.
+    ///
+    /// This is useful for formats where `av_seek_frame` is imprecise, but requires the input to be
+    /// seekable and the format to support seeking by byte position.
+    pub fn seek_to_keyframe(&mut self, index: &[(i64, i64)], pts: i64) -> Result<Option<i64>, FfmpegError> {
+        let Some(&(keyframe_pts, pos)) = index[..index.partition_point(|&(p, _)| p <= pts)].last() else {
+            return Ok(None);
+        };
+
+        // Safety: The context is valid, the stream index is ignored when seeking by byte position.
+        FfmpegErrorCode(unsafe { av_seek_frame(self.as_mut_ptr(), -1, pos, AVSeekFlag::Byte.into()) }).result()?;
+
+        Ok(Some(keyframe_pts))
+    }
+
+    /// Returns the current position of the io context, if there is one.
+    fn io_position(&mut self) -> Option<i64> {
+        let pb = self.inner.inner_mut().context.as_deref_mut_except().pb;
+        if pb.is_null() {
+            return None;
+        }
+
+        // Safety: `pb` is a valid pointer, seeking 0 bytes from the current position returns the position.
+        let position = unsafe { avio_seek(pb, 0, AVSeekWhence::Current.into()) };
+
+        (position >= 0).then_some(position)
+    }
+
+    /// Returns the error stored in the io context, if there is one.
+    fn io_error(&self) -> Option<FfmpegErrorCode> {
+        // Safety: `pb` is either null or a valid pointer owned by the context.
+        let pb = unsafe { self.inner.inner_ref().context.as_deref_except().pb.as_ref() }?;
+
+        (pb.error < 0).then_some(FfmpegErrorCode(pb.error))
+    }
+
+    /// Clears the error state of the io context so it can be read from again, and
+    /// optionally seeks back to the given position.
+    fn reset_io(&mut self, position: Option<i64>) {
+        // Safety: `pb` is either null or a valid pointer owned by the context.
+        let Some(pb) = (unsafe { self.inner.inner_mut().context.as_deref_mut_except().pb.as_mut() }) else {
+            return;
+        };
+
+        pb.error = 0;
+        pb.eof_reached = 0;
+
+        if let Some(position) = position {
+            if pb.seekable != 0 {
+                // Safety: `pb` is a valid pointer.
+                unsafe { avio_seek(pb, position, AVSeekWhence::Start.into()) };
+            }
+        }
     }
 
-    fn create_input(mut inner: Inner<T>, path: Option<&CStr>, dictionary: &mut Dictionary) -> Result<Self, FfmpegError> {
+    fn create_input(
+        mut inner: Inner<T>,
+        path: Option<&CStr>,
+        dictionary: &mut Dictionary,
+        retry_policy: Option<RetryPolicy>,
+        interrupt: Option<impl FnMut() -> bool + Send + 'static>,
+        decoders: ForcedDecoders,
+    ) -> Result<Self, FfmpegError> {
+        let mut interrupt = interrupt
+            .map(|interrupt| -> InterruptCallback { Box::new(Box::new(interrupt) as Box<dyn FnMut() -> bool + Send>) });
+
+        if interrupt.is_some() || !decoders.is_empty() {
+            if inner.context.as_ptr().is_null() {
+                // The callback and decoders have to be set before opening the input, so we allocate the context ourselves.
+                // Safety: avformat_alloc_context is safe to call
+                *inner.context.as_mut() = unsafe { avformat_alloc_context() };
+            }
+
+            let context = inner.context.as_deref_mut().ok_or(FfmpegError::Alloc)?;
+            if let Some(interrupt) = &mut interrupt {
+                context.interrupt_callback = AVIOInterruptCB {
+                    callback: Some(interrupt_callback),
+                    opaque: &mut **interrupt as *mut Box<dyn FnMut() -> bool + Send> as *mut libc::c_void,
+                };
+            }
+
+            // ffmpeg applies the codec ids to the streams of that media type as packets are read.
+            if let Some(codec) = decoders.video {
+                // Safety: The codec was returned by ffmpeg, so the pointer is valid.
+                context.video_codec_id = unsafe { (*codec.as_ptr()).id };
+                context.video_codec = codec.as_ptr();
+            }
+
+            if let Some(codec) = decoders.audio {
+                // Safety: The codec was returned by ffmpeg, so the pointer is valid.
+                context.audio_codec_id = unsafe { (*codec.as_ptr()).id };
+                context.audio_codec = codec.as_ptr();
+            }
+        }
+
         // Safety: avformat_open_input is safe to call
         FfmpegErrorCode(unsafe {
             avformat_open_input(
@@ -154,29 +944,163 @@ impl<T: Send + Sync> Input<T> {
         // Safety: avformat_find_stream_info is safe to call
         FfmpegErrorCode(unsafe { avformat_find_stream_info(inner.context.as_mut_ptr(), std::ptr::null_mut()) }).result()?;
 
-        Ok(Self { inner })
+        Ok(Self {
+            inner,
+            retry_policy,
+            stats: DemuxStats::default(),
+            _interrupt_callback: interrupt,
+        })
+    }
+}
+
+/// The packets of a single stream of an [`Input`], created with [`Input::elementary_stream`].
+pub struct ElementaryStream<'a, T: Send + Sync> {
+    input: &'a mut Input<T>,
+    stream_index: i32,
+    filter: Option<BitstreamFilter>,
+    eof: bool,
+    current: Option<Packet>,
+    offset: usize,
+}
+
+impl<T: Send + Sync> std::fmt::Debug for ElementaryStream<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ElementaryStream")
+            .field("stream_index", &self.stream_index)
+            .field("filtered", &self.filter.is_some())
+            .field("eof", &self.eof)
+            .finish()
+    }
+}
+
+impl<T: Send + Sync> ElementaryStream<'_, T> {
+    /// Receives the next packet of the stream.
+    ///
+    /// Returns `Ok(None)` at the end of the input, once the bitstream filter has been flushed.
+    pub fn receive_packet(&mut self) -> Result<Option<Packet>, FfmpegError> {
+        loop {
+            if let Some(filter) = &mut self.filter {
+                if let Some(packet) = filter.receive_packet()? {
+                    return Ok(Some(packet));
+                }
+            }
+
+            if self.eof {
+                return Ok(None);
+            }
+
+            match self.input.receive_packet()? {
+                Some(packet) if packet.stream_index() != self.stream_index => {}
+                Some(packet) => match &mut self.filter {
+                    Some(filter) => filter.send_packet(&packet)?,
+                    None => return Ok(Some(packet)),
+                },
+                None => {
+                    self.eof = true;
+                    if let Some(filter) = &mut self.filter {
+                        filter.send_eof()?;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T: Send + Sync> Iterator for ElementaryStream<'_, T> {
+    type Item = Result<Packet, FfmpegError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receive_packet().transpose()
+    }
+}
+
+impl<T: Send + Sync> std::io::Read for ElementaryStream<'_, T> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            if let Some(packet) = &self.current {
+                let data = &packet.data()[self.offset..];
+                if !data.is_empty() {
+                    let len = data.len().min(buf.len());
+                    buf[..len].copy_from_slice(&data[..len]);
+                    self.offset += len;
+                    return Ok(len);
+                }
+            }
+
+            match self.receive_packet() {
+                Ok(packet) => {
+                    self.offset = 0;
+                    self.current = packet;
+                    if self.current.is_none() {
+                        return Ok(0);
+                    }
+                }
+                Err(err) => return Err(std::io::Error::other(err)),
+            }
+        }
     }
 }
 
 impl Input<()> {
     /// Opens an input stream from a file path.
     pub fn open(path: &str) -> Result<Self, FfmpegError> {
+        Self::open_with_options(path, &mut InputOptions::default())
+    }
+
+    /// Opens an input stream from a file path or url with custom options.
+    ///
+    /// The buffer size is not used, since the io context is created by ffmpeg.
+    /// The interrupt callback is moved out of the options into the input.
+    ///
+    /// Returns [`FfmpegError::Arguments`] if [`InputOptions::max_read_bytes`] is set, since the
+    /// reads of the io context cannot be limited.
+    pub fn open_with_options(
+        path: &str,
+        options: &mut InputOptions<impl FnMut() -> bool + Send + 'static>,
+    ) -> Result<Self, FfmpegError> {
+        if options.max_read_bytes.is_some() {
+            return Err(FfmpegError::Arguments("max_read_bytes is only supported for readers"));
+        }
+
+        options.apply_demuxer_options()?;
+        let decoders = options.forced_decoders()?;
+
         // We immediately create an input and setup the inner, before using it.
         // Safety: When we pass this inner to `create_input` with a valid path, the inner will be initialized by ffmpeg using the path.
         let inner = unsafe { Inner::empty() };
 
-        Self::create_input(inner, Some(&std::ffi::CString::new(path).unwrap()), &mut Dictionary::new())
+        Self::create_input(
+            inner,
+            Some(&std::ffi::CString::new(path).unwrap()),
+            &mut options.dictionary,
+            options.retry_policy,
+            options.interrupt_callback.take(),
+            decoders,
+        )
     }
 }
 
 #[cfg(test)]
 #[cfg_attr(all(test, coverage_nightly), coverage(off))]
 mod tests {
-    use std::io::Cursor;
+    use std::io::{Cursor, Read};
 
     use insta::Settings;
 
-    use super::{DEFAULT_BUFFER_SIZE, FfmpegError, Input, InputOptions};
+    use super::{DEFAULT_BUFFER_SIZE, FfmpegError, Input, InputOptions, RetryPolicy};
+    use crate::dict::Dictionary;
+    use crate::error::FfmpegErrorCode;
+    use crate::ffi::{
+        AV_DISPOSITION_FORCED, AV_TIME_BASE, AVERROR, EIO, av_dict_set, av_new_program, av_program_add_stream_index,
+        avio_size,
+    };
+    use crate::rational::Rational;
+    use crate::stream::StreamInfoKind;
+    use crate::{AVCodecID, AVFmtFlags, AVMediaType, AVPixelFormat};
 
     fn configure_insta_filters(settings: &mut Settings) {
         settings.add_filter(r"0x0000000000000000", "[NULL_POINTER]");
@@ -190,6 +1114,316 @@ mod tests {
         assert_eq!(default_options.buffer_size, DEFAULT_BUFFER_SIZE);
         assert!(default_options.dictionary.is_empty());
         assert!(default_options.interrupt_callback.is_none());
+        assert!(default_options.retry_policy.is_none());
+        assert!(default_options.analyze_duration.is_none());
+        assert!(default_options.probe_size.is_none());
+        assert!(!default_options.generate_pts);
+        assert!(default_options.max_read_bytes.is_none());
+        assert!(default_options.video_decoder.is_none());
+        assert!(default_options.audio_decoder.is_none());
+    }
+
+    fn flags<T: Send + Sync>(input: &Input<T>) -> AVFmtFlags {
+        // Safety: `input.as_ptr()` is a valid pointer.
+        AVFmtFlags(unsafe { (*input.as_ptr()).flags })
+    }
+
+    fn read_all<T: Send + Sync>(input: Result<Input<T>, FfmpegError>) -> Result<usize, FfmpegError> {
+        let mut input = input?;
+        let mut packets = 0;
+        while input.receive_packet()?.is_some() {
+            packets += 1;
+        }
+        Ok(packets)
+    }
+
+    #[test]
+    fn test_max_read_bytes() {
+        let data: Vec<u8> = include_bytes!("../../../../assets/avc_aac.flv").to_vec();
+        let limited = |max_read_bytes: u64| InputOptions {
+            max_read_bytes: Some(max_read_bytes),
+            ..Default::default()
+        };
+
+        // Depending on the demuxer, the error is returned when opening the input or reading from it.
+        let result = read_all(Input::with_options(Cursor::new(data.clone()), &mut limited(64)));
+        assert!(result.is_err(), "Expected reading the input to be aborted");
+
+        let result = read_all(Input::seekable_with_options(
+            Cursor::new(include_bytes!("../../../../assets/avc_aac.mp4").to_vec()),
+            limited(64),
+        ));
+        assert!(result.is_err(), "Expected reading the input to be aborted");
+
+        // A reader that ends exactly at the cap is not an error.
+        let expected = read_all(Input::new(Cursor::new(data.clone()))).expect("Failed to read input");
+        let packets = read_all(Input::with_options(
+            Cursor::new(data.clone()),
+            &mut limited(data.len() as u64),
+        ))
+        .expect("Failed to read input");
+        assert!(packets > 0);
+        assert_eq!(packets, expected);
+
+        assert!(matches!(
+            Input::open_with_options("../../assets/avc_aac.mp4", &mut limited(64)),
+            Err(FfmpegError::Arguments(_))
+        ));
+    }
+
+    #[test]
+    fn test_forced_decoder() {
+        let codec_id = |input: &Input<()>, media_type: AVMediaType| {
+            let streams = input.streams();
+            let stream = streams.best(media_type).expect("Expected a stream");
+            let params = stream.codec_parameters().expect("Expected codec parameters");
+            AVCodecID(params.codec_id as _)
+        };
+
+        let input = Input::open("../../assets/avc_aac.mp4").expect("Failed to open input");
+        assert_eq!(codec_id(&input, AVMediaType::Video), AVCodecID::H264);
+
+        let mut options = InputOptions {
+            video_decoder: Some("hevc".into()),
+            ..Default::default()
+        };
+        let input = Input::open_with_options("../../assets/avc_aac.mp4", &mut options).expect("Failed to open input");
+        assert_eq!(codec_id(&input, AVMediaType::Video), AVCodecID::Hevc);
+        assert_eq!(codec_id(&input, AVMediaType::Audio), AVCodecID::Aac);
+
+        let open = |video_decoder: Option<&str>, audio_decoder: Option<&str>| {
+            let mut options = InputOptions {
+                video_decoder: video_decoder.map(Into::into),
+                audio_decoder: audio_decoder.map(Into::into),
+                ..Default::default()
+            };
+            Input::open_with_options("../../assets/avc_aac.mp4", &mut options)
+        };
+
+        assert!(matches!(open(Some("not_a_decoder"), None), Err(FfmpegError::NoDecoder)));
+        assert!(matches!(open(Some("aac"), None), Err(FfmpegError::Arguments(_))));
+        assert!(matches!(open(None, Some("h264")), Err(FfmpegError::Arguments(_))));
+    }
+
+    #[test]
+    fn test_forced_subtitle_stream() {
+        let mut input = Input::open("../../assets/avc_aac.mp4").expect("Failed to open input");
+        assert_eq!(input.forced_subtitle_stream(None), None);
+
+        let mut streams = input.streams_mut();
+        let mut stream = streams.get(1).expect("Expected an audio stream");
+
+        // A forced stream that is not a subtitle stream is ignored.
+        stream.set_disposition(AV_DISPOSITION_FORCED as i32);
+        assert_eq!(input.forced_subtitle_stream(None), None);
+
+        let mut streams = input.streams_mut();
+        let mut stream = streams.get(1).expect("Expected an audio stream");
+        // Safety: The stream is a valid pointer.
+        let codecpar = unsafe { (*stream.as_mut_ptr()).codecpar };
+        // Safety: The codec parameters are a valid pointer owned by the stream.
+        unsafe { (*codecpar).codec_type = AVMediaType::Subtitle.into() };
+        stream.metadata_mut().set("language", "eng").expect("Failed to set language");
+
+        assert_eq!(input.forced_subtitle_stream(None), Some(1));
+        assert_eq!(input.forced_subtitle_stream(Some("eng")), Some(1));
+        assert_eq!(input.forced_subtitle_stream(Some("ENG")), Some(1));
+        assert_eq!(input.forced_subtitle_stream(Some("fre")), None);
+
+        // A subtitle stream without the forced disposition is ignored.
+        let mut streams = input.streams_mut();
+        let mut stream = streams.get(1).expect("Expected a subtitle stream");
+        stream.set_disposition(0);
+        assert_eq!(input.forced_subtitle_stream(None), None);
+    }
+
+    #[test]
+    fn test_from_bufread() {
+        let data: Vec<u8> = include_bytes!("../../../../assets/avc_aac.flv").to_vec();
+        let expected = read_all(Input::new(Cursor::new(data.clone()))).expect("Failed to read input");
+        assert!(expected > 0);
+
+        let file = std::fs::File::open("../../assets/avc_aac.flv").expect("Failed to open file");
+        let packets = read_all(Input::from_bufread(std::io::BufReader::new(file))).expect("Failed to read input");
+        assert_eq!(packets, expected);
+
+        // A buffer smaller than ffmpeg's is read in multiple steps.
+        let file = std::fs::File::open("../../assets/avc_aac.flv").expect("Failed to open file");
+        let reader = std::io::BufReader::with_capacity(100, file);
+        let packets = read_all(Input::from_bufread(reader)).expect("Failed to read input");
+        assert_eq!(packets, expected);
+
+        // A buffer larger than ffmpeg's is consumed in parts.
+        let packets = read_all(Input::from_bufread(data.as_slice())).expect("Failed to read input");
+        assert_eq!(packets, expected);
+
+        let mut options = InputOptions {
+            max_read_bytes: Some(64),
+            ..Default::default()
+        };
+        assert!(read_all(Input::from_bufread_with_options(data.as_slice(), &mut options)).is_err());
+    }
+
+    #[test]
+    fn test_is_seekable() {
+        let data: Vec<u8> = include_bytes!("../../../../assets/avc_aac.mp4").to_vec();
+
+        let input = Input::seekable(Cursor::new(data.clone())).expect("Failed to open input");
+        assert!(input.is_seekable());
+
+        // The reader implements `Seek`, but the input is not created with a seek function.
+        let input = Input::new(Cursor::new(data)).expect("Failed to open input");
+        assert!(!input.is_seekable());
+
+        let input = Input::open("../../assets/avc_aac.mp4").expect("Failed to open input");
+        assert!(input.is_seekable());
+    }
+
+    #[test]
+    fn test_bit_rate() {
+        let mut input = Input::open("../../assets/avc_aac.mp4").expect("Failed to open input");
+        let bit_rate = input.bit_rate().expect("Expected a bitrate");
+        assert!(
+            (10_000..50_000_000).contains(&bit_rate),
+            "Expected a plausible bitrate, got {bit_rate}"
+        );
+        assert_eq!(input.estimated_bit_rate(), Some(bit_rate));
+
+        // Safety: `input.as_mut_ptr()` is a valid pointer.
+        unsafe { (*input.as_mut_ptr()).bit_rate = 0 };
+        assert_eq!(input.bit_rate(), None);
+
+        // Safety: `input.as_ptr()` is a valid pointer.
+        let context = unsafe { &*input.as_ptr() };
+        let duration = context.duration;
+        // Safety: `pb` is a valid pointer owned by the context.
+        let size = unsafe { avio_size(context.pb) };
+        let estimated = input.estimated_bit_rate().expect("Expected an estimated bitrate");
+        assert_eq!(estimated, size * 8 * AV_TIME_BASE as i64 / duration);
+        assert!(
+            (10_000..50_000_000).contains(&estimated),
+            "Expected a plausible bitrate, got {estimated}"
+        );
+
+        // The size of an input that is not seekable is unknown
+        let data: Vec<u8> = include_bytes!("../../../../assets/avc_aac.flv").to_vec();
+        let mut input = Input::new(Cursor::new(data)).expect("Failed to open input");
+        // Safety: `input.as_mut_ptr()` is a valid pointer.
+        unsafe { (*input.as_mut_ptr()).bit_rate = 0 };
+        assert_eq!(input.estimated_bit_rate(), None);
+    }
+
+    #[test]
+    fn test_programs() {
+        let mut input = Input::open("../../assets/avc_aac.mp4").expect("Failed to open input");
+        assert!(input.programs().is_empty(), "Expected no programs in an mp4 input");
+
+        // Safety: `input.as_mut_ptr()` is a valid pointer.
+        let program = unsafe { av_new_program(input.as_mut_ptr(), 1) };
+        assert!(!program.is_null(), "Failed to create program");
+
+        // Safety: `program` is a valid pointer owned by the context.
+        let program = unsafe { &mut *program };
+        // Safety: `program.metadata` is a valid pointer to a dictionary pointer.
+        let ret = unsafe { av_dict_set(&mut program.metadata, c"service_name".as_ptr(), c"Service01".as_ptr(), 0) };
+        FfmpegErrorCode(ret).result().expect("Failed to set metadata");
+
+        // Safety: `input.as_mut_ptr()` is a valid pointer, and the streams exist.
+        unsafe { av_program_add_stream_index(input.as_mut_ptr(), 1, 1) };
+        // Safety: `input.as_mut_ptr()` is a valid pointer, and the streams exist.
+        unsafe { av_program_add_stream_index(input.as_mut_ptr(), 1, 0) };
+
+        let programs = input.programs();
+        assert_eq!(programs.len(), 1);
+        assert_eq!(programs[0].id, 1);
+        assert_eq!(programs[0].stream_indices, vec![1, 0]);
+        assert_eq!(
+            programs[0].metadata.get(c"service_name"),
+            Some(c"Service01"),
+            "Expected the metadata to be copied"
+        );
+    }
+
+    #[test]
+    fn test_generate_pts() {
+        let valid_media_data: Vec<u8> = include_bytes!("../../../../assets/avc_aac.flv").to_vec();
+
+        let mut input = Input::with_options(
+            Cursor::new(valid_media_data.clone()),
+            &mut InputOptions {
+                generate_pts: true,
+                ..Default::default()
+            },
+        )
+        .expect("Failed to open input");
+        assert!(flags(&input) & AVFmtFlags::GenPts != 0, "Expected GenPts to be set");
+
+        let mut packets = 0;
+        while let Some(packet) = input.receive_packet().expect("Failed to receive packet") {
+            assert!(packet.pts().is_some(), "Expected every packet to have a pts");
+            packets += 1;
+        }
+        assert!(packets > 0);
+
+        // Other fflags in the dictionary are kept
+        let input = Input::with_options(
+            Cursor::new(valid_media_data.clone()),
+            &mut InputOptions {
+                generate_pts: true,
+                dictionary: Dictionary::try_from_iter([("fflags", "+igndts")]).expect("Failed to create dictionary"),
+                ..Default::default()
+            },
+        )
+        .expect("Failed to open input");
+        assert!(flags(&input) & AVFmtFlags::GenPts != 0, "Expected GenPts to be set");
+        assert!(flags(&input) & AVFmtFlags::IgnoreDts != 0, "Expected IgnoreDts to be set");
+
+        let input = Input::new(Cursor::new(valid_media_data)).expect("Failed to open input");
+        assert!(
+            flags(&input) & AVFmtFlags::GenPts == 0,
+            "Expected GenPts to be unset by default"
+        );
+    }
+
+    #[test]
+    fn test_demuxer_options() {
+        let valid_media_data: Vec<u8> = include_bytes!("../../../../assets/avc_aac_large.mp4").to_vec();
+
+        let input = Input::seekable_with_options(
+            Cursor::new(valid_media_data),
+            InputOptions {
+                analyze_duration: Some(std::time::Duration::from_millis(1)),
+                probe_size: Some(32),
+                ..Default::default()
+            },
+        )
+        .expect("Failed to open input with a small probesize");
+
+        // Safety: `input.as_ptr()` is a valid pointer.
+        let context = unsafe { &*input.as_ptr() };
+        assert_eq!(context.probesize, 32);
+        assert_eq!(context.max_analyze_duration, 1000);
+
+        // The stream information is still available
+        assert_eq!(input.streams().len(), 2);
+        assert_eq!(input.avg_frame_rate(0), Some(Rational::static_new::<60, 1>()));
+    }
+
+    #[test]
+    fn test_demuxer_options_override_dictionary() {
+        let valid_media_data: Vec<u8> = include_bytes!("../../../../assets/avc_aac_large.mp4").to_vec();
+
+        let mut options = InputOptions {
+            probe_size: Some(4096),
+            dictionary: Dictionary::try_from_iter([("probesize", "1000000")]).expect("Failed to create dictionary"),
+            ..Default::default()
+        };
+        let input = Input::with_options(Cursor::new(valid_media_data), &mut options).expect("Failed to open input");
+
+        // Safety: `input.as_ptr()` is a valid pointer.
+        let context = unsafe { &*input.as_ptr() };
+        assert_eq!(context.probesize, 4096);
+        assert_eq!(context.max_analyze_duration, 0, "Expected the default analyzeduration");
     }
 
     #[test]
@@ -240,6 +1474,30 @@ mod tests {
         assert!(result.is_ok(), "Expected success but got error");
     }
 
+    #[test]
+    fn test_into_reader() {
+        let valid_media_data: Vec<u8> = include_bytes!("../../../../assets/avc_aac.mp4").to_vec();
+        let len = valid_media_data.len() as u64;
+        let mut input = Input::seekable(Cursor::new(valid_media_data)).expect("Failed to create input");
+
+        while input.receive_packet().expect("Failed to receive packet").is_some() {}
+
+        let data = input.into_reader();
+        assert!(data.position() > 0, "Expected the reader to have been read from");
+        assert!(data.position() <= len);
+        assert_eq!(data.get_ref().len() as u64, len);
+
+        // Reading a non seekable input consumes it front to back.
+        let valid_media_data: Vec<u8> = include_bytes!("../../../../assets/avc_aac_long.flv").to_vec();
+        let len = valid_media_data.len() as u64;
+        let mut input = Input::new(Cursor::new(valid_media_data)).expect("Failed to create input");
+
+        while input.receive_packet().expect("Failed to receive packet").is_some() {}
+
+        let data = input.into_reader();
+        assert_eq!(data.position(), len);
+    }
+
     #[test]
     fn test_as_ptr() {
         let valid_file_path = "../../assets/avc_aac_large.mp4";
@@ -353,6 +1611,33 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_frame_rates() {
+        let valid_file_path = "../../assets/avc_aac_large.mp4";
+        let input = Input::open(valid_file_path).expect("Failed to open valid file");
+
+        let fps = Rational::static_new::<60, 1>();
+        assert_eq!(input.avg_frame_rate(0), Some(fps));
+        assert_eq!(input.r_frame_rate(0), Some(fps));
+        assert_eq!(input.frame_rate(0), Some(fps));
+
+        // audio stream
+        assert_eq!(input.avg_frame_rate(1), Some(Rational::ZERO));
+
+        // invalid stream index
+        assert_eq!(input.avg_frame_rate(2), None);
+        assert_eq!(input.r_frame_rate(2), None);
+        assert_eq!(input.frame_rate(2), None);
+    }
+
+    #[test]
+    fn test_attached_pictures() {
+        let valid_file_path = "../../assets/avc_aac_large.mp4";
+        let input = Input::open(valid_file_path).expect("Failed to open valid file");
+
+        assert!(input.attached_pictures().is_empty(), "Expected no cover art");
+    }
+
     #[test]
     fn test_packets() {
         let valid_file_path = "../../assets/avc_aac_large.mp4";
@@ -397,4 +1682,661 @@ mod tests {
 
         insta::assert_debug_snapshot!(packets);
     }
+
+    #[test]
+    fn test_stream_infos() {
+        let input = Input::open("../../assets/avc_aac_large.mp4").expect("Failed to open valid file");
+        let infos = input.stream_infos();
+        assert_eq!(infos.len(), 2);
+
+        let video = infos
+            .iter()
+            .find(|info| matches!(info.kind, StreamInfoKind::Video { .. }))
+            .expect("Expected a video stream");
+        assert_eq!(video.codec_id, AVCodecID::H264);
+        assert!(!video.extradata.is_empty(), "Expected the avcC extradata");
+        assert!(video.duration_secs().is_some_and(|d| d > 0.0));
+        let StreamInfoKind::Video {
+            width,
+            height,
+            pixel_format,
+            frame_rate,
+        } = video.kind
+        else {
+            unreachable!();
+        };
+        assert!(width > 0 && height > 0, "Expected a resolution: {width}x{height}");
+        assert_eq!(pixel_format, AVPixelFormat::Yuv420p);
+        assert!(frame_rate.as_f64() > 0.0, "Expected a frame rate: {frame_rate:?}");
+
+        let audio = infos
+            .iter()
+            .find(|info| matches!(info.kind, StreamInfoKind::Audio { .. }))
+            .expect("Expected an audio stream");
+        assert_eq!(audio.codec_id, AVCodecID::Aac);
+        assert!(!audio.extradata.is_empty(), "Expected the AudioSpecificConfig extradata");
+        let StreamInfoKind::Audio {
+            sample_rate, channels, ..
+        } = audio.kind
+        else {
+            unreachable!();
+        };
+        assert!(sample_rate > 0, "Expected a sample rate: {sample_rate}");
+        assert!(channels > 0, "Expected channels: {channels}");
+
+        // The summary matches the individual accessors
+        for (info, stream) in infos.iter().zip(input.streams().iter()) {
+            assert_eq!(info.index, stream.index() as usize);
+            assert_eq!(info.time_base, stream.time_base());
+            assert_eq!(info.duration, stream.duration());
+        }
+    }
+
+    #[test]
+    fn test_demux_stats() {
+        let mut input = Input::open("../../assets/avc_aac.mp4").expect("Failed to open valid file");
+        assert_eq!(input.stats().packet_count(), 0);
+        assert!(input.stats().streams().is_empty());
+
+        let mut expected = Vec::new();
+        for _ in 0..20 {
+            let packet = input
+                .receive_packet()
+                .expect("Failed to receive packet")
+                .expect("Expected a packet");
+            let index = packet.stream_index() as usize;
+            if index >= expected.len() {
+                expected.resize(index + 1, (0u64, 0u64));
+            }
+
+            expected[index].0 += 1;
+            expected[index].1 += packet.data().len() as u64;
+        }
+
+        let stats = input.stats();
+        assert_eq!(stats.packet_count(), 20);
+        assert_eq!(stats.byte_count(), expected.iter().map(|(_, bytes)| bytes).sum::<u64>());
+        assert_eq!(stats.streams().len(), expected.len());
+        for (index, (packets, bytes)) in expected.iter().enumerate() {
+            let stream = stats.stream(index).expect("Expected stream stats");
+            assert_eq!(stream.packet_count, *packets);
+            assert_eq!(stream.byte_count, *bytes);
+        }
+        assert!(stats.stream(expected.len()).is_none());
+
+        // Reading the rest of the input keeps counting
+        let mut remaining = 0;
+        while input.receive_packet().expect("Failed to receive packet").is_some() {
+            remaining += 1;
+        }
+        assert_eq!(input.stats().packet_count(), 20 + remaining);
+    }
+
+    #[test]
+    fn test_keyframe_index() {
+        let valid_file_path = "../../assets/avc_aac_long.flv";
+        let mut input = Input::open(valid_file_path).expect("Failed to open valid file");
+        let stream_index = input
+            .streams()
+            .best_index(AVMediaType::Video)
+            .expect("Expected a video stream");
+
+        let index = input
+            .build_keyframe_index(stream_index)
+            .expect("Failed to build keyframe index");
+        assert!(index.len() > 1, "Expected more than one keyframe");
+        assert!(
+            index.windows(2).all(|w| w[0].0 < w[1].0 && w[0].1 < w[1].1),
+            "Expected keyframe pts and positions to be monotonic: {index:?}"
+        );
+
+        // The input was read to the end
+        assert!(input.receive_packet().expect("Failed to receive packet").is_none());
+
+        let (pts, pos) = index[1];
+        assert_eq!(input.seek_to_keyframe(&index, pts + 1).expect("Failed to seek"), Some(pts));
+
+        let packet = std::iter::from_fn(|| input.receive_packet().expect("Failed to receive packet"))
+            .find(|packet| packet.stream_index() as usize == stream_index)
+            .expect("Expected a video packet after seeking");
+        assert!(packet.is_key());
+        assert_eq!(packet.pos(), Some(pos));
+        assert_eq!(packet.pts(), Some(pts));
+
+        assert_eq!(input.seek_to_keyframe(&index, index[0].0 - 1).expect("Failed to seek"), None);
+        assert_eq!(input.seek_to_keyframe(&[], pts).expect("Failed to seek"), None);
+    }
+
+    #[test]
+    fn test_elementary_stream() {
+        let valid_file_path = "../../assets/avc_aac.mp4";
+        let mut input = Input::open(valid_file_path).expect("Failed to open valid file");
+        let video_index = input
+            .streams()
+            .best_index(AVMediaType::Video)
+            .expect("Expected a video stream");
+
+        let packets = input
+            .elementary_stream(video_index, None)
+            .expect("Failed to create elementary stream")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("Failed to read packets");
+        assert!(!packets.is_empty(), "Expected video packets");
+        assert!(packets.iter().all(|p| p.stream_index() as usize == video_index));
+        // mp4 stores length prefixed nal units
+        assert!(!packets[0].data().starts_with(&[0, 0, 0, 1]));
+
+        let mut input = Input::open(valid_file_path).expect("Failed to open valid file");
+        let mut annex_b = Vec::new();
+        input
+            .elementary_stream(video_index, Some("h264_mp4toannexb"))
+            .expect("Failed to create elementary stream")
+            .read_to_end(&mut annex_b)
+            .expect("Failed to read elementary stream");
+
+        assert!(annex_b.starts_with(&[0, 0, 0, 1]), "Expected an Annex B start code");
+
+        // The parameter sets are inserted before the first idr slice
+        let nal_types = annex_b
+            .windows(4)
+            .filter(|w| w[..3] == [0, 0, 1])
+            .map(|w| w[3] & 0x1f)
+            .collect::<Vec<_>>();
+        let idr = nal_types.iter().position(|&t| t == 5).expect("Expected an idr slice");
+        assert!(
+            nal_types[..idr].contains(&7) && nal_types[..idr].contains(&8),
+            "Expected an SPS and a PPS before the first idr slice, got {:?}",
+            &nal_types[..idr]
+        );
+
+        assert!(matches!(input.elementary_stream(10, None), Err(FfmpegError::NoStream)));
+        assert!(matches!(
+            input.elementary_stream(video_index, Some("not_a_filter")),
+            Err(FfmpegError::NoFilter)
+        ));
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        let policy = RetryPolicy {
+            max_retries: 3,
+            backoff: std::time::Duration::from_millis(10),
+            seek_to_last_position: false,
+        };
+
+        assert_eq!(policy.backoff_for(0), std::time::Duration::from_millis(10));
+        assert_eq!(policy.backoff_for(1), std::time::Duration::from_millis(20));
+        assert_eq!(policy.backoff_for(2), std::time::Duration::from_millis(40));
+        assert_eq!(policy.backoff_for(64), std::time::Duration::MAX);
+
+        assert!(RetryPolicy::is_retryable(FfmpegErrorCode(AVERROR(EIO))));
+        assert!(RetryPolicy::is_retryable(FfmpegErrorCode::Eagain));
+        assert!(!RetryPolicy::is_retryable(FfmpegErrorCode::Eof));
+        assert!(!RetryPolicy::is_retryable(FfmpegErrorCode::InvalidData));
+    }
+
+    /// A reader that fails exactly once when reading reaches `fail_at`. Until then no read goes past
+    /// `fail_at`, so the failing read is the first one at that position and consumes no bytes.
+    struct FlakyReader {
+        inner: Cursor<Vec<u8>>,
+        fail_at: u64,
+        failed: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    }
+
+    impl std::io::Read for FlakyReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let position = self.inner.position();
+            if self.failed.load(std::sync::atomic::Ordering::Relaxed) || position > self.fail_at {
+                return self.inner.read(buf);
+            }
+
+            if position == self.fail_at {
+                self.failed.store(true, std::sync::atomic::Ordering::Relaxed);
+                return Err(std::io::Error::new(std::io::ErrorKind::ConnectionReset, "connection reset"));
+            }
+
+            let len = buf.len().min((self.fail_at - position) as usize);
+            self.inner.read(&mut buf[..len])
+        }
+    }
+
+    impl std::io::Seek for FlakyReader {
+        fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    /// Reads every packet of the input as `(stream index, pts, data)`.
+    fn read_packets<T: Send + Sync>(input: &mut Input<T>) -> Vec<(i32, Option<i64>, Vec<u8>)> {
+        let mut packets = Vec::new();
+        while let Some(packet) = input.receive_packet().expect("Failed to receive packet") {
+            packets.push((packet.stream_index(), packet.pts(), packet.data().to_vec()));
+        }
+        packets
+    }
+
+    struct WouldBlockReader {
+        inner: Cursor<Vec<u8>>,
+        block_at: u64,
+        blocked: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    }
+
+    impl std::io::Read for WouldBlockReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.inner.position() >= self.block_at && !self.blocked.swap(true, std::sync::atomic::Ordering::Relaxed) {
+                return Err(std::io::Error::from(std::io::ErrorKind::WouldBlock));
+            }
+
+            self.inner.read(buf)
+        }
+    }
+
+    #[test]
+    fn test_receive_packet_would_block() {
+        let data: Vec<u8> = include_bytes!("../../../../assets/avc_aac_long.flv").to_vec();
+
+        let blocked = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let reader = WouldBlockReader {
+            block_at: data.len() as u64 / 2,
+            inner: Cursor::new(data),
+            blocked: blocked.clone(),
+        };
+
+        let mut input = Input::new(reader).expect("Failed to create input");
+
+        let mut packets_before_block = None;
+        let mut packets = 0;
+        loop {
+            match input.receive_packet() {
+                Ok(Some(_)) => packets += 1,
+                Ok(None) => break,
+                Err(FfmpegError::Code(FfmpegErrorCode::Eagain)) => {
+                    assert!(packets_before_block.is_none(), "Expected a single EAGAIN");
+                    packets_before_block = Some(packets);
+                }
+                Err(err) => panic!("Unexpected error: {err}"),
+            }
+        }
+
+        assert!(blocked.load(std::sync::atomic::Ordering::Relaxed));
+        let packets_before_block = packets_before_block.expect("Expected EAGAIN instead of the end of the input");
+        assert!(packets > packets_before_block, "Expected reading to continue after EAGAIN");
+    }
+
+    struct StalledReader {
+        inner: Cursor<Vec<u8>>,
+        stall_at: u64,
+    }
+
+    impl std::io::Read for StalledReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.inner.position() >= self.stall_at {
+                return Err(std::io::Error::from(std::io::ErrorKind::WouldBlock));
+            }
+
+            self.inner.read(buf)
+        }
+    }
+
+    #[test]
+    fn test_receive_packet_timeout() {
+        let data: Vec<u8> = include_bytes!("../../../../assets/avc_aac_long.flv").to_vec();
+        let reader = StalledReader {
+            stall_at: data.len() as u64 / 2,
+            inner: Cursor::new(data),
+        };
+
+        let mut input = Input::new(reader).expect("Failed to create input");
+        let timeout = std::time::Duration::from_millis(100);
+
+        let mut packets = 0;
+        let start = loop {
+            let start = std::time::Instant::now();
+            match input.receive_packet_timeout(timeout) {
+                Ok(Some(_)) => packets += 1,
+                Ok(None) => panic!("Expected the reader to stall before the end of the input"),
+                Err(FfmpegError::Timeout) => break start,
+                Err(err) => panic!("Unexpected error: {err}"),
+            }
+        };
+
+        let elapsed = start.elapsed();
+        assert!(packets > 0, "Expected packets before the reader stalled");
+        assert!(elapsed >= timeout, "Returned before the timeout: {elapsed:?}");
+        assert!(elapsed < timeout * 10, "Took too long to time out: {elapsed:?}");
+
+        // The call can be retried, and the interrupt callback is restored afterwards.
+        assert!(matches!(input.receive_packet_timeout(timeout), Err(FfmpegError::Timeout)));
+        // Safety: The context is valid.
+        assert!(unsafe { (*input.as_ptr()).interrupt_callback.callback.is_none() });
+    }
+
+    /// A reader that blocks once after `block_at` bytes have been read, and reports that no data
+    /// is available afterwards.
+    struct BlockingReader {
+        inner: Cursor<Vec<u8>>,
+        block_at: u64,
+        block_for: std::time::Duration,
+        blocked: bool,
+    }
+
+    impl std::io::Read for BlockingReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.inner.position() >= self.block_at && !self.blocked {
+                self.blocked = true;
+                std::thread::sleep(self.block_for);
+                return Err(std::io::Error::from(std::io::ErrorKind::WouldBlock));
+            }
+
+            self.inner.read(buf)
+        }
+    }
+
+    #[test]
+    fn test_receive_packet_timeout_blocking_reader() {
+        let data: Vec<u8> = include_bytes!("../../../../assets/avc_aac_long.flv").to_vec();
+        let reader = BlockingReader {
+            block_at: data.len() as u64 / 2,
+            inner: Cursor::new(data),
+            block_for: std::time::Duration::from_millis(300),
+            blocked: false,
+        };
+
+        let mut input = Input::new(reader).expect("Failed to create input");
+        let timeout = std::time::Duration::from_millis(50);
+
+        let start = loop {
+            let start = std::time::Instant::now();
+            match input.receive_packet_timeout(timeout) {
+                Ok(Some(_)) => {}
+                Ok(None) => panic!("Expected the reader to block before the end of the input"),
+                Err(FfmpegError::Timeout) => break start,
+                Err(err) => panic!("Unexpected error: {err}"),
+            }
+        };
+
+        // The blocking read cannot be interrupted, so the timeout is only noticed once it returns.
+        let elapsed = start.elapsed();
+        assert!(
+            elapsed >= std::time::Duration::from_millis(300),
+            "Returned during the read: {elapsed:?}"
+        );
+
+        let mut packets = 0;
+        while input
+            .receive_packet_timeout(timeout)
+            .expect("Failed to receive packet")
+            .is_some()
+        {
+            packets += 1;
+        }
+
+        assert!(packets > 0, "Expected reading to continue after the timeout");
+    }
+
+    /// Returns the offset of the first flv tag which ends after `at`.
+    fn flv_tag_boundary(data: &[u8], at: usize) -> usize {
+        // The header is followed by the size of the (non-existent) previous tag.
+        let mut offset = 9 + 4;
+        loop {
+            let data_size = u32::from_be_bytes([0, data[offset + 1], data[offset + 2], data[offset + 3]]) as usize;
+            let next = offset + 11 + data_size + 4;
+            if next > at {
+                return offset;
+            }
+
+            offset = next;
+        }
+    }
+
+    #[test]
+    fn test_receive_packet_timeout_interrupt() {
+        use std::io::Write;
+
+        let data: &[u8] = include_bytes!("../../../../assets/avc_aac_long.flv");
+        let first_split = flv_tag_boundary(data, data.len() / 3);
+        let second_split = flv_tag_boundary(data, data.len() * 2 / 3);
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("Failed to bind listener");
+        let addr = listener.local_addr().expect("Failed to get the listener address");
+        let (resume, resumed) = std::sync::mpsc::channel::<()>();
+
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("Failed to accept connection");
+            // Keeps the connection open without sending anything after each part, so the read blocks in ffmpeg.
+            stream
+                .write_all(&data[..first_split])
+                .expect("Failed to write the first part");
+            resumed.recv().expect("Failed to wait for the client");
+            stream
+                .write_all(&data[first_split..second_split])
+                .expect("Failed to write the second part");
+            // Returns once the client is done.
+            let _ = resumed.recv();
+        });
+
+        let interrupted = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let mut options = InputOptions {
+            probe_size: Some(64 * 1024),
+            analyze_duration: Some(std::time::Duration::from_millis(100)),
+            ..Default::default()
+        }
+        .with_interrupt_callback({
+            let interrupted = interrupted.clone();
+            move || interrupted.load(std::sync::atomic::Ordering::Relaxed)
+        });
+
+        let mut input = Input::open_with_options(&format!("tcp://{addr}"), &mut options).expect("Failed to create input");
+        let timeout = std::time::Duration::from_millis(100);
+
+        let mut packets = 0;
+        let start = loop {
+            let start = std::time::Instant::now();
+            match input.receive_packet_timeout(timeout) {
+                Ok(Some(_)) => packets += 1,
+                Ok(None) => panic!("Expected the connection to stall before the end of the input"),
+                Err(FfmpegError::Timeout) => break start,
+                Err(err) => panic!("Unexpected error: {err}"),
+            }
+        };
+
+        // The read blocks in ffmpeg, so only the interrupt callback can end it.
+        let elapsed = start.elapsed();
+        assert!(packets > 0, "Expected packets before the connection stalled");
+        assert!(elapsed >= timeout, "Returned before the timeout: {elapsed:?}");
+        assert!(elapsed < timeout * 10, "Took too long to time out: {elapsed:?}");
+
+        // The callback passed in the options is restored once the call returns.
+        // Safety: The context is valid.
+        assert!(unsafe { (*input.as_ptr()).interrupt_callback.callback.is_some() });
+
+        // The interrupted read does not stop the input from being read once more data is available.
+        resume.send(()).expect("Failed to resume the server");
+        let mut resumed_packets = 0;
+        loop {
+            match input.receive_packet_timeout(std::time::Duration::from_millis(500)) {
+                Ok(Some(_)) => resumed_packets += 1,
+                Ok(None) => panic!("Expected the connection to stall again before the end of the input"),
+                Err(FfmpegError::Timeout) => break,
+                Err(err) => panic!("Unexpected error after the timeout: {err}"),
+            }
+        }
+
+        assert!(resumed_packets > 0, "Expected reading to continue after the timeout");
+
+        // The previous callback is still called before the deadline, and aborts the read.
+        interrupted.store(true, std::sync::atomic::Ordering::Relaxed);
+        let start = std::time::Instant::now();
+        assert!(matches!(
+            input.receive_packet_timeout(std::time::Duration::from_secs(5)),
+            Err(FfmpegError::Code(FfmpegErrorCode::Exit))
+        ));
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(5),
+            "Expected the callback to abort the read"
+        );
+
+        drop(resume);
+        server.join().expect("Server failed");
+    }
+
+    #[test]
+    fn test_receive_packet_retry() {
+        let data: Vec<u8> = include_bytes!("../../../../assets/avc_aac_long.flv").to_vec();
+        let expected = read_packets(&mut Input::new(Cursor::new(data.clone())).expect("Failed to create input"));
+
+        // The reader is not seekable, so it fails between two tags to not cut a packet short.
+        let failed = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let reader = FlakyReader {
+            fail_at: flv_tag_boundary(&data, data.len() / 2) as u64,
+            inner: Cursor::new(data),
+            failed: failed.clone(),
+        };
+
+        let mut input = Input::with_options(
+            reader,
+            &mut InputOptions {
+                retry_policy: Some(RetryPolicy {
+                    max_retries: 1,
+                    backoff: std::time::Duration::ZERO,
+                    seek_to_last_position: true,
+                }),
+                ..Default::default()
+            },
+        )
+        .expect("Failed to create input");
+        assert!(!input.is_seekable());
+
+        let packets = read_packets(&mut input);
+        assert!(failed.load(std::sync::atomic::Ordering::Relaxed), "reader never failed");
+        assert_eq!(packets.len(), expected.len());
+        assert!(packets == expected, "Expected no packet to be lost or changed by the retry");
+    }
+
+    #[test]
+    fn test_receive_packet_retry_seek() {
+        let data: Vec<u8> = include_bytes!("../../../../assets/avc_aac_long.flv").to_vec();
+        let expected = read_packets(&mut Input::new(Cursor::new(data.clone())).expect("Failed to create input"));
+
+        // The reader fails in the middle of a tag, so the packet is only complete if the retry seeks back.
+        let fail_at = data.len() / 2;
+        assert_ne!(flv_tag_boundary(&data, fail_at), fail_at);
+
+        let failed = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let reader = FlakyReader {
+            fail_at: fail_at as u64,
+            inner: Cursor::new(data),
+            failed: failed.clone(),
+        };
+
+        let mut input = Input::seekable_with_options(
+            reader,
+            InputOptions {
+                retry_policy: Some(RetryPolicy {
+                    max_retries: 1,
+                    backoff: std::time::Duration::ZERO,
+                    seek_to_last_position: true,
+                }),
+                ..Default::default()
+            },
+        )
+        .expect("Failed to create input");
+        assert!(input.is_seekable());
+
+        let packets = read_packets(&mut input);
+        assert!(failed.load(std::sync::atomic::Ordering::Relaxed), "reader never failed");
+        assert_eq!(packets.len(), expected.len());
+        assert!(packets == expected, "Expected no packet to be lost or changed by the retry");
+    }
+
+    #[test]
+    fn test_receive_packet_no_retry() {
+        let data: Vec<u8> = include_bytes!("../../../../assets/avc_aac_long.flv").to_vec();
+
+        let failed = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let reader = FlakyReader {
+            fail_at: data.len() as u64 / 2,
+            inner: Cursor::new(data),
+            failed: failed.clone(),
+        };
+
+        let mut input = Input::new(reader).expect("Failed to create input");
+
+        // Without a retry policy the read error ends consumption early.
+        let mut packets = 0;
+        while let Ok(Some(_)) = input.receive_packet() {
+            packets += 1;
+        }
+
+        assert!(failed.load(std::sync::atomic::Ordering::Relaxed));
+        assert!(packets > 0);
+    }
+
+    #[test]
+    fn test_interrupt_callback() {
+        let valid_media_data: Vec<u8> = include_bytes!("../../../../assets/avc_aac_large.mp4").to_vec();
+
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut options = InputOptions::default().with_interrupt_callback({
+            let calls = calls.clone();
+            move || {
+                calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                false
+            }
+        });
+
+        let input = Input::with_options(Cursor::new(valid_media_data.clone()), &mut options).expect("Failed to open input");
+        assert!(
+            options.interrupt_callback.is_none(),
+            "Expected the callback to be moved into the input"
+        );
+        assert!(
+            calls.load(std::sync::atomic::Ordering::Relaxed) > 0,
+            "Expected the callback to be called"
+        );
+        drop(input);
+
+        let mut options = InputOptions::default().with_interrupt_callback(|| true);
+
+        let result = Input::with_options(Cursor::new(valid_media_data), &mut options);
+        assert!(
+            matches!(result, Err(FfmpegError::Code(FfmpegErrorCode::Exit))),
+            "Expected the open to be interrupted"
+        );
+    }
+
+    #[cfg(feature = "context")]
+    #[test]
+    fn test_with_context() {
+        let valid_file_path = "../../assets/avc_aac_large.mp4";
+        let valid_media_data: Vec<u8> = include_bytes!("../../../../assets/avc_aac_large.mp4").to_vec();
+
+        // A cancelled context aborts opening the input.
+        let (ctx, handler) = scuffle_context::Context::new();
+        handler.cancel();
+
+        let mut options = InputOptions::default().with_context(ctx.clone());
+        let result = Input::with_options(Cursor::new(valid_media_data.clone()), &mut options);
+        assert!(matches!(result, Err(FfmpegError::Code(FfmpegErrorCode::Exit))));
+
+        let mut options = InputOptions::default().with_context(ctx);
+        let result = Input::open_with_options(valid_file_path, &mut options);
+        assert!(matches!(result, Err(FfmpegError::Code(FfmpegErrorCode::Exit))));
+
+        // Cancelling the context while reading aborts the read.
+        let (ctx, handler) = scuffle_context::Context::new();
+        let mut options = InputOptions::default().with_context(ctx);
+        let mut input = Input::open_with_options(valid_file_path, &mut options).expect("Failed to open input");
+        assert!(input.receive_packet().expect("Failed to receive packet").is_some());
+
+        handler.cancel();
+
+        let result = loop {
+            match input.receive_packet() {
+                Ok(Some(_)) => continue,
+                result => break result,
+            }
+        };
+        assert!(result.is_err(), "Expected the read to be interrupted");
+    }
 }
@@ -19,7 +19,12 @@ pub(crate) unsafe extern "C" fn read_packet<T: std::io::Read>(
     // Safety: the buffer has at least `buf_size` bytes.
     let buffer = unsafe { std::slice::from_raw_parts_mut(buf, buf_size as usize) };
 
-    let ret = this.read(buffer).map(|n| n as i32).unwrap_or(AVERROR_IO);
+    let ret = match this.read(buffer) {
+        Ok(n) => n as i32,
+        // The reader has no data available yet, this is not the end of the stream.
+        Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => AVERROR(EAGAIN),
+        Err(_) => AVERROR_IO,
+    };
 
     if ret == 0 {
         return AVERROR_EOF;
@@ -28,6 +33,88 @@ pub(crate) unsafe extern "C" fn read_packet<T: std::io::Read>(
     ret
 }
 
+/// Safety: The function must be used with the same type as the one used to
+/// generically create the function pointer
+///
+/// This copies from the buffer of the reader, instead of asking it to copy into ours.
+pub(crate) unsafe extern "C" fn read_packet_buffered<T: std::io::BufRead>(
+    opaque: *mut libc::c_void,
+    buf: *mut u8,
+    buf_size: i32,
+) -> i32 {
+    // Safety: The pointer is valid given the way this function is constructed, the opaque pointer is a pointer to a T.
+    let this = unsafe { &mut *(opaque as *mut T) };
+
+    let available = match this.fill_buf() {
+        Ok(available) => available,
+        // The reader has no data available yet, this is not the end of the stream.
+        Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => return AVERROR(EAGAIN),
+        Err(_) => return AVERROR_IO,
+    };
+
+    if available.is_empty() {
+        return AVERROR_EOF;
+    }
+
+    let len = available.len().min(buf_size as usize);
+    // Safety: the buffer has at least `buf_size` bytes, and it is owned by ffmpeg so it does not overlap with the buffer of the reader.
+    unsafe { std::ptr::copy_nonoverlapping(available.as_ptr(), buf, len) };
+    this.consume(len);
+
+    len as i32
+}
+
+type ReadFn = unsafe extern "C" fn(*mut c_void, *mut u8, i32) -> i32;
+type SeekFn = unsafe extern "C" fn(*mut c_void, i64, i32) -> i64;
+
+/// A cap on the total number of bytes read from a reader.
+///
+/// When the cap is set, this is passed to ffmpeg as the opaque pointer instead of the reader,
+/// together with [`read_packet_limited`] and [`seek_limited`] which call the functions of the reader.
+pub(crate) struct ReadLimit {
+    data: *mut c_void,
+    read_fn: ReadFn,
+    seek_fn: Option<SeekFn>,
+    remaining: u64,
+}
+
+/// Safety: The opaque pointer must be a pointer to a [`ReadLimit`].
+unsafe extern "C" fn read_packet_limited(opaque: *mut libc::c_void, buf: *mut u8, buf_size: i32) -> i32 {
+    // Safety: The pointer is valid given the way this function is constructed, the opaque pointer is a pointer to a ReadLimit.
+    let this = unsafe { &mut *(opaque as *mut ReadLimit) };
+
+    if this.remaining == 0 {
+        // The reader might end exactly at the cap, which is not an error, so we only fail once
+        // there is more data to read.
+        // Safety: The read function was created for the data pointer, and the buffer has at least 1 byte.
+        let ret = unsafe { (this.read_fn)(this.data, buf, 1) };
+        return if ret > 0 { AVERROR_EXIT } else { ret };
+    }
+
+    let buf_size = this.remaining.min(buf_size as u64) as i32;
+
+    // Safety: The read function was created for the data pointer, and the buffer has at least `buf_size` bytes.
+    let ret = unsafe { (this.read_fn)(this.data, buf, buf_size) };
+    if ret > 0 {
+        this.remaining -= ret as u64;
+    }
+
+    ret
+}
+
+/// Safety: The opaque pointer must be a pointer to a [`ReadLimit`] with a seek function.
+unsafe extern "C" fn seek_limited(opaque: *mut libc::c_void, offset: i64, whence: i32) -> i64 {
+    // Safety: The pointer is valid given the way this function is constructed, the opaque pointer is a pointer to a ReadLimit.
+    let this = unsafe { &mut *(opaque as *mut ReadLimit) };
+    let Some(seek_fn) = this.seek_fn else {
+        return -1;
+    };
+
+    // Seeking does not read any bytes, so it is not limited.
+    // Safety: The seek function was created for the data pointer.
+    unsafe { seek_fn(this.data, offset, whence) }
+}
+
 /// Safety: The function must be used with the same type as the one used to
 /// generically create the function pointer
 pub(crate) unsafe extern "C" fn write_packet<T: std::io::Write>(
@@ -92,10 +179,47 @@ pub(crate) unsafe extern "C" fn seek<T: std::io::Seek>(opaque: *mut libc::c_void
     }
 }
 
+/// An interrupt callback, boxed so a thin pointer to it can be passed to ffmpeg.
+pub(crate) type InterruptCallback = Box<Box<dyn FnMut() -> bool + Send>>;
+
+/// Safety: The opaque pointer must be a pointer to the inner box of an [`InterruptCallback`].
+pub(crate) unsafe extern "C" fn interrupt_callback(opaque: *mut libc::c_void) -> i32 {
+    // Safety: The pointer is valid given the way this function is constructed, the opaque pointer is a pointer to a boxed callback.
+    let this = unsafe { &mut *(opaque as *mut Box<dyn FnMut() -> bool + Send>) };
+
+    this() as i32
+}
+
+/// A deadline installed as the interrupt callback, which also calls the previously installed callback.
+pub(crate) struct Deadline {
+    pub(crate) deadline: std::time::Instant,
+    pub(crate) previous: AVIOInterruptCB,
+    pub(crate) expired: bool,
+}
+
+/// Safety: The opaque pointer must be a pointer to a [`Deadline`], and the previous callback must be valid.
+pub(crate) unsafe extern "C" fn deadline_callback(opaque: *mut libc::c_void) -> i32 {
+    // Safety: The pointer is valid given the way this function is constructed, the opaque pointer is a pointer to a deadline.
+    let this = unsafe { &mut *(opaque as *mut Deadline) };
+
+    if std::time::Instant::now() >= this.deadline {
+        this.expired = true;
+        return 1;
+    }
+
+    match this.previous.callback {
+        // Safety: The previous callback was installed together with its opaque pointer.
+        Some(callback) => unsafe { callback(this.previous.opaque) },
+        None => 0,
+    }
+}
+
 pub(crate) struct Inner<T: Send + Sync> {
     pub(crate) data: Option<Box<T>>,
     pub(crate) context: SmartPtr<AVFormatContext>,
     _io: SmartPtr<AVIOContext>,
+    // This must be dropped after the io context, which holds a pointer to it.
+    _read_limit: Option<Box<ReadLimit>>,
 }
 
 pub(crate) struct InnerOptions {
@@ -104,6 +228,9 @@ pub(crate) struct InnerOptions {
     pub(crate) write_fn: Option<unsafe extern "C" fn(*mut c_void, *const u8, i32) -> i32>,
     pub(crate) seek_fn: Option<unsafe extern "C" fn(*mut c_void, i64, i32) -> i64>,
     pub(crate) output_format: *const AVOutputFormat,
+    /// The maximum number of bytes to read. When set, the read and seek functions are wrapped
+    /// to count the bytes read.
+    pub(crate) max_read_bytes: Option<u64>,
 }
 
 impl Default for InnerOptions {
@@ -114,6 +241,7 @@ impl Default for InnerOptions {
             write_fn: None,
             seek_fn: None,
             output_format: std::ptr::null(),
+            max_read_bytes: None,
         }
     }
 }
@@ -138,6 +266,25 @@ impl<T: Send + Sync> Inner<T> {
 
         let mut data = Box::new(data);
 
+        let mut read_limit = match (options.max_read_bytes, options.read_fn) {
+            (Some(remaining), Some(read_fn)) => Some(Box::new(ReadLimit {
+                data: data.as_mut() as *mut _ as *mut c_void,
+                read_fn,
+                seek_fn: options.seek_fn,
+                remaining,
+            })),
+            _ => None,
+        };
+
+        let (opaque, read_fn, seek_fn) = match &mut read_limit {
+            Some(read_limit) => (
+                read_limit.as_mut() as *mut ReadLimit as *mut c_void,
+                Some(read_packet_limited as ReadFn),
+                read_limit.seek_fn.map(|_| seek_limited as SeekFn),
+            ),
+            None => (data.as_mut() as *mut _ as *mut c_void, options.read_fn, options.seek_fn),
+        };
+
         // Safety: avio_alloc_context is safe to call, and all the function pointers are valid
         let destructor = |ptr: &mut *mut AVIOContext| {
             // Safety: the pointer is always valid.
@@ -157,10 +304,10 @@ impl<T: Send + Sync> Inner<T> {
                 buffer.as_ptr() as *mut u8,
                 options.buffer_size as i32,
                 if options.write_fn.is_some() { 1 } else { 0 },
-                data.as_mut() as *mut _ as *mut c_void,
-                options.read_fn,
+                opaque,
+                read_fn,
                 options.write_fn,
-                options.seek_fn,
+                seek_fn,
             )
         };
 
@@ -216,6 +363,7 @@ impl<T: Send + Sync> Inner<T> {
             data: Some(data),
             context,
             _io: io,
+            _read_limit: read_limit,
         })
     }
 }
@@ -234,6 +382,7 @@ impl Inner<()> {
                 *mut_ref = std::ptr::null_mut();
             }),
             _io: SmartPtr::null(|_| {}),
+            _read_limit: None,
         }
     }
 
@@ -1,6 +1,7 @@
 mod input;
 mod internal;
 mod output;
+mod peek;
 
 /// A module that contains the channel implementation for io operations.
 #[cfg(feature = "channel")]
@@ -9,3 +10,4 @@ pub mod channel;
 
 pub use input::*;
 pub use output::*;
+pub use peek::*;
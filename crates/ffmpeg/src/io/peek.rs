@@ -0,0 +1,119 @@
+use std::io::Read;
+
+/// A reader that reads the first bytes of another reader ahead of time, and
+/// replays them before reading the rest.
+///
+/// This allows inspecting the start of a stream, for example to detect a
+/// custom container from a magic number, and still passing the complete
+/// stream to an [`Input`](super::Input).
+///
+/// Seekable readers do not need this, they can be rewound to the start
+/// instead.
+#[derive(Debug, Clone)]
+pub struct PeekReader<T> {
+    inner: T,
+    peeked: Vec<u8>,
+    position: usize,
+}
+
+impl<T: Read> PeekReader<T> {
+    /// Creates a new `PeekReader`, reading the first `len` bytes of the reader.
+    ///
+    /// Fewer bytes are peeked if the reader ends before `len` bytes.
+    pub fn new(mut inner: T, len: usize) -> std::io::Result<Self> {
+        let mut peeked = Vec::with_capacity(len);
+        inner.by_ref().take(len as u64).read_to_end(&mut peeked)?;
+
+        Ok(Self {
+            inner,
+            peeked,
+            position: 0,
+        })
+    }
+}
+
+impl<T> PeekReader<T> {
+    /// Returns the bytes peeked from the start of the reader.
+    pub fn peeked(&self) -> &[u8] {
+        &self.peeked
+    }
+
+    /// Consumes the `PeekReader` and returns the underlying reader.
+    ///
+    /// The peeked bytes that were not read yet are lost.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Read> Read for PeekReader<T> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let remaining = &self.peeked[self.position..];
+        if remaining.is_empty() {
+            return self.inner.read(buf);
+        }
+
+        let len = remaining.len().min(buf.len());
+        buf[..len].copy_from_slice(&remaining[..len]);
+        self.position += len;
+
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(test, coverage_nightly), coverage(off))]
+mod tests {
+    use std::io::{Cursor, Read};
+
+    use super::PeekReader;
+    use crate::io::Input;
+
+    #[test]
+    fn test_peek_reader() {
+        let data = b"0123456789".to_vec();
+        let mut reader = PeekReader::new(Cursor::new(data.clone()), 4).expect("Failed to peek");
+        assert_eq!(reader.peeked(), b"0123");
+
+        let mut buf = [0; 3];
+        assert_eq!(reader.read(&mut buf).expect("Failed to read"), 3);
+        assert_eq!(&buf, b"012");
+
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).expect("Failed to read");
+        assert_eq!(rest, b"3456789");
+
+        // Peeking more than the reader has
+        let mut reader = PeekReader::new(Cursor::new(data.clone()), 100).expect("Failed to peek");
+        assert_eq!(reader.peeked(), data.as_slice());
+
+        let mut all = Vec::new();
+        reader.read_to_end(&mut all).expect("Failed to read");
+        assert_eq!(all, data);
+        assert_eq!(reader.into_inner().position(), 10);
+    }
+
+    fn count_packets<T: Send + Sync>(mut input: Input<T>) -> usize {
+        let mut packets = 0;
+        while let Some(packet) = input.receive_packet().expect("Failed to receive packet") {
+            assert!(!packet.data().is_empty());
+            packets += 1;
+        }
+        packets
+    }
+
+    #[test]
+    fn test_peek_reader_input() {
+        let data: Vec<u8> = include_bytes!("../../../../assets/avc_aac.flv").to_vec();
+
+        let reader = PeekReader::new(Cursor::new(data.clone()), 8).expect("Failed to peek");
+        assert_eq!(reader.peeked().len(), 8);
+        assert!(reader.peeked().starts_with(b"FLV"), "Expected the FLV signature");
+
+        let peeked = count_packets(Input::new(reader).expect("Failed to open input"));
+        let expected = count_packets(Input::new(Cursor::new(data)).expect("Failed to open input"));
+
+        assert!(peeked > 0);
+        assert_eq!(peeked, expected);
+    }
+}
@@ -214,6 +214,8 @@
 #![deny(clippy::undocumented_unsafe_blocks)]
 #![deny(clippy::multiple_unsafe_ops_per_block)]
 
+/// Bitstream filter specific functionality.
+pub mod bitstream_filter;
 /// Codec specific functionality.
 pub mod codec;
 /// Constants.
@@ -49,6 +49,8 @@ impl std::fmt::Display for LogLevel {
 }
 
 /// Sets the log level.
+///
+/// Messages less important than the level are not logged. This is global to the process.
 pub fn set_log_level(level: LogLevel) {
     // Safety: `av_log_set_level` is safe to call.
     unsafe {
@@ -59,13 +61,25 @@ pub fn set_log_level(level: LogLevel) {
 type Function = Box<dyn Fn(LogLevel, Option<String>, String) + Send + Sync>;
 static LOG_CALLBACK: ArcSwapOption<Function> = ArcSwapOption::const_empty();
 
-/// Sets the log callback.
+/// Sets the log callback, to capture the logs of ffmpeg instead of printing them to stderr.
+///
+/// The log callback is global to the process. It applies to every input, output, decoder and
+/// encoder, including the ones created by other libraries using ffmpeg.
+///
+/// The callback is called with the level, the name of the class that logged the message if any,
+/// such as the name of the demuxer, and the message. It replaces the default callback which
+/// prints to stderr, and any callback set before.
+///
+/// The callback can be called from any thread that uses ffmpeg, and messages are only passed to
+/// it if they are at least as important as the level set with [`set_log_level`].
 #[inline(always)]
 pub fn log_callback_set(callback: impl Fn(LogLevel, Option<String>, String) + Send + Sync + 'static) {
     log_callback_set_boxed(Box::new(callback));
 }
 
 /// Sets the log callback.
+///
+/// See [`log_callback_set`].
 pub fn log_callback_set_boxed(callback: Function) {
     LOG_CALLBACK.store(Some(Arc::new(callback)));
 
@@ -80,6 +94,8 @@ pub fn log_callback_set_boxed(callback: Function) {
 }
 
 /// Unsets the log callback.
+///
+/// This also removes the default callback, so ffmpeg does not log anywhere afterwards.
 pub fn log_callback_unset() {
     LOG_CALLBACK.store(None);
 
@@ -137,6 +153,8 @@ unsafe extern "C" fn log_cb(ptr: *mut libc::c_void, level: libc::c_int, fmt: *co
 }
 
 /// Sets the log callback to use tracing.
+///
+/// See [`log_callback_set`], this replaces any callback set before.
 #[cfg(feature = "tracing")]
 #[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
 pub fn log_callback_tracing() {
@@ -171,6 +189,7 @@ mod tests {
 
     use crate::AVCodecID;
     use crate::ffi::{av_log, av_log_get_level, avcodec_find_decoder};
+    use crate::io::Input;
     use crate::log::{LogLevel, log_callback_set, log_callback_unset, set_log_level};
 
     #[test]
@@ -291,6 +310,37 @@ mod tests {
         log_callback_unset();
     }
 
+    #[test]
+    fn test_log_callback_demuxer_error() {
+        let captured_logs = Arc::new(Mutex::new(Vec::new()));
+        let callback_logs = Arc::clone(&captured_logs);
+        log_callback_set(move |level, class, message| {
+            let mut logs = callback_logs.lock().unwrap();
+            logs.push((level, class, message));
+        });
+
+        // An mp4 file with only a `ftyp` box, the mov demuxer logs an error because there is no `moov` box.
+        #[rustfmt::skip]
+        let data = [
+            0x00, 0x00, 0x00, 0x18, b'f', b't', b'y', b'p',
+            b'i', b's', b'o', b'm', 0x00, 0x00, 0x02, 0x00,
+            b'i', b's', b'o', b'm', b'm', b'p', b'4', b'1',
+        ];
+        assert!(Input::new(std::io::Cursor::new(data.to_vec())).is_err());
+
+        let logs = captured_logs.lock().unwrap();
+        let (level, class, _) = logs
+            .iter()
+            .find(|(_, _, message)| message.contains("moov atom not found"))
+            .expect("Expected the demuxer error to be captured");
+        assert!(level.0 <= LogLevel::Warning.0, "Expected at least a warning, got {level}");
+        assert!(
+            class.as_deref().is_some_and(|class| class.contains("mp4")),
+            "Expected the demuxer class, got {class:?}"
+        );
+        log_callback_unset();
+    }
+
     #[test]
     fn test_log_callback_unset() {
         let captured_logs = Arc::new(Mutex::new(Vec::new()));
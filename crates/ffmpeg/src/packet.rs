@@ -70,6 +70,7 @@ impl std::fmt::Debug for Packet {
             .field("pts", &self.pts())
             .field("dts", &self.dts())
             .field("duration", &self.duration())
+            .field("size", &self.data().len())
             .field("pos", &self.pos())
             .field("is_key", &self.is_key())
             .field("is_corrupt", &self.is_corrupt())
@@ -80,6 +81,14 @@ impl std::fmt::Debug for Packet {
     }
 }
 
+/// Cloning a packet is cheap, the underlying data buffer is reference counted
+/// and shared between the original and the clone via `av_packet_clone`. The
+/// buffer is only freed once every packet referencing it has been dropped, so
+/// the original can be dropped while the clone is still in use.
+///
+/// The packet properties (timestamps, flags, stream index, etc.) are copied, so
+/// modifying them on one packet does not affect the other. If the original
+/// packet's data is not reference counted, the data is copied into a new buffer.
 impl Clone for Packet {
     fn clone(&self) -> Self {
         // Safety: `av_packet_clone` is safe to call.
@@ -100,6 +109,18 @@ impl Packet {
         unsafe { Self::wrap(packet) }.ok_or(FfmpegError::Alloc)
     }
 
+    /// Creates a new reference to the data of the given packet, copying its properties.
+    ///
+    /// # Safety
+    /// `ptr` must be a valid pointer to a packet.
+    pub(crate) unsafe fn ref_from_ptr(ptr: *const AVPacket) -> Option<Self> {
+        // Safety: `av_packet_clone` is safe to call, `ptr` is a valid pointer.
+        let clone = unsafe { av_packet_clone(ptr) };
+
+        // Safety: The pointer is either null or a valid packet.
+        unsafe { Self::wrap(clone) }
+    }
+
     /// Wraps a pointer to a packet.
     /// We take ownership of the pointer and free it when the `Packet` is dropped.
     ///
@@ -246,6 +267,7 @@ mod tests {
     use insta::assert_debug_snapshot;
 
     use crate::ffi::AVRational;
+    use crate::io::Input;
     use crate::packet::Packet;
 
     #[test]
@@ -271,6 +293,7 @@ mod tests {
             duration: Some(
                 1000,
             ),
+            size: 0,
             pos: Some(
                 2000,
             ),
@@ -302,6 +325,7 @@ mod tests {
             duration: Some(
                 1000,
             ),
+            size: 0,
             pos: Some(
                 2000,
             ),
@@ -314,6 +338,52 @@ mod tests {
         ");
     }
 
+    #[test]
+    fn test_packet_debug() {
+        let mut input = Input::open("../../assets/avc_aac.mp4").expect("Failed to open valid file");
+        let packet = input
+            .receive_packet()
+            .expect("Failed to receive packet")
+            .expect("Expected a packet");
+
+        let debug = format!("{packet:?}");
+        assert!(debug.contains(&format!("stream_index: {}", packet.stream_index())));
+        assert!(debug.contains(&format!("size: {}", packet.data().len())));
+        assert!(debug.contains(&format!("is_key: {}", packet.is_key())));
+    }
+
+    #[test]
+    fn test_packet_clone_outlives_original() {
+        let mut input = Input::open("../../assets/avc_aac_large.mp4").expect("Failed to open valid file");
+        let original_packet = input
+            .receive_packet()
+            .expect("Failed to receive packet")
+            .expect("Expected a packet");
+
+        let expected_data = original_packet.data().to_vec();
+        assert!(!expected_data.is_empty(), "Expected the packet to contain data");
+
+        let cloned_packet = original_packet.clone();
+
+        // Safety: `original_packet.as_ptr()` is a valid pointer.
+        let original_buf = unsafe { (*original_packet.as_ptr()).buf };
+        // Safety: `cloned_packet.as_ptr()` is a valid pointer.
+        let cloned_buf = unsafe { (*cloned_packet.as_ptr()).buf };
+        assert!(!cloned_buf.is_null(), "Expected the cloned packet to be reference counted");
+        assert_eq!(
+            // Safety: `original_buf` is a valid pointer.
+            unsafe { (*original_buf).data },
+            // Safety: `cloned_buf` is a valid pointer.
+            unsafe { (*cloned_buf).data },
+            "Expected the clone to share the original buffer"
+        );
+
+        drop(original_packet);
+        drop(input);
+
+        assert_eq!(cloned_packet.data(), expected_data.as_slice());
+    }
+
     #[test]
     fn test_packet_as_ptr() {
         let packet = Packet::new().expect("Failed to create Packet");
@@ -352,6 +422,7 @@ mod tests {
             duration: Some(
                 4800,
             ),
+            size: 0,
             pos: Some(
                 -1,
             ),
@@ -3,9 +3,103 @@ use std::marker::PhantomData;
 use crate::consts::{Const, Mut};
 use crate::dict::Dictionary;
 use crate::ffi::*;
+use crate::packet::Packet;
 use crate::rational::Rational;
 use crate::utils::check_i64;
-use crate::{AVDiscard, AVMediaType};
+use crate::{
+    AVCodecID, AVColorPrimaries, AVColorRange, AVColorSpace, AVColorTransferCharacteristic, AVDiscard, AVMediaType,
+    AVPixelFormat, AVSampleFormat,
+};
+
+/// The color properties of a video stream.
+///
+/// See [`Stream::color_properties`] and [`Stream::set_color_properties`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorProperties {
+    /// The range of the sample values.
+    pub range: AVColorRange,
+    /// The chromaticity coordinates of the source primaries.
+    pub primaries: AVColorPrimaries,
+    /// The transfer characteristic.
+    pub transfer: AVColorTransferCharacteristic,
+    /// The matrix coefficients.
+    pub space: AVColorSpace,
+}
+
+impl ColorProperties {
+    /// Creates the color properties from the code points signalled in the VUI of a H.264 or H.265
+    /// bitstream, such as the `ColorConfig` of the `scuffle-h265` crate.
+    ///
+    /// Reserved and unknown code points are mapped to unspecified.
+    pub const fn from_code_points(
+        full_range: bool,
+        color_primaries: u8,
+        transfer_characteristics: u8,
+        matrix_coefficients: u8,
+    ) -> Self {
+        Self {
+            range: AVColorRange::from_full_range(full_range),
+            primaries: AVColorPrimaries::from_code_point(color_primaries),
+            transfer: AVColorTransferCharacteristic::from_code_point(transfer_characteristics),
+            space: AVColorSpace::from_code_point(matrix_coefficients),
+        }
+    }
+}
+
+/// A summary of a stream, assembled from its codec parameters and stream fields.
+///
+/// See [`Stream::info`] and [`Input::stream_infos`](crate::io::Input::stream_infos).
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamInfo {
+    /// The index of the stream.
+    pub index: usize,
+    /// The codec of the stream.
+    pub codec_id: AVCodecID,
+    /// The time base of the stream.
+    pub time_base: Rational,
+    /// The duration of the stream in the time base of the stream, if known.
+    pub duration: Option<i64>,
+    /// The bitrate of the stream in bits per second, if known.
+    pub bit_rate: Option<i64>,
+    /// The codec specific extradata, such as the `avcC` box of H.264 in mp4. Empty if there is none.
+    pub extradata: Vec<u8>,
+    /// The media type specific properties of the stream.
+    pub kind: StreamInfoKind,
+}
+
+impl StreamInfo {
+    /// Returns the duration of the stream in seconds, if known.
+    pub fn duration_secs(&self) -> Option<f64> {
+        self.duration.map(|duration| duration as f64 * self.time_base.as_f64())
+    }
+}
+
+/// The media type specific properties of a [`StreamInfo`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StreamInfoKind {
+    /// A video stream.
+    Video {
+        /// The width of the video in pixels.
+        width: i32,
+        /// The height of the video in pixels.
+        height: i32,
+        /// The pixel format of the video.
+        pixel_format: AVPixelFormat,
+        /// The frame rate as guessed by ffmpeg, see [`Stream::guess_frame_rate`].
+        frame_rate: Rational,
+    },
+    /// An audio stream.
+    Audio {
+        /// The sample rate of the audio in Hz.
+        sample_rate: i32,
+        /// The number of channels of the audio.
+        channels: i32,
+        /// The sample format of the audio.
+        sample_format: AVSampleFormat,
+    },
+    /// Any other stream, such as subtitles or data.
+    Other(AVMediaType),
+}
 
 /// A collection of streams. Streams implements [`IntoIterator`] to iterate over the streams.
 pub struct Streams<'a> {
@@ -72,6 +166,15 @@ impl<'a> Streams<'a> {
         self.best(media_type).map(|s| s.0)
     }
 
+    /// Returns an iterator over the streams with the given codec id.
+    pub fn by_codec(&'a self, codec_id: AVCodecID) -> impl Iterator<Item = Const<'a, Stream<'a>>> {
+        self.iter().filter(move |stream| {
+            stream
+                .codec_parameters()
+                .is_some_and(|params| AVCodecID(params.codec_id as _) == codec_id)
+        })
+    }
+
     /// Returns an iterator over the streams.
     pub const fn iter(&'a self) -> StreamIter<'a> {
         StreamIter {
@@ -187,17 +290,94 @@ impl<'a> Stream<'a> {
         self.0.index
     }
 
-    /// Returns the ID of the stream.
+    /// Returns the format specific ID of the stream.
+    ///
+    /// For MPEG-TS this is the PID of the stream, and for MP4 it is the track ID. This is set by
+    /// the demuxer, and can be used to correlate streams with the tables of the container or to
+    /// keep track of them across a remux.
     pub const fn id(&self) -> i32 {
         self.0.id
     }
 
+    /// Sets the format specific ID of the stream.
+    ///
+    /// Muxers that support it use this as the ID of the stream in the container, for example the
+    /// MPEG-TS muxer uses it as the PID.
+    pub const fn set_id(&mut self, id: i32) {
+        self.0.id = id;
+    }
+
     /// Returns the codec parameters of the stream.
     pub const fn codec_parameters(&self) -> Option<&'a AVCodecParameters> {
         // Safety: the pointer is valid
         unsafe { self.0.codecpar.as_ref() }
     }
 
+    /// Returns a summary of the stream, or `None` if the stream has no codec parameters.
+    pub fn info(&self) -> Option<StreamInfo> {
+        let codec_params = self.codec_parameters()?;
+
+        let kind = match AVMediaType(codec_params.codec_type) {
+            AVMediaType::Video => StreamInfoKind::Video {
+                width: codec_params.width,
+                height: codec_params.height,
+                pixel_format: AVPixelFormat(codec_params.format),
+                frame_rate: self.guess_frame_rate(),
+            },
+            AVMediaType::Audio => StreamInfoKind::Audio {
+                sample_rate: codec_params.sample_rate,
+                channels: codec_params.ch_layout.nb_channels,
+                sample_format: AVSampleFormat(codec_params.format),
+            },
+            media_type => StreamInfoKind::Other(media_type),
+        };
+
+        let extradata = if codec_params.extradata.is_null() || codec_params.extradata_size <= 0 {
+            Vec::new()
+        } else {
+            // Safety: `extradata` is a valid pointer to `extradata_size` bytes owned by the codec parameters.
+            unsafe { std::slice::from_raw_parts(codec_params.extradata, codec_params.extradata_size as usize) }.to_vec()
+        };
+
+        Some(StreamInfo {
+            index: self.index() as usize,
+            codec_id: AVCodecID(codec_params.codec_id as _),
+            time_base: self.time_base(),
+            duration: self.duration(),
+            bit_rate: (codec_params.bit_rate > 0).then_some(codec_params.bit_rate),
+            extradata,
+            kind,
+        })
+    }
+
+    /// Returns the color properties from the codec parameters of the stream.
+    pub fn color_properties(&self) -> Option<ColorProperties> {
+        let codec_params = self.codec_parameters()?;
+
+        Some(ColorProperties {
+            range: AVColorRange(codec_params.color_range as _),
+            primaries: AVColorPrimaries(codec_params.color_primaries as _),
+            transfer: AVColorTransferCharacteristic(codec_params.color_trc as _),
+            space: AVColorSpace(codec_params.color_space as _),
+        })
+    }
+
+    /// Sets the color properties in the codec parameters of the stream.
+    ///
+    /// For an output stream this must be done before the header is written, so the muxer can
+    /// signal the color properties in the container.
+    pub fn set_color_properties(&mut self, color: ColorProperties) {
+        // Safety: the pointer is valid
+        let Some(codec_params) = (unsafe { self.0.codecpar.as_mut() }) else {
+            return;
+        };
+
+        codec_params.color_range = color.range.into();
+        codec_params.color_primaries = color.primaries.into();
+        codec_params.color_trc = color.transfer.into();
+        codec_params.color_space = color.space.into();
+    }
+
     /// Returns the time base of the stream.
     pub fn time_base(&self) -> Rational {
         self.0.time_base.into()
@@ -254,6 +434,54 @@ impl<'a> Stream<'a> {
         self.0.disposition = disposition;
     }
 
+    /// Returns true if the stream is an attached picture, such as the cover art of an audio file.
+    pub const fn is_attached_pic(&self) -> bool {
+        self.0.disposition & AV_DISPOSITION_ATTACHED_PIC as i32 != 0
+    }
+
+    /// Returns the attached picture of the stream, such as the cover art of an audio file.
+    ///
+    /// This is only set for streams with the `AV_DISPOSITION_ATTACHED_PIC` disposition, where the
+    /// picture is stored as a single packet on the stream rather than being read with the other packets.
+    /// The returned packet references the same data as the stream.
+    pub fn attached_pic(&self) -> Option<Packet> {
+        if !self.is_attached_pic() || self.0.attached_pic.data.is_null() {
+            return None;
+        }
+
+        // Safety: `attached_pic` is a valid packet owned by the stream.
+        unsafe { Packet::ref_from_ptr(&self.0.attached_pic) }
+    }
+
+    /// Returns the rotation of the stream in degrees, read from its display matrix side data.
+    ///
+    /// This is the counterclockwise angle in the range `[-180, 180]`, as returned by
+    /// `av_display_rotation_get`. Frames must be rotated by the negated angle to be displayed
+    /// upright. Returns `None` if the stream has no display matrix.
+    pub fn rotation(&self) -> Option<f64> {
+        let codec_params = self.codec_parameters()?;
+
+        // Safety: `coded_side_data` is an array of `nb_coded_side_data` elements owned by the codec parameters.
+        let side_data = unsafe {
+            av_packet_side_data_get(
+                codec_params.coded_side_data,
+                codec_params.nb_coded_side_data,
+                AV_PKT_DATA_DISPLAYMATRIX,
+            )
+        };
+
+        // Safety: `av_packet_side_data_get` returns either null or a pointer into the side data array.
+        let side_data = unsafe { side_data.as_ref() }?;
+        if side_data.data.is_null() || side_data.size < 9 * std::mem::size_of::<i32>() {
+            return None;
+        }
+
+        // Safety: The side data holds a 3x3 matrix of `i32`s, and is allocated with `av_malloc` so it is aligned.
+        let rotation = unsafe { av_display_rotation_get(side_data.data as *const i32) };
+
+        (!rotation.is_nan()).then_some(rotation)
+    }
+
     /// Returns the discard flag of the stream.
     pub const fn discard(&self) -> AVDiscard {
         AVDiscard(self.0.discard)
@@ -298,6 +526,16 @@ impl<'a> Stream<'a> {
         self.0.r_frame_rate.into()
     }
 
+    /// Returns the frame rate of the stream as guessed by ffmpeg from the container and codec information.
+    ///
+    /// For constant frame rate content this is usually the same as [`Stream::avg_frame_rate`] and
+    /// [`Stream::r_frame_rate`]. Returns [`Rational::ZERO`] if the frame rate is unknown.
+    pub fn guess_frame_rate(&self) -> Rational {
+        // Safety: av_guess_frame_rate is safe to call, both pointers are valid and it does not mutate them.
+        // We upcast the stream pointer to a mutable pointer because the function signature requires it.
+        unsafe { av_guess_frame_rate(self.1, self.as_ptr() as *mut _, std::ptr::null_mut()) }.into()
+    }
+
     /// Returns the format context of the stream.
     ///
     /// # Safety
@@ -335,11 +573,14 @@ mod tests {
 
     use insta::{Settings, assert_debug_snapshot};
 
-    use crate::AVDiscard;
-    use crate::ffi::AVStream;
+    use crate::ffi::{
+        AV_DISPOSITION_ATTACHED_PIC, AV_PKT_DATA_DISPLAYMATRIX, AVStream, av_display_rotation_set, av_new_packet,
+        av_packet_side_data_new,
+    };
     use crate::io::Input;
     use crate::rational::Rational;
-    use crate::stream::AVMediaType;
+    use crate::stream::{AVMediaType, ColorProperties};
+    use crate::{AVCodecID, AVColorPrimaries, AVColorRange, AVColorSpace, AVColorTransferCharacteristic, AVDiscard};
 
     #[test]
     fn test_best_stream() {
@@ -355,6 +596,21 @@ mod tests {
         assert!(best_stream.index() >= 0, "Expected a valid stream index");
     }
 
+    #[test]
+    fn test_streams_by_codec() {
+        let valid_file_path = "../../assets/avc_aac_large.mp4";
+        let input = Input::open(valid_file_path).expect("Failed to open valid file");
+        let streams = input.streams();
+
+        let h264 = streams.by_codec(AVCodecID::H264).map(|s| s.index()).collect::<Vec<_>>();
+        assert_eq!(h264, vec![0]);
+
+        let aac = streams.by_codec(AVCodecID::Aac).map(|s| s.index()).collect::<Vec<_>>();
+        assert_eq!(aac, vec![1]);
+
+        assert_eq!(streams.by_codec(AVCodecID::Hevc).count(), 0, "Expected no HEVC streams");
+    }
+
     #[test]
     fn test_best_none_stream() {
         let valid_file_path = "../../assets/avc_aac_large.mp4";
@@ -437,6 +693,21 @@ mod tests {
         assert!(stream.id() >= 0, "Stream ID should be valid");
     }
 
+    #[test]
+    fn test_stream_id() {
+        let valid_file_path = "../../assets/avc_aac.mp4";
+        let mut input = Input::open(valid_file_path).expect("Failed to open valid file");
+
+        // The mp4 demuxer uses the track ID as the stream ID.
+        let ids = input.streams().iter().map(|stream| stream.id()).collect::<Vec<_>>();
+        assert_eq!(ids, vec![1, 2]);
+
+        let mut streams = input.streams_mut();
+        let mut stream = streams.get(0).expect("Expected a valid stream");
+        stream.set_id(0x100);
+        assert_eq!(stream.id(), 0x100, "Expected `id` to match the set value");
+    }
+
     #[test]
     fn test_streams_get_invalid_index() {
         let valid_file_path = "../../assets/avc_aac_large.mp4";
@@ -497,6 +768,73 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_stream_attached_pic() {
+        let valid_file_path = "../../assets/avc_aac_large.mp4";
+        let mut input = Input::open(valid_file_path).expect("Failed to open valid file");
+        let mut streams = input.streams_mut();
+        let mut stream = streams.get(0).expect("Expected a valid stream");
+
+        assert!(!stream.is_attached_pic());
+        assert!(stream.attached_pic().is_none());
+
+        stream.set_disposition(AV_DISPOSITION_ATTACHED_PIC as i32);
+        assert!(stream.is_attached_pic());
+        assert!(stream.attached_pic().is_none(), "Expected no picture without data");
+
+        // Safety: `stream.as_mut_ptr()` is a valid pointer.
+        let attached_pic = unsafe { &mut (*stream.as_mut_ptr()).attached_pic };
+        // Safety: `attached_pic` is a valid, empty packet. It is freed by ffmpeg when the input is closed.
+        assert_eq!(unsafe { av_new_packet(attached_pic, 4) }, 0);
+        // Safety: `av_new_packet` allocated 4 bytes of data.
+        unsafe { std::ptr::copy_nonoverlapping([1u8, 2, 3, 4].as_ptr(), attached_pic.data, 4) };
+        attached_pic.stream_index = 0;
+
+        let picture = stream.attached_pic().expect("Expected an attached picture");
+        assert_eq!(picture.data(), &[1, 2, 3, 4]);
+        assert_eq!(picture.stream_index(), 0);
+
+        drop(input);
+
+        assert_eq!(picture.data(), &[1, 2, 3, 4], "Expected the picture to outlive the input");
+    }
+
+    #[test]
+    fn test_stream_rotation() {
+        let valid_file_path = "../../assets/avc_aac.mp4";
+        let mut input = Input::open(valid_file_path).expect("Failed to open valid file");
+        let mut streams = input.streams_mut();
+        let mut stream = streams.get(0).expect("Expected a valid stream");
+
+        assert_eq!(stream.rotation(), None, "Expected no display matrix");
+
+        // Safety: `stream.as_mut_ptr()` is a valid pointer.
+        let codecpar = unsafe { (*stream.as_mut_ptr()).codecpar };
+        // Safety: `codecpar` is a valid pointer owned by the stream.
+        let codecpar = unsafe { &mut *codecpar };
+
+        // Safety: The side data array is owned by the codec parameters, and is freed with them.
+        let side_data = unsafe {
+            av_packet_side_data_new(
+                &mut codecpar.coded_side_data,
+                &mut codecpar.nb_coded_side_data,
+                AV_PKT_DATA_DISPLAYMATRIX,
+                9 * std::mem::size_of::<i32>(),
+                0,
+            )
+        };
+        assert!(!side_data.is_null());
+
+        // Safety: `side_data` is a valid pointer.
+        let matrix = unsafe { (*side_data).data } as *mut i32;
+        // Safety: The side data was allocated with room for a 3x3 matrix of `i32`s.
+        unsafe { av_display_rotation_set(matrix, 90.0) };
+
+        // `av_display_rotation_set` describes a clockwise rotation, the rotation is counterclockwise.
+        let rotation = stream.rotation().expect("Expected a rotation");
+        assert!((rotation + 90.0).abs() < 1e-6, "Expected a rotation of -90, got {rotation}");
+    }
+
     #[test]
     fn test_stream_discard() {
         let valid_file_path = "../../assets/avc_aac_large.mp4";
@@ -509,6 +847,49 @@ mod tests {
         assert_eq!(stream.discard(), test_discard, "Expected `discard` to match the set value");
     }
 
+    #[test]
+    fn test_stream_color_properties() {
+        // BT.2020 with the PQ transfer characteristic, as used by HDR10
+        let color_config = scuffle_h265::ColorConfig {
+            full_range: false,
+            color_primaries: 9,
+            transfer_characteristics: 16,
+            matrix_coefficients: 9,
+        };
+
+        let color = ColorProperties::from_code_points(
+            color_config.full_range,
+            color_config.color_primaries,
+            color_config.transfer_characteristics,
+            color_config.matrix_coefficients,
+        );
+        assert_eq!(
+            color,
+            ColorProperties {
+                range: AVColorRange::Mpeg,
+                primaries: AVColorPrimaries::Bt2020,
+                transfer: AVColorTransferCharacteristic::Smpte2084,
+                space: AVColorSpace::Bt2020Ncl,
+            }
+        );
+
+        let valid_file_path = "../../assets/avc_aac_large.mp4";
+        let mut input = Input::open(valid_file_path).expect("Failed to open valid file");
+        let mut streams = input.streams_mut();
+        let mut stream = streams.best_mut(AVMediaType::Video).expect("Expected a video stream");
+
+        stream.set_color_properties(color);
+        assert_eq!(stream.color_properties(), Some(color));
+
+        // Reserved code points are not passed on
+        let color = ColorProperties::from_code_points(true, 3, 0, 3);
+        assert_eq!(color.range, AVColorRange::Jpeg);
+        assert_eq!(color.primaries, AVColorPrimaries::Unspecified);
+        assert_eq!(color.transfer, AVColorTransferCharacteristic::Unspecified);
+        assert_eq!(color.space, AVColorSpace::Unspecified);
+        assert_eq!(ColorProperties::from_code_points(false, 1, 1, 0).space, AVColorSpace::Rgb);
+    }
+
     #[test]
     fn test_stream_sample_aspect_ratio() {
         let valid_file_path = "../../assets/avc_aac_large.mp4";
@@ -572,6 +953,20 @@ mod tests {
         assert!(real_frame_rate.as_f64() > 0.0, "Expected non-zero r_frame_rate numerator");
     }
 
+    #[test]
+    fn test_stream_guess_frame_rate() {
+        let valid_file_path = "../../assets/avc_aac_large.mp4";
+        let mut input = Input::open(valid_file_path).expect("Failed to open valid file");
+
+        let mut streams = input.streams_mut();
+        let video = streams.get(0).expect("Expected a valid stream");
+        assert_eq!(video.guess_frame_rate(), Rational::static_new::<60, 1>());
+
+        let mut streams = input.streams_mut();
+        let audio = streams.get(1).expect("Expected a valid stream");
+        assert_eq!(audio.guess_frame_rate(), Rational::ZERO);
+    }
+
     #[test]
     fn test_stream_format_context() {
         let valid_file_path = "../../assets/avc_aac_large.mp4";
@@ -12,6 +12,7 @@
 //! You can choose between one of them if you use this work.
 //!
 //! `SPDX-License-Identifier: MIT OR Apache-2.0`
+#![cfg_attr(all(coverage_nightly, test), feature(coverage_attribute))]
 #![deny(missing_docs)]
 #![deny(unsafe_code)]
 
@@ -38,6 +39,38 @@ pub trait FutureExt {
     fn with_timeout_at(self, deadline: tokio::time::Instant) -> tokio::time::Timeout<Self>
     where
         Self: Sized;
+
+    /// Measure how long the future takes to complete.
+    ///
+    /// Resolves to the output of the future along with the time elapsed
+    /// between the first poll and its completion. Time spent before the future
+    /// is first polled, for example while waiting to be spawned, is not
+    /// included.
+    fn timed(self) -> impl std::future::Future<Output = (<Self as std::future::Future>::Output, tokio::time::Duration)>
+    where
+        Self: std::future::Future + Sized;
+
+    /// Cancel the future when the given context is done.
+    ///
+    /// Resolves to `None` if the context is done before the future completes,
+    /// otherwise resolves to `Some` with the output of the future.
+    ///
+    /// This is similar to
+    /// [`ContextFutExt::with_context`](scuffle_context::ContextFutExt::with_context),
+    /// with two differences:
+    /// - The context is only borrowed, so the returned future does not keep the
+    ///   context alive and does not delay
+    ///   [`Handler::shutdown`](scuffle_context::Handler::shutdown).
+    /// - Cancellation takes priority. If the context is done and the future is
+    ///   ready at the same time, this resolves to `None`, whereas
+    ///   `with_context` would resolve to the output of the future.
+    #[cfg(feature = "context")]
+    fn with_cancellation<'a>(
+        self,
+        ctx: &'a scuffle_context::Context,
+    ) -> impl std::future::Future<Output = Option<<Self as std::future::Future>::Output>> + 'a
+    where
+        Self: std::future::Future + Sized + 'a;
 }
 
 impl<F: std::future::Future> FutureExt for F {
@@ -48,4 +81,111 @@ impl<F: std::future::Future> FutureExt for F {
     fn with_timeout_at(self, deadline: tokio::time::Instant) -> tokio::time::Timeout<Self> {
         tokio::time::timeout_at(deadline, self)
     }
+
+    async fn timed(self) -> (F::Output, tokio::time::Duration) {
+        let start = tokio::time::Instant::now();
+        let output = self.await;
+        (output, start.elapsed())
+    }
+
+    #[cfg(feature = "context")]
+    async fn with_cancellation<'a>(self, ctx: &'a scuffle_context::Context) -> Option<F::Output>
+    where
+        Self: 'a,
+    {
+        let mut future = std::pin::pin!(self);
+        let mut done = std::pin::pin!(ctx.done());
+
+        std::future::poll_fn(move |cx| {
+            if done.as_mut().poll(cx).is_ready() {
+                return std::task::Poll::Ready(None);
+            }
+
+            future.as_mut().poll(cx).map(Some)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(coverage_nightly, test), coverage(off))]
+mod tests {
+    use std::time::Duration;
+
+    use super::FutureExt;
+
+    #[tokio::test]
+    async fn timed() {
+        let (output, elapsed) = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            42
+        }
+        .timed()
+        .await;
+
+        assert_eq!(output, 42);
+        assert!(elapsed >= Duration::from_millis(50), "elapsed: {elapsed:?}");
+
+        // Time before the first poll is not included
+        let future = async { 42 }.timed();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let (output, elapsed) = future.await;
+        assert_eq!(output, 42);
+        assert!(elapsed < Duration::from_millis(50), "elapsed: {elapsed:?}");
+    }
+
+    #[cfg(feature = "context")]
+    #[tokio::test]
+    async fn with_cancellation_future_wins() {
+        let (ctx, _handler) = scuffle_context::Context::new();
+
+        let result = async { 42 }.with_cancellation(&ctx).await;
+        assert_eq!(result, Some(42));
+
+        let result = tokio::time::sleep(Duration::from_millis(10)).with_cancellation(&ctx).await;
+        assert_eq!(result, Some(()));
+    }
+
+    #[cfg(feature = "context")]
+    #[tokio::test]
+    async fn with_cancellation_cancel_wins() {
+        let (ctx, handler) = scuffle_context::Context::new();
+
+        let task = tokio::spawn({
+            let ctx = ctx.clone();
+            async move { std::future::pending::<()>().with_cancellation(&ctx).await }
+        });
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        handler.cancel();
+
+        let result = task.with_timeout(Duration::from_millis(100)).await.unwrap().unwrap();
+        assert_eq!(result, None);
+
+        // Cancellation takes priority over a future that is already ready
+        let result = async { 42 }.with_cancellation(&ctx).await;
+        assert_eq!(result, None);
+    }
+
+    #[cfg(feature = "context")]
+    #[tokio::test]
+    async fn with_cancellation_does_not_block_shutdown() {
+        let (ctx, handler) = scuffle_context::Context::new();
+
+        // The task owns the only context, which is dropped once the future is cancelled.
+        let task = tokio::spawn(async move { std::future::pending::<()>().with_cancellation(&ctx).await });
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!task.is_finished(), "expected the future to be pending");
+
+        // The shutdown starts while the future is still pending
+        handler
+            .shutdown()
+            .with_timeout(Duration::from_millis(100))
+            .await
+            .expect("expected the shutdown to finish");
+
+        let result = task.with_timeout(Duration::from_millis(100)).await.unwrap().unwrap();
+        assert_eq!(result, None);
+    }
 }
@@ -1,8 +1,10 @@
 mod config;
+mod nal;
 mod sps;
 
 pub use self::config::{HEVCDecoderConfigurationRecord, NaluArray, NaluType};
-pub use self::sps::{ColorConfig, Sps};
+pub use self::nal::{NalUnitFormat, NalUnitHeader, NalUnits, filter_temporal_sublayers, nal_units};
+pub use self::sps::{AspectRatioInfo, ColorConfig, Sps};
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,202 @@
+use std::io;
+
+use crate::NaluType;
+
+/// How NAL units are delimited within an access unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NalUnitFormat {
+    /// Each NAL unit is prefixed with its size as a big endian integer of the
+    /// given number of bytes (1 to 4).
+    ///
+    /// This is the format used by ISO/IEC 14496-15 (mp4, flv), where the
+    /// length size is `length_size_minus_one + 1` from the
+    /// [`HEVCDecoderConfigurationRecord`](crate::HEVCDecoderConfigurationRecord).
+    LengthPrefixed(u8),
+    /// NAL units are separated by `0x000001` or `0x00000001` start codes.
+    /// ISO/IEC 23008-2:2020(E) - Annex B
+    AnnexB,
+}
+
+/// NAL Unit Header
+/// ISO/IEC 23008-2:2020(E) - 7.3.1.2
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NalUnitHeader {
+    pub nal_unit_type: NaluType,
+    pub nuh_layer_id: u8,
+    pub nuh_temporal_id_plus1: u8,
+}
+
+impl NalUnitHeader {
+    /// Parses the header from the first two bytes of a NAL unit.
+    pub fn parse(nal: &[u8]) -> io::Result<Self> {
+        let [first, second, ..] = *nal else {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "not enough data for nal unit header",
+            ));
+        };
+
+        if first & 0x80 != 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "forbidden_zero_bit is not zero"));
+        }
+
+        let nuh_temporal_id_plus1 = second & 0x07;
+        if nuh_temporal_id_plus1 == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "nuh_temporal_id_plus1 is zero"));
+        }
+
+        Ok(Self {
+            nal_unit_type: NaluType::from((first >> 1) & 0x3f),
+            nuh_layer_id: ((first & 0x01) << 5) | (second >> 3),
+            nuh_temporal_id_plus1,
+        })
+    }
+
+    /// Returns the temporal id of the NAL unit, `nuh_temporal_id_plus1 - 1`.
+    pub fn temporal_id(&self) -> u8 {
+        self.nuh_temporal_id_plus1 - 1
+    }
+
+    /// Returns true if this is a VCL NAL unit, which contains the data of a slice.
+    pub fn is_vcl(&self) -> bool {
+        u8::from(self.nal_unit_type) < 32
+    }
+}
+
+/// Returns an iterator over the NAL units of an access unit.
+///
+/// The returned slices borrow from `data` and do not include the length
+/// prefix or start code. Zero-length NAL units and trailing zero padding are
+/// skipped.
+pub fn nal_units(data: &[u8], format: NalUnitFormat) -> NalUnits<'_> {
+    NalUnits { data, format }
+}
+
+/// An iterator over the NAL units of an access unit.
+///
+/// Created with [`nal_units`].
+#[derive(Debug, Clone)]
+pub struct NalUnits<'a> {
+    data: &'a [u8],
+    format: NalUnitFormat,
+}
+
+impl<'a> NalUnits<'a> {
+    fn next_length_prefixed(&mut self, length_size: u8) -> Option<io::Result<&'a [u8]>> {
+        let length_size = length_size as usize;
+
+        loop {
+            // Trailing zero padding shorter than a length prefix is not a nal unit.
+            if self.data.iter().all(|&b| b == 0) {
+                return None;
+            }
+
+            if !(1..=4).contains(&length_size) {
+                return Some(Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid nal unit length size: {length_size}"),
+                )));
+            }
+
+            if self.data.len() < length_size {
+                return Some(Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "not enough data for nal unit length",
+                )));
+            }
+
+            let (length, rest) = self.data.split_at(length_size);
+            let length = length.iter().fold(0usize, |acc, &b| (acc << 8) | b as usize);
+
+            if rest.len() < length {
+                return Some(Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!("nal unit length {length} exceeds remaining data {}", rest.len()),
+                )));
+            }
+
+            let (nal, rest) = rest.split_at(length);
+            self.data = rest;
+
+            if !nal.is_empty() {
+                return Some(Ok(nal));
+            }
+        }
+    }
+
+    fn next_annex_b(&mut self) -> Option<&'a [u8]> {
+        loop {
+            let start = find_start_code(self.data)? + 3;
+            let data = &self.data[start..];
+            let end = find_start_code(data).unwrap_or(data.len());
+            self.data = &data[end..];
+
+            // Strip the trailing_zero_8bits, which includes the leading zero of a 4 byte start code.
+            let nal = &data[..end];
+            let len = nal.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+
+            if len > 0 {
+                return Some(&nal[..len]);
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for NalUnits<'a> {
+    type Item = io::Result<&'a [u8]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = match self.format {
+            NalUnitFormat::LengthPrefixed(length_size) => self.next_length_prefixed(length_size),
+            NalUnitFormat::AnnexB => self.next_annex_b().map(Ok),
+        };
+
+        // Stop iterating after an error.
+        if matches!(result, Some(Err(_)) | None) {
+            self.data = &[];
+        }
+
+        result
+    }
+}
+
+impl std::iter::FusedIterator for NalUnits<'_> {}
+
+/// Removes the VCL NAL units with a temporal id above `max_temporal_id` from
+/// an access unit, to drop higher temporal sub-layers and reduce the frame rate.
+///
+/// Non-VCL NAL units, such as parameter sets and SEI messages, are always
+/// kept. The returned access unit uses the same format as the input, Annex B
+/// NAL units are written with 4 byte start codes.
+///
+/// Pictures only reference pictures with the same or a lower temporal id, so
+/// the remaining sub-layers can still be decoded.
+/// ISO/IEC 23008-2:2020(E) - 10
+pub fn filter_temporal_sublayers(access_unit: &[u8], format: NalUnitFormat, max_temporal_id: u8) -> io::Result<Vec<u8>> {
+    let mut filtered = Vec::with_capacity(access_unit.len());
+
+    for nal in nal_units(access_unit, format) {
+        let nal = nal?;
+        let header = NalUnitHeader::parse(nal)?;
+        if header.is_vcl() && header.temporal_id() > max_temporal_id {
+            continue;
+        }
+
+        match format {
+            NalUnitFormat::LengthPrefixed(length_size) => {
+                // The length was read with the same size, so it fits.
+                let length = (nal.len() as u32).to_be_bytes();
+                filtered.extend_from_slice(&length[4 - length_size as usize..]);
+            }
+            NalUnitFormat::AnnexB => filtered.extend_from_slice(&[0x00, 0x00, 0x00, 0x01]),
+        }
+
+        filtered.extend_from_slice(nal);
+    }
+
+    Ok(filtered)
+}
+
+fn find_start_code(data: &[u8]) -> Option<usize> {
+    data.windows(3).position(|w| w == [0, 0, 1])
+}
@@ -1,6 +1,6 @@
 use std::io;
 
-use byteorder::ReadBytesExt;
+use byteorder::{BigEndian, ReadBytesExt};
 use bytes::Bytes;
 use scuffle_bytes_util::BitReader;
 use scuffle_expgolomb::BitReaderExpGolombExt;
@@ -13,6 +13,13 @@ pub struct Sps {
     pub height: u64,
     pub frame_rate: f64,
     pub color_config: Option<ColorConfig>,
+    pub aspect_ratio_info: Option<AspectRatioInfo>,
+    /// The `nuh_layer_id` of the NAL unit header. Non-zero for the enhancement
+    /// layers of scalable or multiview (L-HEVC) streams.
+    pub nuh_layer_id: u8,
+    /// Whether a `sps_multilayer_extension()` is present.
+    /// ISO/IEC-23008-2-2022 - F.7.3.2.2.4
+    pub multilayer_extension: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -24,14 +31,73 @@ pub struct ColorConfig {
     pub matrix_coefficients: u8,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+/// Aspect ratio info for SPS
+/// ISO/IEC-23008-2-2022 - E.2.1
+pub struct AspectRatioInfo {
+    pub aspect_ratio_idc: u8,
+    /// Only set when `aspect_ratio_idc` is 255 (Extended_SAR).
+    pub sar_width: u16,
+    /// Only set when `aspect_ratio_idc` is 255 (Extended_SAR).
+    pub sar_height: u16,
+}
+
+impl AspectRatioInfo {
+    /// `aspect_ratio_idc` value indicating that the ratio is given explicitly
+    /// by `sar_width` and `sar_height`.
+    pub const EXTENDED_SAR: u8 = 255;
+}
+
 impl Sps {
+    /// Returns `true` if this is the SPS of the base layer, which is the
+    /// only layer of a single-layer stream.
+    pub fn is_base_layer(&self) -> bool {
+        self.nuh_layer_id == 0
+    }
+
+    /// Returns the sample aspect ratio as `(width, height)`.
+    ///
+    /// Maps `aspect_ratio_idc` using ISO/IEC-23008-2-2022 - Table E.1, or
+    /// returns the explicit `sar_width` and `sar_height` for Extended_SAR.
+    /// Returns `None` if no aspect ratio info is present, or if the ratio is
+    /// unspecified or reserved.
+    pub fn sample_aspect_ratio(&self) -> Option<(u32, u32)> {
+        let info = self.aspect_ratio_info.as_ref()?;
+
+        let sar = match info.aspect_ratio_idc {
+            1 => (1, 1),
+            2 => (12, 11),
+            3 => (10, 11),
+            4 => (16, 11),
+            5 => (40, 33),
+            6 => (24, 11),
+            7 => (20, 11),
+            8 => (32, 11),
+            9 => (80, 33),
+            10 => (18, 11),
+            11 => (15, 11),
+            12 => (64, 33),
+            13 => (160, 99),
+            14 => (4, 3),
+            15 => (3, 2),
+            16 => (2, 1),
+            AspectRatioInfo::EXTENDED_SAR if info.sar_width != 0 && info.sar_height != 0 => {
+                (info.sar_width as u32, info.sar_height as u32)
+            }
+            // 0 is unspecified, 17..=254 are reserved
+            _ => return None,
+        };
+
+        Some(sar)
+    }
+
     pub fn parse(data: Bytes) -> io::Result<Self> {
         let mut vec = Vec::with_capacity(data.len());
 
         // ISO/IEC-23008-2-2022 - 7.3.1.1
         let mut i = 0;
-        while i < data.len() - 3 {
-            if data[i] == 0x00 && data[i + 1] == 0x00 && data[i + 2] == 0x03 {
+        while i < data.len() {
+            if i + 2 < data.len() && data[i] == 0x00 && data[i + 1] == 0x00 && data[i + 2] == 0x03 {
                 vec.push(0x00);
                 vec.push(0x00);
                 i += 3;
@@ -56,13 +122,22 @@ impl Sps {
             ));
         }
 
+        let nuh_layer_id = bit_reader.read_bits(6)? as u8;
         bit_reader.seek_bits(
-            6 // nuh_layer_id
-            + 3 // nuh_temporal_id_plus1
+            3 // nuh_temporal_id_plus1
             + 4, // sps_video_parameter_set_id
         )?;
 
+        // This is sps_ext_or_max_sub_layers_minus1 for non-base layers.
+        // ISO/IEC-23008-2-2022 - F.7.3.2.2.1
         let sps_max_sub_layers_minus1 = bit_reader.read_bits(3)?;
+        if nuh_layer_id > 0 && sps_max_sub_layers_minus1 == 7 {
+            // MultiLayerExtSpsFlag, the sps inherits most of its fields from the vps.
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "sps with MultiLayerExtSpsFlag is not supported",
+            ));
+        }
         bit_reader.seek_bits(1)?; // sps_temporal_id_nesting_flag
         {
             bit_reader.seek_bits(
@@ -146,9 +221,15 @@ impl Sps {
         let width = pic_width_in_luma_samples - sub_width_c * (conf_win_left_offset + conf_win_right_offset);
         let height = pic_height_in_luma_samples - sub_height_c * (conf_win_top_offset + conf_win_bottom_offset);
 
-        bit_reader.read_exp_golomb()?; // bit_depth_luma_minus8
-        bit_reader.read_exp_golomb()?; // bit_depth_chroma_minus8
-        bit_reader.read_exp_golomb()?; // log2_max_pic_order_cnt_lsb_minus4
+        let bit_depth_luma_minus8 = bit_reader.read_exp_golomb()?;
+        let bit_depth_chroma_minus8 = bit_reader.read_exp_golomb()?;
+        let log2_max_pic_order_cnt_lsb_minus4 = bit_reader.read_exp_golomb()?;
+        if bit_depth_luma_minus8 > 8 || bit_depth_chroma_minus8 > 8 || log2_max_pic_order_cnt_lsb_minus4 > 12 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "bit depth or log2_max_pic_order_cnt_lsb_minus4 out of range",
+            ));
+        }
         let sps_sub_layer_ordering_info_present_flag = bit_reader.read_bit()?;
 
         if sps_sub_layer_ordering_info_present_flag {
@@ -170,27 +251,7 @@ impl Sps {
         if scaling_list_enabled_flag {
             let sps_scaling_list_data_present_flag = bit_reader.read_bit()?;
             if sps_scaling_list_data_present_flag {
-                for size_id in 0..4 {
-                    let mut matrix_id = 0;
-                    while matrix_id < 6 {
-                        let scaling_list_pred_mode_flag = bit_reader.read_bit()?;
-                        if !scaling_list_pred_mode_flag {
-                            bit_reader.read_exp_golomb()?; // scaling_list_pred_matrix_id_delta
-                        } else {
-                            let coef_num = 64.min(1 << (4 + (size_id << 1)));
-                            let mut next_coef = 8;
-                            if size_id > 1 {
-                                let scaling_list_dc_coef_minus8 = bit_reader.read_signed_exp_golomb()?;
-                                next_coef = 8 + scaling_list_dc_coef_minus8;
-                            }
-                            for _ in 0..coef_num {
-                                let scaling_list_delta_coef = bit_reader.read_signed_exp_golomb()?;
-                                next_coef = (next_coef + scaling_list_delta_coef + 256) % 256;
-                            }
-                        }
-                        matrix_id += if size_id == 3 { 3 } else { 1 };
-                    }
-                }
+                skip_scaling_list_data(&mut bit_reader)?;
             }
         }
 
@@ -207,47 +268,31 @@ impl Sps {
         }
 
         let num_short_term_ref_pic_sets = bit_reader.read_exp_golomb()?;
-        let mut num_delta_pocs = vec![0; num_short_term_ref_pic_sets as usize];
-        for st_rps_idx in 0..num_short_term_ref_pic_sets {
-            if st_rps_idx != 0 && bit_reader.read_bit()? {
-                bit_reader.seek_bits(1)?;
-                bit_reader.read_exp_golomb()?; // delta_rps_sign
-
-                num_delta_pocs[st_rps_idx as usize] = 0;
-
-                for _ in 0..num_delta_pocs[(st_rps_idx - 1) as usize] {
-                    let used_by_curr_pic_flag = bit_reader.read_bit()?;
-                    let use_delta_flag = if !used_by_curr_pic_flag {
-                        bit_reader.read_bit()? // use_delta_flag
-                    } else {
-                        false
-                    };
-
-                    if used_by_curr_pic_flag || use_delta_flag {
-                        num_delta_pocs[st_rps_idx as usize] += 1;
-                    }
-                }
-            } else {
-                let num_negative_pics = bit_reader.read_exp_golomb()?;
-                let num_positive_pics = bit_reader.read_exp_golomb()?;
+        if num_short_term_ref_pic_sets > 64 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "num_short_term_ref_pic_sets is greater than 64",
+            ));
+        }
 
-                num_delta_pocs[st_rps_idx as usize] = num_negative_pics + num_positive_pics;
-                for _ in 0..num_negative_pics {
-                    bit_reader.read_exp_golomb()?; // delta_poc_s0_minus1
-                    bit_reader.seek_bits(1)?; // used_by_curr_pic_s0_flag
-                }
-                for _ in 0..num_positive_pics {
-                    bit_reader.read_exp_golomb()?; // delta_poc_s1_minus1
-                    bit_reader.seek_bits(1)?; // used_by_curr_pic_s1_flag
-                }
-            }
+        let mut num_delta_pocs = Vec::with_capacity(num_short_term_ref_pic_sets as usize);
+        for st_rps_idx in 0..num_short_term_ref_pic_sets as usize {
+            let num_delta_pocs_st_rps = skip_st_ref_pic_set(&mut bit_reader, st_rps_idx, &num_delta_pocs)?;
+            num_delta_pocs.push(num_delta_pocs_st_rps);
         }
 
         let long_term_ref_pics_present_flag = bit_reader.read_bit()?;
         if long_term_ref_pics_present_flag {
             let num_long_term_ref_pics_sps = bit_reader.read_exp_golomb()?;
+            if num_long_term_ref_pics_sps > 32 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "num_long_term_ref_pics_sps is greater than 32",
+                ));
+            }
+
             for _ in 0..num_long_term_ref_pics_sps {
-                bit_reader.read_exp_golomb()?; // lt_ref_pic_poc_lsb_sps
+                bit_reader.seek_bits(log2_max_pic_order_cnt_lsb_minus4 as i64 + 4)?; // lt_ref_pic_poc_lsb_sps
                 bit_reader.seek_bits(1)?; // used_by_curr_pic_lt_sps_flag
             }
         }
@@ -257,16 +302,25 @@ impl Sps {
         let vui_parameters_present_flag = bit_reader.read_bit()?;
 
         let mut color_config = None;
+        let mut aspect_ratio_info = None;
 
         let mut frame_rate = 0.0;
         if vui_parameters_present_flag {
             let aspect_ratio_info_present_flag = bit_reader.read_bit()?;
             if aspect_ratio_info_present_flag {
-                let aspect_ratio_idc = bit_reader.read_bits(8)?;
-                if aspect_ratio_idc == 255 {
-                    bit_reader.seek_bits(16)?; // sar_width
-                    bit_reader.seek_bits(16)?; // sar_height
+                let aspect_ratio_idc = bit_reader.read_u8()?;
+                let mut sar_width = 0;
+                let mut sar_height = 0;
+                if aspect_ratio_idc == AspectRatioInfo::EXTENDED_SAR {
+                    sar_width = bit_reader.read_u16::<BigEndian>()?;
+                    sar_height = bit_reader.read_u16::<BigEndian>()?;
                 }
+
+                aspect_ratio_info = Some(AspectRatioInfo {
+                    aspect_ratio_idc,
+                    sar_width,
+                    sar_height,
+                });
             }
 
             let overscan_info_present_flag = bit_reader.read_bit()?;
@@ -332,6 +386,131 @@ impl Sps {
                 }
 
                 frame_rate = time_scale as f64 / num_units_in_tick as f64;
+
+                let vui_poc_proportional_to_timing_flag = bit_reader.read_bit()?;
+                if vui_poc_proportional_to_timing_flag {
+                    bit_reader.read_exp_golomb()?; // vui_num_ticks_poc_diff_one_minus1
+                }
+
+                let vui_hrd_parameters_present_flag = bit_reader.read_bit()?;
+                if vui_hrd_parameters_present_flag {
+                    skip_hrd_parameters(&mut bit_reader, true, sps_max_sub_layers_minus1)?;
+                }
+            }
+
+            let bitstream_restriction_flag = bit_reader.read_bit()?;
+            if bitstream_restriction_flag {
+                bit_reader.seek_bits(1)?; // tiles_fixed_structure_flag
+                bit_reader.seek_bits(1)?; // motion_vectors_over_pic_boundaries_flag
+                bit_reader.seek_bits(1)?; // restricted_ref_pic_lists_flag
+                bit_reader.read_exp_golomb()?; // min_spatial_segmentation_idc
+                bit_reader.read_exp_golomb()?; // max_bytes_per_pic_denom
+                bit_reader.read_exp_golomb()?; // max_bits_per_min_cu_denom
+                bit_reader.read_exp_golomb()?; // log2_max_mv_length_horizontal
+                bit_reader.read_exp_golomb()?; // log2_max_mv_length_vertical
+            }
+        }
+
+        let sps_extension_present_flag = bit_reader.read_bit()?;
+        let mut sps_extension_4bits = 0;
+        let mut sps_multilayer_extension_flag = false;
+        if sps_extension_present_flag {
+            let sps_range_extension_flag = bit_reader.read_bit()?;
+            sps_multilayer_extension_flag = bit_reader.read_bit()?;
+            let sps_3d_extension_flag = bit_reader.read_bit()?;
+            let sps_scc_extension_flag = bit_reader.read_bit()?;
+            sps_extension_4bits = bit_reader.read_bits(4)?;
+
+            if sps_range_extension_flag {
+                // ISO/IEC-23008-2-2022 - 7.3.2.2.2
+                bit_reader.seek_bits(
+                    1 // transform_skip_rotation_enabled_flag
+                    + 1 // transform_skip_context_enabled_flag
+                    + 1 // implicit_rdpcm_enabled_flag
+                    + 1 // explicit_rdpcm_enabled_flag
+                    + 1 // extended_precision_processing_flag
+                    + 1 // intra_smoothing_disabled_flag
+                    + 1 // high_precision_offsets_enabled_flag
+                    + 1 // persistent_rice_adaptation_enabled_flag
+                    + 1, // cabac_bypass_alignment_enabled_flag
+                )?;
+            }
+
+            if sps_multilayer_extension_flag {
+                // ISO/IEC-23008-2-2022 - F.7.3.2.2.4
+                bit_reader.seek_bits(1)?; // inter_view_mv_vert_constraint_flag
+            }
+
+            if sps_3d_extension_flag {
+                // ISO/IEC-23008-2-2022 - I.7.3.2.2.5
+                for d in 0..=1 {
+                    bit_reader.seek_bits(1)?; // iv_di_mc_enabled_flag
+                    bit_reader.seek_bits(1)?; // iv_mv_scal_enabled_flag
+                    if d == 0 {
+                        bit_reader.read_exp_golomb()?; // log2_ivmc_sub_pb_size_minus3
+                        bit_reader.seek_bits(1)?; // iv_res_pred_enabled_flag
+                        bit_reader.seek_bits(1)?; // depth_ref_enabled_flag
+                        bit_reader.seek_bits(1)?; // vsp_mc_enabled_flag
+                        bit_reader.seek_bits(1)?; // dbbp_enabled_flag
+                    } else {
+                        bit_reader.seek_bits(1)?; // tex_mc_enabled_flag
+                        bit_reader.read_exp_golomb()?; // log2_texmc_sub_pb_size_minus3
+                        bit_reader.seek_bits(1)?; // intra_contour_enabled_flag
+                        bit_reader.seek_bits(1)?; // intra_dc_only_wedge_enabled_flag
+                        bit_reader.seek_bits(1)?; // cqt_cu_part_pred_enabled_flag
+                        bit_reader.seek_bits(1)?; // inter_dc_only_enabled_flag
+                        bit_reader.seek_bits(1)?; // skip_intra_enabled_flag
+                    }
+                }
+            }
+
+            if sps_scc_extension_flag {
+                // ISO/IEC-23008-2-2022 - 7.3.2.2.3
+                bit_reader.seek_bits(1)?; // sps_curr_pic_ref_enabled_flag
+                let palette_mode_enabled_flag = bit_reader.read_bit()?;
+                if palette_mode_enabled_flag {
+                    bit_reader.read_exp_golomb()?; // palette_max_size
+                    bit_reader.read_exp_golomb()?; // delta_palette_max_predictor_size
+                    let sps_palette_predictor_initializers_present_flag = bit_reader.read_bit()?;
+                    if sps_palette_predictor_initializers_present_flag {
+                        let sps_num_palette_predictor_initializers_minus1 = bit_reader.read_exp_golomb()?;
+                        // PaletteMaxPredictorSize is at most 128
+                        if sps_num_palette_predictor_initializers_minus1 >= 128 {
+                            return Err(io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                "sps_num_palette_predictor_initializers_minus1 is greater than 127",
+                            ));
+                        }
+
+                        let num_comps = if chroma_format_idc == 0 { 1 } else { 3 };
+                        for comp in 0..num_comps {
+                            let bit_depth = if comp == 0 {
+                                bit_depth_luma_minus8 + 8
+                            } else {
+                                bit_depth_chroma_minus8 + 8
+                            };
+
+                            for _ in 0..=sps_num_palette_predictor_initializers_minus1 {
+                                bit_reader.seek_bits(bit_depth as i64)?; // sps_palette_predictor_initializer
+                            }
+                        }
+                    }
+                }
+
+                bit_reader.seek_bits(2)?; // motion_vector_resolution_control_idc
+                bit_reader.seek_bits(1)?; // intra_boundary_filtering_disabled_flag
+            }
+        }
+
+        // The contents of sps_extension_data_flag are not specified, so we can only
+        // check that everything was consumed when there is none.
+        if sps_extension_4bits == 0 {
+            let rbsp_stop_one_bit = bit_reader.read_bit()?;
+            if !rbsp_stop_one_bit {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "rbsp_stop_one_bit is not one, the sps was not fully consumed",
+                ));
             }
         }
 
@@ -340,6 +519,172 @@ impl Sps {
             height,
             frame_rate,
             color_config,
+            aspect_ratio_info,
+            nuh_layer_id,
+            multilayer_extension: sps_multilayer_extension_flag,
         })
     }
 }
+
+/// Skips over a `hrd_parameters(commonInfPresentFlag, maxNumSubLayersMinus1)` structure.
+/// ISO/IEC-23008-2-2022 - E.2.2
+fn skip_hrd_parameters<T: io::Read + io::Seek>(
+    bit_reader: &mut BitReader<T>,
+    common_inf_present_flag: bool,
+    max_num_sub_layers_minus1: u64,
+) -> io::Result<()> {
+    let mut nal_hrd_parameters_present_flag = false;
+    let mut vcl_hrd_parameters_present_flag = false;
+    let mut sub_pic_hrd_params_present_flag = false;
+
+    if common_inf_present_flag {
+        nal_hrd_parameters_present_flag = bit_reader.read_bit()?;
+        vcl_hrd_parameters_present_flag = bit_reader.read_bit()?;
+
+        if nal_hrd_parameters_present_flag || vcl_hrd_parameters_present_flag {
+            sub_pic_hrd_params_present_flag = bit_reader.read_bit()?;
+            if sub_pic_hrd_params_present_flag {
+                bit_reader.seek_bits(
+                    8 // tick_divisor_minus2
+                    + 5 // du_cpb_removal_delay_increment_length_minus1
+                    + 1 // sub_pic_cpb_params_in_pic_timing_sei_flag
+                    + 5, // dpb_output_delay_du_length_minus1
+                )?;
+            }
+
+            bit_reader.seek_bits(4)?; // bit_rate_scale
+            bit_reader.seek_bits(4)?; // cpb_size_scale
+            if sub_pic_hrd_params_present_flag {
+                bit_reader.seek_bits(4)?; // cpb_size_du_scale
+            }
+
+            bit_reader.seek_bits(
+                5 // initial_cpb_removal_delay_length_minus1
+                + 5 // au_cpb_removal_delay_length_minus1
+                + 5, // dpb_output_delay_length_minus1
+            )?;
+        }
+    }
+
+    for _ in 0..=max_num_sub_layers_minus1 {
+        let fixed_pic_rate_general_flag = bit_reader.read_bit()?;
+        // fixed_pic_rate_within_cvs_flag is inferred to be 1 when fixed_pic_rate_general_flag is 1
+        let fixed_pic_rate_within_cvs_flag = fixed_pic_rate_general_flag || bit_reader.read_bit()?;
+
+        // low_delay_hrd_flag is inferred to be 0 when not present
+        let mut low_delay_hrd_flag = false;
+        if fixed_pic_rate_within_cvs_flag {
+            bit_reader.read_exp_golomb()?; // elemental_duration_in_tc_minus1
+        } else {
+            low_delay_hrd_flag = bit_reader.read_bit()?;
+        }
+
+        let mut cpb_cnt_minus1 = 0;
+        if !low_delay_hrd_flag {
+            cpb_cnt_minus1 = bit_reader.read_exp_golomb()?;
+        }
+
+        if cpb_cnt_minus1 > 31 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "cpb_cnt_minus1 is greater than 31",
+            ));
+        }
+
+        let sub_layer_count = nal_hrd_parameters_present_flag as u8 + vcl_hrd_parameters_present_flag as u8;
+        for _ in 0..sub_layer_count {
+            // sub_layer_hrd_parameters(i)
+            // ISO/IEC-23008-2-2022 - E.2.3
+            for _ in 0..=cpb_cnt_minus1 {
+                bit_reader.read_exp_golomb()?; // bit_rate_value_minus1
+                bit_reader.read_exp_golomb()?; // cpb_size_value_minus1
+                if sub_pic_hrd_params_present_flag {
+                    bit_reader.read_exp_golomb()?; // cpb_size_du_value_minus1
+                    bit_reader.read_exp_golomb()?; // bit_rate_du_value_minus1
+                }
+                bit_reader.seek_bits(1)?; // cbr_flag
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Skips over a `scaling_list_data()` structure.
+/// ISO/IEC-23008-2-2022 - 7.3.4
+fn skip_scaling_list_data<T: io::Read>(bit_reader: &mut BitReader<T>) -> io::Result<()> {
+    for size_id in 0..4 {
+        let mut matrix_id = 0;
+        while matrix_id < 6 {
+            let scaling_list_pred_mode_flag = bit_reader.read_bit()?;
+            if !scaling_list_pred_mode_flag {
+                bit_reader.read_exp_golomb()?; // scaling_list_pred_matrix_id_delta
+            } else {
+                let coef_num = 64.min(1 << (4 + (size_id << 1)));
+                if size_id > 1 {
+                    bit_reader.read_signed_exp_golomb()?; // scaling_list_dc_coef_minus8
+                }
+
+                for _ in 0..coef_num {
+                    bit_reader.read_signed_exp_golomb()?; // scaling_list_delta_coef
+                }
+            }
+
+            matrix_id += if size_id == 3 { 3 } else { 1 };
+        }
+    }
+
+    Ok(())
+}
+
+/// Skips over a `st_ref_pic_set(stRpsIdx)` structure in the SPS and returns
+/// its `NumDeltaPocs`.
+///
+/// `num_delta_pocs` contains the `NumDeltaPocs` of all previous sets.
+/// ISO/IEC-23008-2-2022 - 7.3.7
+fn skip_st_ref_pic_set<T: io::Read + io::Seek>(
+    bit_reader: &mut BitReader<T>,
+    st_rps_idx: usize,
+    num_delta_pocs: &[u64],
+) -> io::Result<u64> {
+    let inter_ref_pic_set_prediction_flag = st_rps_idx != 0 && bit_reader.read_bit()?;
+
+    if inter_ref_pic_set_prediction_flag {
+        // delta_idx_minus1 is only present in slice headers, so RefRpsIdx is always the previous set.
+        // ISO/IEC-23008-2-2022 - 7.4.8
+        let ref_num_delta_pocs = num_delta_pocs[st_rps_idx - 1];
+
+        bit_reader.seek_bits(1)?; // delta_rps_sign
+        bit_reader.read_exp_golomb()?; // abs_delta_rps_minus1
+
+        // There is one more entry than NumDeltaPocs[RefRpsIdx], for the reference picture itself.
+        let mut delta_pocs = 0;
+        for _ in 0..=ref_num_delta_pocs {
+            let used_by_curr_pic_flag = bit_reader.read_bit()?;
+            // use_delta_flag is inferred to be 1 when not present
+            let use_delta_flag = used_by_curr_pic_flag || bit_reader.read_bit()?;
+
+            // ISO/IEC-23008-2-2022 - 7.4.8 (7-61, 7-62)
+            if use_delta_flag {
+                delta_pocs += 1;
+            }
+        }
+
+        Ok(delta_pocs)
+    } else {
+        let num_negative_pics = bit_reader.read_exp_golomb()?;
+        let num_positive_pics = bit_reader.read_exp_golomb()?;
+
+        for _ in 0..num_negative_pics {
+            bit_reader.read_exp_golomb()?; // delta_poc_s0_minus1
+            bit_reader.seek_bits(1)?; // used_by_curr_pic_s0_flag
+        }
+
+        for _ in 0..num_positive_pics {
+            bit_reader.read_exp_golomb()?; // delta_poc_s1_minus1
+            bit_reader.seek_bits(1)?; // used_by_curr_pic_s1_flag
+        }
+
+        Ok(num_negative_pics + num_positive_pics)
+    }
+}
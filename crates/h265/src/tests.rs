@@ -1,9 +1,10 @@
 use std::io;
 
 use bytes::Bytes;
+use scuffle_bytes_util::BitWriter;
 
-use crate::sps::{ColorConfig, Sps};
-use crate::{HEVCDecoderConfigurationRecord, NaluType};
+use crate::sps::{AspectRatioInfo, ColorConfig, Sps};
+use crate::{HEVCDecoderConfigurationRecord, NalUnitFormat, NalUnitHeader, NaluType, filter_temporal_sublayers, nal_units};
 
 #[test]
 fn test_sps_parse() {
@@ -22,10 +23,261 @@ fn test_sps_parse() {
             frame_rate: 144.0,
             width: 2560,
             height: 1440,
+            aspect_ratio_info: Some(AspectRatioInfo {
+                aspect_ratio_idc: 1,
+                sar_width: 0,
+                sar_height: 0,
+            }),
+            nuh_layer_id: 0,
+            multilayer_extension: false,
+        }
+    );
+}
+
+#[test]
+fn test_sps_parse_scaling_list_data() {
+    // Same as above but with scaling_list_enabled_flag and sps_scaling_list_data_present_flag set,
+    // followed by a scaling_list_data() with explicit coefficients for the first matrix of every size.
+    let data = b"B\x01\x01\x01@\0\0\x03\0\x90\0\0\x03\0\0\x03\0\x99\xa0\x01@ \x05\xa1e\x95R\x90\x84\xe9\xa6\x9ai\xa6\x9adDEM4\xd3M4\xd3M4\xd3M4\xd3M4\xd3M4\xd3M4\xd3M4\xd3\x22\x22(A4\xd3M4\xd3M4\xd3M4\xd3M4\xd3M4\xd3M4\xd3M4\xd3L\x88\x88\xa1\x04\xd3M4\xd3M4\xd3M4\xd3M4\xd3M4\xd3M4\xd3M4\xd3M7\x22\xff\xc6\x02\xd4\x04\x04\x04\x10\0\0\x03\0\x10\0\0\x09\x06\0]\xe5\x10\0\x13\x12\xc0\0\x09\x89h@".to_vec();
+
+    let sps = Sps::parse(Bytes::from(data)).unwrap();
+    assert_eq!(
+        sps,
+        Sps {
+            color_config: Some(ColorConfig {
+                full_range: false,
+                color_primaries: 1,
+                matrix_coefficients: 1,
+                transfer_characteristics: 1,
+            }),
+            frame_rate: 144.0,
+            width: 2560,
+            height: 1440,
+            aspect_ratio_info: Some(AspectRatioInfo {
+                aspect_ratio_idc: 1,
+                sar_width: 0,
+                sar_height: 0,
+            }),
+            nuh_layer_id: 0,
+            multilayer_extension: false,
+        }
+    );
+}
+
+#[test]
+fn test_sps_parse_inter_ref_pic_set_prediction() {
+    // Same as above but with 3 short-term reference picture sets:
+    // - 0: 2 negative and 1 positive pictures
+    // - 1: predicted from set 0, using 3 of its 4 entries
+    // - 2: predicted from set 1, using all 4 of its entries
+    let data = b"B\x01\x01\x01@\0\0\x03\0\x90\0\0\x03\0\0\x03\0\x99\xa0\x01@ \x05\xa1e\x95R\x90\x84b5\xfd\xa7^0\x16\xa0   \x80\0\0\x03\0\x80\0\0H0\x02\xef(\x80\0\x98\x96\0\0LKB\0".to_vec();
+
+    let sps = Sps::parse(Bytes::from(data)).unwrap();
+    assert_eq!(
+        sps,
+        Sps {
+            color_config: Some(ColorConfig {
+                full_range: false,
+                color_primaries: 1,
+                matrix_coefficients: 1,
+                transfer_characteristics: 1,
+            }),
+            frame_rate: 144.0,
+            width: 2560,
+            height: 1440,
+            aspect_ratio_info: Some(AspectRatioInfo {
+                aspect_ratio_idc: 1,
+                sar_width: 0,
+                sar_height: 0,
+            }),
+            nuh_layer_id: 0,
+            multilayer_extension: false,
+        }
+    );
+}
+
+#[test]
+fn test_sps_parse_pcm_enabled() {
+    // Same as the first test but with pcm_enabled_flag set, 8 bit pcm samples,
+    // log2_diff_max_min_pcm_luma_coding_block_size = 1 and pcm_loop_filter_disabled_flag set.
+    let data = b"B\x01\x01\x01@\0\0\x03\0\x90\0\0\x03\0\0\x03\0\x99\xa0\x01@ \x05\xa1e\x95R\x90\x84wz\xa2\xff\xc6\x02\xd4\x04\x04\x04\x10\0\0\x03\0\x10\0\0\x09\x06\0]\xe5\x10\0\x13\x12\xc0\0\x09\x89h@".to_vec();
+
+    let sps = Sps::parse(Bytes::from(data)).unwrap();
+    assert_eq!(
+        sps,
+        Sps {
+            color_config: Some(ColorConfig {
+                full_range: false,
+                color_primaries: 1,
+                matrix_coefficients: 1,
+                transfer_characteristics: 1,
+            }),
+            frame_rate: 144.0,
+            width: 2560,
+            height: 1440,
+            aspect_ratio_info: Some(AspectRatioInfo {
+                aspect_ratio_idc: 1,
+                sar_width: 0,
+                sar_height: 0,
+            }),
+            nuh_layer_id: 0,
+            multilayer_extension: false,
+        }
+    );
+}
+
+#[test]
+fn test_sps_parse_extensions() {
+    // Same as the first test but with sps_extension_present_flag set, followed by
+    // a sps_range_extension() and a sps_multilayer_extension().
+    let data = b"B\x01\x01\x01@\0\0\x03\0\x90\0\0\x03\0\0\x03\0\x99\xa0\x01@ \x05\xa1e\x95R\x90\x84d_\xf8\xc0Z\x80\x80\x80\x82\0\0\x03\0\x02\0\0\x03\x01 \xc0\x0b\xbc\xa2\0\x02bX\0\x011-\x1c\x0a\xae".to_vec();
+
+    let sps = Sps::parse(Bytes::from(data)).unwrap();
+    assert_eq!(
+        sps,
+        Sps {
+            color_config: Some(ColorConfig {
+                full_range: false,
+                color_primaries: 1,
+                matrix_coefficients: 1,
+                transfer_characteristics: 1,
+            }),
+            frame_rate: 144.0,
+            width: 2560,
+            height: 1440,
+            aspect_ratio_info: Some(AspectRatioInfo {
+                aspect_ratio_idc: 1,
+                sar_width: 0,
+                sar_height: 0,
+            }),
+            nuh_layer_id: 0,
+            multilayer_extension: true,
         }
     );
 }
 
+#[test]
+fn test_sps_parse_extensions_mismatch() {
+    // sps_range_extension_flag is set, but the sps ends before the sps_range_extension().
+    let data = b"B\x01\x01\x01@\0\0\x03\0\x90\0\0\x03\0\0\x03\0\x99\xa0\x01@ \x05\xa1e\x95R\x90\x84d_\xf8\xc0Z\x80\x80\x80\x82\0\0\x03\0\x02\0\0\x03\x01 \xc0\x0b\xbc\xa2\0\x02bX\0\x011-\x18\x08".to_vec();
+    let err = Sps::parse(Bytes::from(data)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+    // No extension flag is set, but a sps_range_extension() follows.
+    let data = b"B\x01\x01\x01@\0\0\x03\0\x90\0\0\x03\0\0\x03\0\x99\xa0\x01@ \x05\xa1e\x95R\x90\x84d_\xf8\xc0Z\x80\x80\x80\x82\0\0\x03\0\x02\0\0\x03\x01 \xc0\x0b\xbc\xa2\0\x02bX\0\x011-\x10\x05T".to_vec();
+    let err = Sps::parse(Bytes::from(data)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+/// Writes an unsigned exp-golomb code.
+fn write_exp_golomb(bits: &mut BitWriter<Vec<u8>>, value: u64) {
+    let len = 64 - (value + 1).leading_zeros() as u8;
+    bits.write_bits(0, len - 1).unwrap();
+    bits.write_bits(value + 1, len).unwrap();
+}
+
+/// Builds a 64x64 sps with `num_long_term_ref_pics_sps` long term reference
+/// pictures and, if set, a sps_scc_extension() with
+/// `sps_num_palette_predictor_initializers_minus1` palette predictor initializers.
+fn build_sps(num_long_term_ref_pics_sps: u64, palette_predictor_initializers_minus1: Option<u64>) -> Bytes {
+    let mut bits = BitWriter::new(Vec::new());
+    bits.write_bits(0x4201, 16).unwrap(); // nal unit header
+    bits.write_bits(0, 4).unwrap(); // sps_video_parameter_set_id
+    bits.write_bits(0, 3).unwrap(); // sps_max_sub_layers_minus1
+    bits.write_bit(true).unwrap(); // sps_temporal_id_nesting_flag
+    bits.write_bits(0x01, 8).unwrap(); // general_profile_space, general_tier_flag, general_profile_idc
+    bits.write_bits(0x6000_0000, 32).unwrap(); // general_profile_compatibility_flag
+    bits.write_bits(0b1001, 4).unwrap(); // general_progressive_source_flag .. general_frame_only_constraint_flag
+    bits.write_bits(0, 44).unwrap(); // general_reserved_zero_43bits, general_reserved_zero_bit
+    bits.write_bits(93, 8).unwrap(); // general_level_idc
+    write_exp_golomb(&mut bits, 0); // sps_seq_parameter_set_id
+    write_exp_golomb(&mut bits, 1); // chroma_format_idc
+    write_exp_golomb(&mut bits, 64); // pic_width_in_luma_samples
+    write_exp_golomb(&mut bits, 64); // pic_height_in_luma_samples
+    bits.write_bit(false).unwrap(); // conformance_window_flag
+    write_exp_golomb(&mut bits, 0); // bit_depth_luma_minus8
+    write_exp_golomb(&mut bits, 0); // bit_depth_chroma_minus8
+    write_exp_golomb(&mut bits, 4); // log2_max_pic_order_cnt_lsb_minus4
+    bits.write_bit(true).unwrap(); // sps_sub_layer_ordering_info_present_flag
+    write_exp_golomb(&mut bits, 4); // sps_max_dec_pic_buffering_minus1
+    write_exp_golomb(&mut bits, 0); // sps_max_num_reorder_pics
+    write_exp_golomb(&mut bits, 0); // sps_max_latency_increase_plus1
+    write_exp_golomb(&mut bits, 0); // log2_min_luma_coding_block_size_minus3
+    write_exp_golomb(&mut bits, 1); // log2_diff_max_min_luma_coding_block_size
+    write_exp_golomb(&mut bits, 0); // log2_min_transform_block_size_minus2
+    write_exp_golomb(&mut bits, 1); // log2_diff_max_min_transform_block_size
+    write_exp_golomb(&mut bits, 0); // max_transform_hierarchy_depth_inter
+    write_exp_golomb(&mut bits, 0); // max_transform_hierarchy_depth_intra
+    bits.write_bits(0, 4).unwrap(); // scaling_list_enabled_flag, amp, sao, pcm_enabled_flag
+    write_exp_golomb(&mut bits, 0); // num_short_term_ref_pic_sets
+    bits.write_bit(true).unwrap(); // long_term_ref_pics_present_flag
+    write_exp_golomb(&mut bits, num_long_term_ref_pics_sps);
+    for _ in 0..num_long_term_ref_pics_sps {
+        bits.write_bits(0xff, 8).unwrap(); // lt_ref_pic_poc_lsb_sps
+        bits.write_bit(true).unwrap(); // used_by_curr_pic_lt_sps_flag
+    }
+    bits.write_bits(0b11, 2).unwrap(); // sps_temporal_mvp_enabled_flag, strong_intra_smoothing_enabled_flag
+    bits.write_bit(false).unwrap(); // vui_parameters_present_flag
+
+    bits.write_bit(palette_predictor_initializers_minus1.is_some()).unwrap(); // sps_extension_present_flag
+    if let Some(palette_predictor_initializers_minus1) = palette_predictor_initializers_minus1 {
+        bits.write_bits(0b0001, 4).unwrap(); // only sps_scc_extension_flag
+        bits.write_bits(0, 4).unwrap(); // sps_extension_4bits
+        bits.write_bit(false).unwrap(); // sps_curr_pic_ref_enabled_flag
+        bits.write_bit(true).unwrap(); // palette_mode_enabled_flag
+        write_exp_golomb(&mut bits, 64); // palette_max_size
+        write_exp_golomb(&mut bits, 64); // delta_palette_max_predictor_size
+        bits.write_bit(true).unwrap(); // sps_palette_predictor_initializers_present_flag
+        write_exp_golomb(&mut bits, palette_predictor_initializers_minus1);
+        for _ in 0..3 * (palette_predictor_initializers_minus1 + 1) {
+            bits.write_bits(0xff, 8).unwrap(); // sps_palette_predictor_initializer
+        }
+        bits.write_bits(0, 2).unwrap(); // motion_vector_resolution_control_idc
+        bits.write_bit(false).unwrap(); // intra_boundary_filtering_disabled_flag
+    }
+
+    bits.write_bit(true).unwrap(); // rbsp_stop_one_bit
+    Bytes::from(bits.finish().unwrap())
+}
+
+#[test]
+fn test_sps_parse_range_limits() {
+    let sps = Sps::parse(build_sps(32, None)).unwrap();
+    assert_eq!((sps.width, sps.height), (64, 64));
+    let err = Sps::parse(build_sps(33, None)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    let sps = Sps::parse(build_sps(0, Some(127))).unwrap();
+    assert_eq!((sps.width, sps.height), (64, 64));
+    let err = Sps::parse(build_sps(0, Some(128))).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_sps_layers() {
+    let data = b"B\x01\x01\x01@\0\0\x03\0\x90\0\0\x03\0\0\x03\0\x99\xa0\x01@ \x05\xa1e\x95R\x90\x84d_\xf8\xc0Z\x80\x80\x80\x82\0\0\x03\0\x02\0\0\x03\x01 \xc0\x0b\xbc\xa2\0\x02bX\0\x011-\x08".to_vec();
+
+    let sps = Sps::parse(Bytes::from(data.clone())).unwrap();
+    assert!(sps.is_base_layer());
+    assert_eq!(sps.nuh_layer_id, 0);
+    assert!(!sps.multilayer_extension);
+
+    // Same sps with nuh_layer_id set to 1
+    let mut layered = data.clone();
+    layered[1] = 0x09;
+
+    let layered_sps = Sps::parse(Bytes::from(layered.clone())).unwrap();
+    assert!(!layered_sps.is_base_layer());
+    assert_eq!(layered_sps.nuh_layer_id, 1);
+    assert_eq!(layered_sps.width, sps.width);
+    assert_eq!(layered_sps.height, sps.height);
+
+    // sps_ext_or_max_sub_layers_minus1 set to 7, so the sps inherits its fields from the vps
+    layered[2] = 0x0f;
+    let err = Sps::parse(Bytes::from(layered)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+}
+
 #[test]
 fn test_config_demux() {
     // h265 config
@@ -74,6 +326,13 @@ fn test_config_demux() {
             frame_rate: 144.0,
             width: 2560,
             height: 1440,
+            aspect_ratio_info: Some(AspectRatioInfo {
+                aspect_ratio_idc: 1,
+                sar_width: 0,
+                sar_height: 0,
+            }),
+            nuh_layer_id: 0,
+            multilayer_extension: false,
         }
     );
 
@@ -112,3 +371,255 @@ fn test_parse_sps_with_zero_vui_num_units_in_tick() {
         ),
     }
 }
+
+#[test]
+fn test_sps_sample_aspect_ratio_square() {
+    let data = b"B\x01\x01\x01@\0\0\x03\0\x90\0\0\x03\0\0\x03\0\x99\xa0\x01@ \x05\xa1e\x95R\x90\x84d_\xf8\xc0Z\x80\x80\x80\x82\0\0\x03\0\x02\0\0\x03\x01 \xc0\x0b\xbc\xa2\0\x02bX\0\x011-\x08".to_vec();
+
+    let sps = Sps::parse(Bytes::from(data)).unwrap();
+    assert_eq!(sps.sample_aspect_ratio(), Some((1, 1)));
+}
+
+#[test]
+fn test_sps_sample_aspect_ratio_extended_sar() {
+    // Same as above but with aspect_ratio_idc = 255 (Extended_SAR), sar_width = 4, sar_height = 3
+    let data = b"B\x01\x01\x01@\0\0\x03\0\x90\0\0\x03\0\0\x03\0\x99\xa0\x01@ \x05\xa1e\x95R\x90\x84d_\xf8\xff\xc0\x01\0\0\xda\x80\x80\x80\x82\0\0\x03\0\x02\0\0\x03\x01 \xc0\x0b\xbc\xa2\0\x02bX\0\x011-\x08".to_vec();
+
+    let sps = Sps::parse(Bytes::from(data)).unwrap();
+    assert_eq!(
+        sps.aspect_ratio_info,
+        Some(AspectRatioInfo {
+            aspect_ratio_idc: 255,
+            sar_width: 4,
+            sar_height: 3,
+        })
+    );
+    assert_eq!(sps.sample_aspect_ratio(), Some((4, 3)));
+    assert_eq!(sps.width, 2560);
+    assert_eq!(sps.height, 1440);
+    assert_eq!(sps.frame_rate, 144.0);
+}
+
+#[test]
+fn test_sps_sample_aspect_ratio_table() {
+    let mut sps = Sps {
+        width: 1440,
+        height: 1080,
+        frame_rate: 30.0,
+        color_config: None,
+        aspect_ratio_info: None,
+        nuh_layer_id: 0,
+        multilayer_extension: false,
+    };
+    assert_eq!(sps.sample_aspect_ratio(), None);
+
+    for (aspect_ratio_idc, expected) in [
+        (0, None), // unspecified
+        (14, Some((4, 3))),
+        (13, Some((160, 99))),
+        (17, None),  // reserved
+        (255, None), // extended sar with zero dimensions
+    ] {
+        sps.aspect_ratio_info = Some(AspectRatioInfo {
+            aspect_ratio_idc,
+            sar_width: 0,
+            sar_height: 0,
+        });
+        assert_eq!(sps.sample_aspect_ratio(), expected);
+    }
+}
+
+fn collect_nal_units(data: &[u8], format: NalUnitFormat) -> io::Result<Vec<&[u8]>> {
+    nal_units(data, format).collect()
+}
+
+#[test]
+fn test_nal_units_annex_b() {
+    let data = [
+        0x00, 0x00, 0x00, 0x01, 0x40, 0x01, 0x0c, // 4 byte start code, vps
+        0x00, 0x00, 0x01, 0x42, 0x01, // 3 byte start code, sps
+        0x00, 0x00, 0x00, 0x01, 0x44, 0x01, 0xc0, // pps
+        0x00, 0x00, 0x01, 0x26, 0x01, 0x00, 0x00, 0x03, 0x00, 0xaf, // idr slice with emulation prevention
+        0x00, 0x00, 0x00, // trailing zero padding
+    ];
+
+    let nals = collect_nal_units(&data, NalUnitFormat::AnnexB).unwrap();
+    assert_eq!(
+        nals,
+        vec![
+            &[0x40, 0x01, 0x0c][..],
+            &[0x42, 0x01][..],
+            &[0x44, 0x01, 0xc0][..],
+            &[0x26, 0x01, 0x00, 0x00, 0x03, 0x00, 0xaf][..],
+        ]
+    );
+
+    let nal_types: Vec<_> = nals.iter().map(|nal| NaluType::from((nal[0] >> 1) & 0x3f)).collect();
+    assert_eq!(
+        nal_types,
+        vec![NaluType::Vps, NaluType::Sps, NaluType::Pps, NaluType::Unknown(19)]
+    );
+}
+
+#[test]
+fn test_nal_units_annex_b_empty() {
+    assert!(collect_nal_units(&[], NalUnitFormat::AnnexB).unwrap().is_empty());
+    assert!(
+        collect_nal_units(&[0x00, 0x00, 0x00, 0x00], NalUnitFormat::AnnexB)
+            .unwrap()
+            .is_empty()
+    );
+    // adjacent start codes with nothing in between
+    assert!(
+        collect_nal_units(&[0x00, 0x00, 0x01, 0x00, 0x00, 0x01], NalUnitFormat::AnnexB)
+            .unwrap()
+            .is_empty()
+    );
+    // garbage before the first start code is ignored
+    assert_eq!(
+        collect_nal_units(&[0xff, 0x00, 0x00, 0x01, 0x40, 0x01], NalUnitFormat::AnnexB).unwrap(),
+        vec![&[0x40, 0x01][..]]
+    );
+}
+
+#[test]
+fn test_nal_units_length_prefixed() {
+    let data = [
+        0x00, 0x00, 0x00, 0x03, 0x40, 0x01, 0x0c, // vps
+        0x00, 0x00, 0x00, 0x00, // zero length nal unit
+        0x00, 0x00, 0x00, 0x02, 0x42, 0x01, // sps
+        0x00, 0x00, // trailing zero padding
+    ];
+
+    assert_eq!(
+        collect_nal_units(&data, NalUnitFormat::LengthPrefixed(4)).unwrap(),
+        vec![&[0x40, 0x01, 0x0c][..], &[0x42, 0x01][..]]
+    );
+
+    let data = [0x00, 0x02, 0x40, 0x01, 0x00, 0x01, 0x42];
+    assert_eq!(
+        collect_nal_units(&data, NalUnitFormat::LengthPrefixed(2)).unwrap(),
+        vec![&[0x40, 0x01][..], &[0x42][..]]
+    );
+
+    let data = [0x02, 0x40, 0x01, 0x01, 0x42];
+    assert_eq!(
+        collect_nal_units(&data, NalUnitFormat::LengthPrefixed(1)).unwrap(),
+        vec![&[0x40, 0x01][..], &[0x42][..]]
+    );
+}
+
+#[test]
+fn test_nal_units_length_prefixed_errors() {
+    // length exceeds the remaining data
+    let data = [0x00, 0x00, 0x00, 0x02, 0x40, 0x01, 0x00, 0x00, 0x00, 0x05, 0x42];
+    let mut iter = nal_units(&data, NalUnitFormat::LengthPrefixed(4));
+    assert_eq!(iter.next().unwrap().unwrap(), &[0x40, 0x01]);
+    assert_eq!(iter.next().unwrap().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    assert!(iter.next().is_none());
+
+    // truncated length prefix
+    let err = collect_nal_units(&[0x00, 0x01], NalUnitFormat::LengthPrefixed(4)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+    // invalid length size
+    let err = collect_nal_units(&[0x01, 0x40], NalUnitFormat::LengthPrefixed(5)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    let err = collect_nal_units(&[0x01, 0x40], NalUnitFormat::LengthPrefixed(0)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_nal_unit_header() {
+    let header = NalUnitHeader::parse(&[0x40, 0x01, 0x0c]).unwrap();
+    assert_eq!(header.nal_unit_type, NaluType::Vps);
+    assert_eq!(header.nuh_layer_id, 0);
+    assert_eq!(header.temporal_id(), 0);
+    assert!(!header.is_vcl());
+
+    // TRAIL_R, layer 1, temporal id 2
+    let header = NalUnitHeader::parse(&[0x02, 0x0b]).unwrap();
+    assert_eq!(header.nal_unit_type, NaluType::Unknown(1));
+    assert_eq!(header.nuh_layer_id, 1);
+    assert_eq!(header.temporal_id(), 2);
+    assert!(header.is_vcl());
+
+    assert_eq!(
+        NalUnitHeader::parse(&[0x40]).unwrap_err().kind(),
+        io::ErrorKind::UnexpectedEof
+    );
+    assert_eq!(
+        NalUnitHeader::parse(&[0xc0, 0x01]).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+    assert_eq!(
+        NalUnitHeader::parse(&[0x40, 0x00]).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+}
+
+#[test]
+fn test_filter_temporal_sublayers() {
+    let vps = [0x40, 0x01, 0x0c];
+    let sps = [0x42, 0x01, 0x01];
+    let pps = [0x44, 0x01, 0xc1];
+    // TRAIL_R slices with temporal ids 0, 1 and 2
+    let slice_t0 = [0x02, 0x01, 0xaa];
+    let slice_t1 = [0x02, 0x02, 0xbb];
+    let slice_t2 = [0x02, 0x03, 0xcc];
+    // A prefix SEI with temporal id 2, which is not a VCL nal unit
+    let sei_t2 = [0x4e, 0x03, 0x05];
+
+    let access_unit: [&[u8]; 7] = [&vps, &sps, &pps, &sei_t2, &slice_t0, &slice_t1, &slice_t2];
+
+    let annex_b = access_unit.iter().fold(Vec::new(), |mut data, nal| {
+        data.extend_from_slice(&[0x00, 0x00, 0x00, 0x01]);
+        data.extend_from_slice(nal);
+        data
+    });
+    let length_prefixed = access_unit.iter().fold(Vec::new(), |mut data, nal| {
+        data.extend_from_slice(&(nal.len() as u16).to_be_bytes());
+        data.extend_from_slice(nal);
+        data
+    });
+
+    for format in [NalUnitFormat::AnnexB, NalUnitFormat::LengthPrefixed(2)] {
+        let data = match format {
+            NalUnitFormat::AnnexB => &annex_b,
+            NalUnitFormat::LengthPrefixed(_) => &length_prefixed,
+        };
+
+        let filter = |max_temporal_id| {
+            let filtered = filter_temporal_sublayers(data, format, max_temporal_id).unwrap();
+            collect_nal_units(&filtered, format)
+                .unwrap()
+                .into_iter()
+                .map(<[u8]>::to_vec)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            filter(0),
+            vec![vps.to_vec(), sps.to_vec(), pps.to_vec(), sei_t2.to_vec(), slice_t0.to_vec()]
+        );
+        assert_eq!(
+            filter(1),
+            vec![
+                vps.to_vec(),
+                sps.to_vec(),
+                pps.to_vec(),
+                sei_t2.to_vec(),
+                slice_t0.to_vec(),
+                slice_t1.to_vec()
+            ]
+        );
+
+        // Nothing is dropped when every sub-layer is kept, the data is unchanged.
+        assert_eq!(&filter_temporal_sublayers(data, format, 2).unwrap(), data);
+    }
+
+    // Invalid nal unit headers are an error, here a `nuh_temporal_id_plus1` of 0. The payload
+    // byte keeps the trailing zero of the header from being stripped as padding.
+    let err = filter_temporal_sublayers(&[0x00, 0x00, 0x01, 0x02, 0x00, 0xaa], NalUnitFormat::AnnexB, 0).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
@@ -65,9 +65,24 @@ where
     /// Run the HTTP server
     ///
     /// This function will bind to the address specified in `bind`, listen for incoming connections and handle requests.
-    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(bind = %self.bind)))]
+    ///
+    /// See [`run_with_listener`](HyperBackend::run_with_listener) to use an already bound listener instead.
+    pub async fn run(self) -> Result<(), Error<F>> {
+        // We have to create an std listener first because the tokio listener isn't clonable
+        let listener = tokio::net::TcpListener::bind(self.bind).await?.into_std()?;
+        self.run_with_listener(listener).await
+    }
+
+    /// Run the HTTP server on an already bound listener.
+    ///
+    /// This is useful for socket activation, where the listener is inherited from the parent process,
+    /// or for binding to port `0` and reading the assigned address before starting the server.
+    /// The `bind` address is ignored.
+    ///
+    /// The listener is set to non-blocking mode.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(bind = ?listener.local_addr().ok())))]
     #[allow(unused_mut)] // allow the unused `mut self`
-    pub async fn run(mut self) -> Result<(), Error<F>> {
+    pub async fn run_with_listener(mut self, listener: std::net::TcpListener) -> Result<(), Error<F>> {
         #[cfg(feature = "tracing")]
         tracing::debug!("starting server");
 
@@ -78,8 +93,8 @@ where
             rustls_config.max_early_data_size = 0;
         }
 
-        // We have to create an std listener first because the tokio listener isn't clonable
-        let listener = tokio::net::TcpListener::bind(self.bind).await?.into_std()?;
+        // Tokio requires the listener to be in non-blocking mode
+        listener.set_nonblocking(true)?;
 
         #[cfg(feature = "tls-rustls")]
         let tls_acceptor = self
@@ -99,7 +114,13 @@ where
                 let tls_acceptor = tls_acceptor.clone();
 
                 let worker_fut = async move {
-                    loop {
+                    // All connection tasks spawned by this worker, so we can wait for them to finish before exiting
+                    let mut connections = tokio::task::JoinSet::new();
+
+                    let res = loop {
+                        // Clean up connections that have already finished
+                        while connections.try_join_next().is_some() {}
+
                         #[cfg(feature = "tracing")]
                         tracing::trace!("waiting for connections");
 
@@ -108,13 +129,23 @@ where
                             Some(Err(e)) if utils::is_fatal_tcp_error(&e) => {
                                 #[cfg(feature = "tracing")]
                                 tracing::error!(err = %e, "failed to accept tcp connection");
-                                return Err(Error::<F>::from(e));
+                                break Err(Error::<F>::from(e));
+                            }
+                            Some(Err(e)) => {
+                                #[cfg(feature = "tracing")]
+                                tracing::warn!(err = %e, "failed to accept tcp connection, retrying");
+                                // Back off instead of spinning while the process is out of file descriptors
+                                if utils::accept_error_backoff(&e).with_context(ctx.clone()).await.is_none() {
+                                    #[cfg(feature = "tracing")]
+                                    tracing::trace!("context done, stopping listener");
+                                    break Ok(());
+                                }
+                                continue;
                             }
-                            Some(Err(_)) => continue,
                             None => {
                                 #[cfg(feature = "tracing")]
                                 tracing::trace!("context done, stopping listener");
-                                break;
+                                break Ok(());
                             }
                         };
 
@@ -174,6 +205,14 @@ where
                             #[cfg(not(feature = "http2"))]
                             let http2 = false;
 
+                            // Only serve the protocol negotiated with ALPN instead of detecting it from the first bytes
+                            #[cfg(feature = "tls-rustls")]
+                            let (http1, http2) = match stream.alpn_protocol() {
+                                Some(b"h2") if http2 => (false, true),
+                                Some(b"http/1.1" | b"http/1.0") if http1 => (true, false),
+                                _ => (http1, http2),
+                            };
+
                             let _res = handler::handle_connection::<F, _, _>(ctx, http_service, stream, http1, http2).await;
 
                             #[cfg(feature = "tracing")]
@@ -188,13 +227,19 @@ where
                         #[cfg(feature = "tracing")]
                         let connection_fut = connection_fut.instrument(tracing::trace_span!("connection", addr = %addr));
 
-                        tokio::spawn(connection_fut);
-                    }
+                        connections.spawn(connection_fut);
+                    };
+
+                    #[cfg(feature = "tracing")]
+                    tracing::trace!(connections = connections.len(), "listener closed, waiting for connections");
+
+                    // Wait for all in-flight connections to finish
+                    while connections.join_next().await.is_some() {}
 
                     #[cfg(feature = "tracing")]
-                    tracing::trace!("listener closed");
+                    tracing::trace!("all connections closed");
 
-                    Ok(())
+                    res
                 };
 
                 #[cfg(feature = "tracing")]
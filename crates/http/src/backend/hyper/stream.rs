@@ -25,6 +25,18 @@ impl Stream {
             Stream::Tls(_) => Ok(self),
         }
     }
+
+    /// Returns the application protocol negotiated with ALPN during the TLS handshake.
+    ///
+    /// Returns `None` for TCP streams or if no protocol was negotiated.
+    #[cfg(feature = "tls-rustls")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tls-rustls")))]
+    pub fn alpn_protocol(&self) -> Option<&[u8]> {
+        match self {
+            Stream::Tcp(_) => None,
+            Stream::Tls(stream) => stream.get_ref().1.alpn_protocol(),
+        }
+    }
 }
 
 impl AsyncRead for Stream {
@@ -1,14 +1,76 @@
+/// The time to wait before accepting connections again after the process ran out of resources.
+pub const ACCEPT_BACKOFF: std::time::Duration = std::time::Duration::from_millis(100);
+
 /// Returns true if the error is a fatal TCP error.
 ///
 /// Not all errors are fatal, some can be ignored.
 pub fn is_fatal_tcp_error(err: &std::io::Error) -> bool {
+    matches!(err.raw_os_error(), Some(libc::EFAULT) | Some(libc::EINVAL))
+}
+
+/// Returns true if the error means the process ran out of resources, such as file descriptors.
+///
+/// Accepting again immediately fails with the same error until the resources are freed.
+pub fn is_resource_exhaustion_error(err: &std::io::Error) -> bool {
     matches!(
         err.raw_os_error(),
-        Some(libc::EFAULT)
-            | Some(libc::EINVAL)
-            | Some(libc::ENFILE)
-            | Some(libc::EMFILE)
-            | Some(libc::ENOBUFS)
-            | Some(libc::ENOMEM)
+        Some(libc::ENFILE) | Some(libc::EMFILE) | Some(libc::ENOBUFS) | Some(libc::ENOMEM)
     )
 }
+
+/// Waits before accepting connections again after a non-fatal accept error.
+///
+/// This waits for [`ACCEPT_BACKOFF`] if the process ran out of resources, so the accept loop does
+/// not spin while they are exhausted. Other errors, such as aborted connections, return immediately.
+pub async fn accept_error_backoff(err: &std::io::Error) {
+    if is_resource_exhaustion_error(err) {
+        tokio::time::sleep(ACCEPT_BACKOFF).await;
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(test, coverage_nightly), coverage(off))]
+mod tests {
+    use std::io::Error;
+    use std::time::Instant;
+
+    use super::{ACCEPT_BACKOFF, accept_error_backoff, is_fatal_tcp_error, is_resource_exhaustion_error};
+
+    #[tokio::test]
+    async fn backoff_on_resource_exhaustion() {
+        for code in [libc::EMFILE, libc::ENFILE] {
+            let err = Error::from_raw_os_error(code);
+            assert!(!is_fatal_tcp_error(&err));
+            assert!(is_resource_exhaustion_error(&err));
+
+            // Every repeated error waits for the backoff.
+            let start = Instant::now();
+            for _ in 0..3 {
+                accept_error_backoff(&err).await;
+            }
+            assert!(start.elapsed() >= ACCEPT_BACKOFF * 3);
+        }
+    }
+
+    #[tokio::test]
+    async fn no_backoff_on_transient_errors() {
+        let err = Error::from_raw_os_error(libc::ECONNABORTED);
+        assert!(!is_fatal_tcp_error(&err));
+        assert!(!is_resource_exhaustion_error(&err));
+
+        let start = Instant::now();
+        for _ in 0..100 {
+            accept_error_backoff(&err).await;
+        }
+        assert!(start.elapsed() < ACCEPT_BACKOFF);
+    }
+
+    #[test]
+    fn fatal_errors() {
+        for code in [libc::EFAULT, libc::EINVAL] {
+            let err = Error::from_raw_os_error(code);
+            assert!(is_fatal_tcp_error(&err));
+            assert!(!is_resource_exhaustion_error(&err));
+        }
+    }
+}
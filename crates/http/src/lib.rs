@@ -80,8 +80,7 @@ pub mod error;
 mod server;
 pub mod service;
 
-pub use http;
-pub use http::Response;
+pub use http::{self, Response};
 pub use server::{HttpServer, HttpServerBuilder};
 
 /// An incoming request.
@@ -348,6 +347,95 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    #[cfg(all(feature = "tls-rustls", feature = "http1", feature = "http2"))]
+    async fn rustls_alpn() {
+        let addr = get_available_addr().expect("failed to get available address");
+        let (ctx, handler) = scuffle_context::Context::new();
+
+        let server = HttpServer::builder()
+            .service_factory(service_clone_factory(fn_http_service(|_| async {
+                Ok::<_, Infallible>(http::Response::new(RESPONSE_TEXT.to_string()))
+            })))
+            .rustls_config(rustls_config())
+            .enable_http1(true)
+            .enable_http2(true)
+            .bind(addr)
+            .ctx(ctx)
+            .build();
+
+        let handle = tokio::spawn(async move {
+            server.run().await.expect("server run failed");
+        });
+
+        // Wait for the server to start
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        let url = format!("https://{}/", addr);
+
+        // Without prior knowledge the client picks the version based on the protocols advertised with ALPN
+        let client = reqwest::Client::builder()
+            .danger_accept_invalid_certs(true)
+            .https_only(true)
+            .build()
+            .expect("failed to build client");
+        let resp = client.get(&url).send().await.expect("failed to get response");
+        assert_eq!(resp.version(), reqwest::Version::HTTP_2, "expected h2 to be advertised");
+        assert_eq!(resp.text().await.expect("failed to get text"), RESPONSE_TEXT);
+
+        let client = reqwest::Client::builder()
+            .danger_accept_invalid_certs(true)
+            .https_only(true)
+            .http1_only()
+            .build()
+            .expect("failed to build client");
+        let resp = client.get(&url).send().await.expect("failed to get response");
+        assert_eq!(resp.version(), reqwest::Version::HTTP_11);
+        assert_eq!(resp.text().await.expect("failed to get text"), RESPONSE_TEXT);
+
+        handler.shutdown().await;
+        handle.await.expect("task failed");
+    }
+
+    #[tokio::test]
+    #[cfg(all(feature = "tls-rustls", feature = "http1"))]
+    async fn rustls_run_with_listener() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("failed to bind listener");
+        let addr = listener.local_addr().expect("failed to get local address");
+        let (ctx, handler) = scuffle_context::Context::new();
+
+        let backend = crate::backend::hyper::HyperBackend::builder()
+            .service_factory(service_clone_factory(fn_http_service(|_| async {
+                Ok::<_, Infallible>(http::Response::new(RESPONSE_TEXT.to_string()))
+            })))
+            .rustls_config(rustls_config())
+            .bind(addr)
+            .ctx(ctx)
+            .build();
+
+        let handle = tokio::spawn(async move {
+            backend.run_with_listener(listener).await.expect("server run failed");
+        });
+
+        // The listener is already bound, so the request does not need to wait for the server to start
+        let client = reqwest::Client::builder()
+            .danger_accept_invalid_certs(true)
+            .https_only(true)
+            .http1_only()
+            .build()
+            .expect("failed to build client");
+        let resp = client
+            .get(format!("https://{}/", addr))
+            .send()
+            .await
+            .expect("failed to get response");
+        assert_eq!(resp.version(), reqwest::Version::HTTP_11);
+        assert_eq!(resp.text().await.expect("failed to get text"), RESPONSE_TEXT);
+
+        handler.shutdown().await;
+        handle.await.expect("task failed");
+    }
+
     #[tokio::test]
     async fn no_backend() {
         let addr = get_available_addr().expect("failed to get available address");
@@ -500,6 +588,88 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    #[cfg(feature = "http1")]
+    async fn shutdown_joins_connections() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        struct DropGuard(Arc<AtomicBool>);
+
+        impl Drop for DropGuard {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let addr = get_available_addr().expect("failed to get available address");
+        let (ctx, handler) = scuffle_context::Context::new();
+
+        let started = Arc::new(tokio::sync::Notify::new());
+        let finished = Arc::new(AtomicBool::new(false));
+
+        let builder = HttpServer::builder()
+            .service_factory(service_clone_factory(fn_http_service({
+                let started = started.clone();
+                let finished = finished.clone();
+                move |_| {
+                    let started = started.clone();
+                    let guard = DropGuard(finished.clone());
+                    async move {
+                        let _guard = guard;
+                        started.notify_one();
+                        // Never respond, the connection is only closed by the shutdown
+                        std::future::pending::<Result<http::Response<String>, Infallible>>().await
+                    }
+                }
+            })))
+            .bind(addr)
+            .ctx(ctx);
+
+        #[cfg(feature = "http2")]
+        let builder = builder.enable_http2(false);
+
+        let server = builder.build();
+
+        let handle = tokio::spawn({
+            let finished = finished.clone();
+            async move {
+                server.run().await.expect("server run failed");
+                // The connection task must be joined by the time run returns
+                finished.load(Ordering::SeqCst)
+            }
+        });
+
+        // Wait for the server to start
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        let client = tokio::spawn(async move {
+            let client = reqwest::Client::builder()
+                .http1_only()
+                .build()
+                .expect("failed to build client");
+            let _ = client.get(format!("http://{}/", addr)).send().await;
+        });
+
+        started
+            .notified()
+            .with_timeout(Duration::from_secs(1))
+            .await
+            .expect("request was not received");
+        assert!(!finished.load(Ordering::SeqCst), "connection finished before shutdown");
+
+        handler.shutdown().await;
+
+        let joined = handle
+            .with_timeout(Duration::from_secs(1))
+            .await
+            .expect("server did not shut down")
+            .expect("task failed");
+        assert!(joined, "connection task was not joined before run returned");
+
+        client.await.expect("client task failed");
+    }
+
     #[tokio::test]
     #[cfg(all(feature = "http1", feature = "http2"))]
     async fn tracked_body() {
@@ -156,20 +156,22 @@ where
             return;
         };
 
+        // The server picks the first protocol in this list that the client also supports,
+        // so the protocols are ordered by preference.
         // https://www.iana.org/assignments/tls-extensiontype-values/tls-extensiontype-values.xhtml#alpn-protocol-ids
         if rustls_config.alpn_protocols.is_empty() {
-            #[cfg(feature = "http1")]
-            if self.enable_http1 {
-                rustls_config.alpn_protocols.push(b"http/1.0".to_vec());
-                rustls_config.alpn_protocols.push(b"http/1.1".to_vec());
-            }
-
             #[cfg(feature = "http2")]
             if self.enable_http2 {
                 rustls_config.alpn_protocols.push(b"h2".to_vec());
                 rustls_config.alpn_protocols.push(b"h2c".to_vec());
             }
 
+            #[cfg(feature = "http1")]
+            if self.enable_http1 {
+                rustls_config.alpn_protocols.push(b"http/1.1".to_vec());
+                rustls_config.alpn_protocols.push(b"http/1.0".to_vec());
+            }
+
             #[cfg(feature = "http3")]
             if self.enable_http3 {
                 rustls_config.alpn_protocols.push(b"h3".to_vec());
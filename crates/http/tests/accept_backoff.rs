@@ -0,0 +1,86 @@
+//! This runs in its own process, since it exhausts the file descriptors of the whole process.
+#![cfg(all(unix, feature = "http1"))]
+
+use std::convert::Infallible;
+use std::time::Duration;
+
+use scuffle_future_ext::FutureExt;
+use scuffle_http::backend::hyper::HyperBackend;
+use scuffle_http::service::{fn_http_service, service_clone_factory};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+const RESPONSE_TEXT: &str = "Hello, world!";
+
+/// Opens files until the process runs out of file descriptors.
+fn exhaust_file_descriptors() -> Vec<std::fs::File> {
+    let mut files = Vec::new();
+    loop {
+        match std::fs::File::open("/dev/null") {
+            Ok(file) => files.push(file),
+            Err(err) if err.raw_os_error() == Some(libc::EMFILE) => return files,
+            Err(err) => panic!("failed to open file: {err}"),
+        }
+    }
+}
+
+#[tokio::test]
+async fn accept_recovers_from_fd_exhaustion() {
+    // Lower the limit so it is quick to exhaust
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    // Safety: `limit` is a valid pointer.
+    assert_eq!(unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) }, 0);
+    limit.rlim_cur = limit.rlim_cur.min(256);
+    // Safety: `limit` is a valid pointer.
+    assert_eq!(unsafe { libc::setrlimit(libc::RLIMIT_NOFILE, &limit) }, 0);
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("failed to bind listener");
+    let addr = listener.local_addr().expect("failed to get local address");
+    let (ctx, handler) = scuffle_context::Context::new();
+
+    let backend = HyperBackend::builder()
+        .service_factory(service_clone_factory(fn_http_service(|_| async {
+            Ok::<_, Infallible>(scuffle_http::Response::new(RESPONSE_TEXT.to_string()))
+        })))
+        .bind(addr)
+        .ctx(ctx)
+        .build();
+
+    let handle = tokio::spawn(async move {
+        backend.run_with_listener(listener).await.expect("server run failed");
+    });
+
+    // Wait for the server to start
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Free a single descriptor for the client, so the server has none left to accept the connection with
+    let mut files = exhaust_file_descriptors();
+    files.pop();
+    let client = std::net::TcpStream::connect(addr).expect("failed to connect");
+
+    // The server fails to accept the connection until the descriptors are freed
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    drop(files);
+
+    client.set_nonblocking(true).expect("failed to set non-blocking");
+    let mut client = tokio::net::TcpStream::from_std(client).expect("failed to register client");
+    client
+        .write_all(b"GET / HTTP/1.1\r\nhost: localhost\r\nconnection: close\r\n\r\n")
+        .await
+        .expect("failed to write request");
+
+    let mut response = String::new();
+    client
+        .read_to_string(&mut response)
+        .with_timeout(Duration::from_secs(5))
+        .await
+        .expect("timed out waiting for the response")
+        .expect("failed to read response");
+    assert!(response.starts_with("HTTP/1.1 200 OK"), "unexpected response: {response}");
+    assert!(response.ends_with(RESPONSE_TEXT), "unexpected response: {response}");
+
+    handler.shutdown().await;
+    handle.await.expect("task failed");
+}
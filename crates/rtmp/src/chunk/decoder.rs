@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use std::io::{Cursor, Seek, SeekFrom};
 
 use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
-use bytes::BytesMut;
+use bytes::{Bytes, BytesMut};
 use num_traits::FromPrimitive;
 
 use super::define::{Chunk, ChunkBasicHeader, ChunkMessageHeader, ChunkType, INIT_CHUNK_SIZE, MAX_CHUNK_SIZE};
@@ -17,6 +17,10 @@ const MAX_PARTIAL_CHUNK_SIZE: usize = 10 * 1024 * 1024; // 10MB (should be more
 const MAX_PREVIOUS_CHUNK_HEADERS: usize = 100; // 100 chunks
 const MAX_PARTIAL_CHUNK_COUNT: usize = 4; // 4 chunks
 
+/// Decodes an RTMP chunk stream into complete messages.
+///
+/// Feed the bytes received from the peer to [`ChunkDecoder::read_chunk`] or
+/// [`ChunkDecoder::read_all_chunks`].
 pub struct ChunkDecoder {
     /// According to the spec chunk streams are identified by the chunk stream
     /// ID. In this case that is our key.
@@ -30,9 +34,20 @@ pub struct ChunkDecoder {
     /// stream id, the second is the message stream id
     partial_chunks: HashMap<(u32, u32), BytesMut>,
 
+    /// The raw headers of the chunks that make up each partial chunk.
+    /// Only used when `raw_headers` is enabled.
+    partial_raw_headers: HashMap<(u32, u32), Vec<Bytes>>,
+
     /// This is the max chunk size that the client has specified.
     /// By default this is 128 bytes.
     max_chunk_size: usize,
+
+    /// Whether to keep the raw header bytes of every chunk.
+    raw_headers: bool,
+
+    /// The number of bytes read from the buffers since the decoder was
+    /// created. Errors report the offset of the chunk that caused them.
+    bytes_read: u64,
 }
 
 impl Default for ChunkDecoder {
@@ -40,12 +55,28 @@ impl Default for ChunkDecoder {
         Self {
             previous_chunk_headers: HashMap::new(),
             partial_chunks: HashMap::new(),
+            partial_raw_headers: HashMap::new(),
             max_chunk_size: INIT_CHUNK_SIZE,
+            raw_headers: false,
+            bytes_read: 0,
         }
     }
 }
 
 impl ChunkDecoder {
+    /// Set whether the raw header bytes of every chunk should be kept.
+    ///
+    /// When enabled, [`Chunk::raw_headers`] contains the exact header bytes
+    /// that were read for the message, so the chunk stream can be
+    /// reconstructed byte for byte. The headers share the memory of the
+    /// buffer and are not copied.
+    ///
+    /// Disabled by default.
+    pub fn with_raw_headers(mut self, raw_headers: bool) -> Self {
+        self.raw_headers = raw_headers;
+        self
+    }
+
     /// Sometimes a client will request a chunk size change.
     pub fn update_max_chunk_size(&mut self, chunk_size: usize) -> bool {
         // We need to make sure that the chunk size is within the allowed range.
@@ -60,8 +91,19 @@ impl ChunkDecoder {
 
     /// This function is used to read a chunk from the buffer.
     /// - will return Ok(None) if the buffer is empty.
-    /// - will return Ok(Some(Chunk)) if we have a full chunk.
+    /// - will return Ok(Some(Chunk)) if we have a full message.
     /// - Err(UnpackError) if we have an error. This will close the connection.
+    ///
+    /// A returned chunk is always a complete message, its payload is
+    /// `msg_length` bytes long and can be dispatched directly. The chunks of a
+    /// message split across multiple chunks are consumed from the buffer as
+    /// they arrive, but are never returned on their own. Messages on other
+    /// chunk streams may be returned while a message is still incomplete.
+    ///
+    /// If a message fits in a single chunk, the payload of the returned chunk
+    /// shares the memory of the buffer and is not copied. Messages split
+    /// across multiple chunks are reassembled into a single buffer, allocated
+    /// once with the length of the message.
     pub fn read_chunk(&mut self, buffer: &mut BytesMut) -> Result<Option<Chunk>, ChunkDecodeError> {
         // We do this in a loop because we may have multiple chunks in the buffer,
         // And those chunks may be partial chunks thus we need to keep reading until we
@@ -126,13 +168,18 @@ impl ChunkDecoder {
             }
 
             let data = buffer.split_to(position);
+            self.bytes_read += position as u64;
 
             // We freeze the chunk data and slice it to get the payload.
             // Data before the slice is the header data, and data after the slice is the
             // next chunk We don't need to keep the header data, because we already decoded
             // it into struct form. The payload_range_end should be the same as the cursor's
             // position.
-            let payload = data.freeze().slice(payload_range_start..payload_range_end);
+            let data = data.freeze();
+            let payload = data.slice(payload_range_start..payload_range_end);
+
+            // The header is everything before the payload.
+            let raw_header = self.raw_headers.then(|| data.slice(..payload_range_start));
 
             // We need to check here if the chunk header is already stored in our map.
             // This isnt a spec check but it is a check to make sure that we dont have too
@@ -167,6 +214,7 @@ impl ChunkDecoder {
                     basic_header: header,
                     message_header,
                     payload,
+                    raw_headers: raw_header.into_iter().collect(),
                 }));
             } else {
                 // Otherwise we generate a key using the chunk stream id and the message stream
@@ -183,8 +231,11 @@ impl ChunkDecoder {
                             return Err(ChunkDecodeError::TooManyPartialChunks);
                         }
 
-                        // Insert a new empty BytesMut into the map.
-                        self.partial_chunks.insert(key, BytesMut::new());
+                        // Insert a new BytesMut into the map, sized for the whole message so it
+                        // is only allocated once. The message length was already checked against
+                        // MAX_PARTIAL_CHUNK_SIZE when the header was read.
+                        self.partial_chunks
+                            .insert(key, BytesMut::with_capacity(message_header.msg_length as usize));
                         // Get the partial chunk we just inserted.
                         self.partial_chunks.get_mut(&key).expect("we just inserted it")
                     }
@@ -206,12 +257,17 @@ impl ChunkDecoder {
                     partial_chunk.len()
                 };
 
+                if let Some(raw_header) = raw_header {
+                    self.partial_raw_headers.entry(key).or_default().push(raw_header);
+                }
+
                 // If we have a full chunk we return it.
                 if length == message_header.msg_length as usize {
                     return Ok(Some(Chunk {
                         basic_header: header,
                         message_header,
                         payload: self.partial_chunks.remove(&key).unwrap().freeze(),
+                        raw_headers: self.partial_raw_headers.remove(&key).unwrap_or_default(),
                     }));
                 }
 
@@ -227,6 +283,22 @@ impl ChunkDecoder {
         }
     }
 
+    /// This function is used to read every complete chunk from the buffer.
+    /// It calls [`ChunkDecoder::read_chunk`] until it returns Ok(None).
+    /// - will return an empty Vec if there are no complete chunks in the buffer.
+    /// - Err(UnpackError) if we have an error. This will close the connection.
+    ///   Any chunks decoded before the error are removed from the buffer and
+    ///   discarded.
+    pub fn read_all_chunks(&mut self, buffer: &mut BytesMut) -> Result<Vec<Chunk>, ChunkDecodeError> {
+        let mut chunks = Vec::new();
+
+        while let Some(chunk) = self.read_chunk(buffer)? {
+            chunks.push(chunk);
+        }
+
+        Ok(chunks)
+    }
+
     /// Internal function used to read the basic chunk header.
     fn read_header(&self, cursor: &mut Cursor<&[u8]>) -> Result<ChunkBasicHeader, Option<ChunkDecodeError>> {
         // The first byte of the basic header is the format of the chunk and the stream
@@ -249,7 +321,9 @@ impl ChunkDecoder {
             // If the chunk stream id is 0 we read the next byte and add 64 to it.
             0 => 64 + cursor.read_u8().map_err(|_| None)? as u32,
             // If it is 1 we read the next 2 bytes and add 64 to it and multiply the 2nd byte by
-            // 256.
+            // 256. The largest id is 64 + 255 + 255 * 256 = 65599, so this cannot overflow.
+            // The state of each chunk stream is kept in maps keyed by the id, so high ids
+            // don't use any more memory than low ones.
             1 => 64 + cursor.read_u8().map_err(|_| None)? as u32 + cursor.read_u8().map_err(|_| None)? as u32 * 256,
             // Any other value means that the chunk stream id is the value of the byte.
             csid => csid,
@@ -286,8 +360,11 @@ impl ChunkDecoder {
 
                 // We validate the message type id. If it is invalid we return an error. (this
                 // is a real error)
-                let msg_type_id =
-                    MessageTypeID::from_u8(msg_type_id).ok_or(ChunkDecodeError::InvalidMessageTypeID(msg_type_id))?;
+                let msg_type_id = MessageTypeID::from_u8(msg_type_id).ok_or(ChunkDecodeError::InvalidMessageTypeID {
+                    message_type_id: msg_type_id,
+                    chunk_stream_id: header.chunk_stream_id,
+                    offset: self.bytes_read,
+                })?;
 
                 // We then read the message stream id. (According to spec this is stored in
                 // LittleEndian, no idea why.)
@@ -333,8 +410,11 @@ impl ChunkDecoder {
 
                 // We validate the message type id. If it is invalid we return an error. (this
                 // is a real error)
-                let msg_type_id =
-                    MessageTypeID::from_u8(msg_type_id).ok_or(ChunkDecodeError::InvalidMessageTypeID(msg_type_id))?;
+                let msg_type_id = MessageTypeID::from_u8(msg_type_id).ok_or(ChunkDecodeError::InvalidMessageTypeID {
+                    message_type_id: msg_type_id,
+                    chunk_stream_id: header.chunk_stream_id,
+                    offset: self.bytes_read,
+                })?;
 
                 // Again as mentioned above we sometimes have a delta timestamp larger than 3
                 // bytes.
@@ -347,10 +427,12 @@ impl ChunkDecoder {
                 // We get the previous chunk header.
                 // If the previous chunk header is not found we return an error. (this is a real
                 // error)
-                let previous_header = self
-                    .previous_chunk_headers
-                    .get(&header.chunk_stream_id)
-                    .ok_or(ChunkDecodeError::MissingPreviousChunkHeader(header.chunk_stream_id))?;
+                let previous_header = self.previous_chunk_headers.get(&header.chunk_stream_id).ok_or(
+                    ChunkDecodeError::MissingPreviousChunkHeader {
+                        chunk_stream_id: header.chunk_stream_id,
+                        offset: self.bytes_read,
+                    },
+                )?;
 
                 // We calculate the timestamp by adding the delta timestamp to the previous
                 // timestamp. We need to make sure this does not overflow.
@@ -391,10 +473,12 @@ impl ChunkDecoder {
                 // We get the previous chunk header.
                 // If the previous chunk header is not found we return an error. (this is a real
                 // error)
-                let previous_header = self
-                    .previous_chunk_headers
-                    .get(&header.chunk_stream_id)
-                    .ok_or(ChunkDecodeError::MissingPreviousChunkHeader(header.chunk_stream_id))?;
+                let previous_header = self.previous_chunk_headers.get(&header.chunk_stream_id).ok_or(
+                    ChunkDecodeError::MissingPreviousChunkHeader {
+                        chunk_stream_id: header.chunk_stream_id,
+                        offset: self.bytes_read,
+                    },
+                )?;
 
                 // We calculate the timestamp by adding the delta timestamp to the previous
                 // timestamp.
@@ -416,7 +500,10 @@ impl ChunkDecoder {
                 let previous_header = self
                     .previous_chunk_headers
                     .get(&header.chunk_stream_id)
-                    .ok_or(ChunkDecodeError::MissingPreviousChunkHeader(header.chunk_stream_id))?
+                    .ok_or(ChunkDecodeError::MissingPreviousChunkHeader {
+                        chunk_stream_id: header.chunk_stream_id,
+                        offset: self.bytes_read,
+                    })?
                     .clone();
 
                 // Now this is truely stupid.
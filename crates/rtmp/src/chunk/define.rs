@@ -8,13 +8,43 @@ use crate::messages::MessageTypeID;
 /// These channel ids are user defined and are not part of the protocol.
 /// We just have to send different data on different channels.
 /// This is just a easy way to make sure we do not mix up the data.
+///
+/// Except for protocol control messages, which must be sent on chunk stream
+/// id 2, these are the ids conventionally used by other RTMP implementations.
 pub enum DefinedChunkStreamID {
+    /// ChannelId for sending protocol control and user control messages
+    ProtocolControl = 2,
     /// ChannelId for sending commands
     Command = 3,
     /// ChannelId for sending audio
     Audio = 4,
     /// ChannelId for sending video
     Video = 5,
+    /// ChannelId for sending data messages, such as metadata
+    Data = 6,
+}
+
+impl DefinedChunkStreamID {
+    /// Returns the chunk stream id conventionally used to send messages of
+    /// the given type.
+    pub fn for_message_type(msg_type_id: MessageTypeID) -> Self {
+        match msg_type_id {
+            MessageTypeID::SetChunkSize
+            | MessageTypeID::Abort
+            | MessageTypeID::Acknowledgement
+            | MessageTypeID::UserControlEvent
+            | MessageTypeID::WindowAcknowledgementSize
+            | MessageTypeID::SetPeerBandwidth => Self::ProtocolControl,
+            MessageTypeID::CommandAMF0
+            | MessageTypeID::CommandAMF3
+            | MessageTypeID::SharedObjAMF0
+            | MessageTypeID::SharedObjAMF3 => Self::Command,
+            MessageTypeID::Audio => Self::Audio,
+            MessageTypeID::Video => Self::Video,
+            // Aggregate messages carry audio, video and data messages together
+            MessageTypeID::DataAMF0 | MessageTypeID::DataAMF3 | MessageTypeID::Aggregate => Self::Data,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, FromPrimitive, Hash)]
@@ -31,6 +61,7 @@ pub enum ChunkType {
     Type3 = 3,
 }
 
+/// The basic header of a chunk, identifying its chunk stream.
 #[derive(Eq, PartialEq, Debug, Clone)]
 pub struct ChunkBasicHeader {
     /// Used for decoding the header only.
@@ -39,6 +70,7 @@ pub struct ChunkBasicHeader {
     pub chunk_stream_id: u32, // 6 bits (if format == 0, 8 bits, if format == 1, 16 bits)
 }
 
+/// The message header of a chunk, describing the message it belongs to.
 #[derive(Eq, PartialEq, Debug, Clone)]
 pub struct ChunkMessageHeader {
     pub timestamp: u32,             /* 3 bytes (when writing the header, if the timestamp is >= 0xFFFFFF,
@@ -60,11 +92,26 @@ impl ChunkMessageHeader {
     }
 }
 
+/// A chunk stream message.
+///
+/// When returned by [`ChunkDecoder::read_chunk`](super::ChunkDecoder::read_chunk),
+/// this is always a complete message, reassembled from all of its chunks.
 #[derive(Eq, PartialEq, Debug, Clone)]
 pub struct Chunk {
     pub basic_header: ChunkBasicHeader,
     pub message_header: ChunkMessageHeader,
+    /// The payload of the message.
+    ///
+    /// When decoded from a single chunk, this is a zero-copy slice of the
+    /// buffer passed to [`ChunkDecoder::read_chunk`](super::ChunkDecoder::read_chunk).
     pub payload: Bytes,
+    /// The raw header bytes of every chunk the message was read from, in the
+    /// order they were received.
+    ///
+    /// Only filled in when decoded by a [`ChunkDecoder`](super::ChunkDecoder)
+    /// with [`with_raw_headers`](super::ChunkDecoder::with_raw_headers)
+    /// enabled, otherwise this is empty.
+    pub raw_headers: Vec<Bytes>,
 }
 
 impl Chunk {
@@ -90,6 +137,7 @@ impl Chunk {
                 was_extended_timestamp: false,
             },
             payload,
+            raw_headers: Vec::new(),
         }
     }
 }
@@ -1,10 +1,13 @@
 use std::io;
 
 use byteorder::{BigEndian, LittleEndian, WriteBytesExt};
+use bytes::Bytes;
 
-use super::define::{Chunk, ChunkMessageHeader, ChunkType, INIT_CHUNK_SIZE};
+use super::define::{Chunk, ChunkMessageHeader, ChunkType, DefinedChunkStreamID, INIT_CHUNK_SIZE};
 use super::errors::ChunkEncodeError;
+use crate::messages::MessageTypeID;
 
+/// Encodes messages into an RTMP chunk stream.
 pub struct ChunkEncoder {
     chunk_size: usize,
 }
@@ -73,28 +76,52 @@ impl ChunkEncoder {
         Ok(())
     }
 
-    pub fn write_chunk(&self, writer: &mut impl io::Write, mut chunk_info: Chunk) -> Result<(), ChunkEncodeError> {
-        Self::write_basic_header(writer, ChunkType::Type0, chunk_info.basic_header.chunk_stream_id)?;
+    pub fn write_chunk(&self, writer: &mut impl io::Write, chunk_info: Chunk) -> Result<(), ChunkEncodeError> {
+        self.write_chunk_ref(writer, &chunk_info)
+    }
 
-        Self::write_message_header(writer, &chunk_info.message_header)?;
+    /// Writes a message on the chunk stream id conventionally used for its
+    /// type, see [`DefinedChunkStreamID::for_message_type`].
+    ///
+    /// Use [`ChunkEncoder::write_chunk`] to choose the chunk stream id.
+    pub fn write_message(
+        &self,
+        writer: &mut impl io::Write,
+        timestamp: u32,
+        msg_type_id: MessageTypeID,
+        msg_stream_id: u32,
+        payload: Bytes,
+    ) -> Result<(), ChunkEncodeError> {
+        let chunk_stream_id = DefinedChunkStreamID::for_message_type(msg_type_id) as u32;
+        self.write_chunk(
+            writer,
+            Chunk::new(chunk_stream_id, timestamp, msg_type_id, msg_stream_id, payload),
+        )
+    }
 
-        while !chunk_info.payload.is_empty() {
-            let cur_payload_size = if chunk_info.payload.len() > self.chunk_size {
-                self.chunk_size
-            } else {
-                chunk_info.payload.len()
-            };
+    /// Writes a chunk without taking ownership of it.
+    ///
+    /// This can be used to re-encode a chunk returned by the
+    /// [`ChunkDecoder`](super::ChunkDecoder), for example when relaying it to
+    /// another peer. The payload is split according to this encoder's chunk
+    /// size, regardless of how it was split when it was received. To relay it on
+    /// another chunk stream, change `basic_header.chunk_stream_id` before
+    /// writing it.
+    pub fn write_chunk_ref(&self, writer: &mut impl io::Write, chunk_info: &Chunk) -> Result<(), ChunkEncodeError> {
+        Self::write_basic_header(writer, ChunkType::Type0, chunk_info.basic_header.chunk_stream_id)?;
 
-            let payload_bytes = chunk_info.payload.split_to(cur_payload_size);
-            writer.write_all(&payload_bytes[..])?;
+        Self::write_message_header(writer, &chunk_info.message_header)?;
 
-            if !chunk_info.payload.is_empty() {
+        for (i, payload_bytes) in chunk_info.payload.chunks(self.chunk_size).enumerate() {
+            if i > 0 {
                 Self::write_basic_header(writer, ChunkType::Type3, chunk_info.basic_header.chunk_stream_id)?;
 
                 if chunk_info.message_header.is_extended_timestamp() {
                     Self::write_extened_timestamp(writer, chunk_info.message_header.timestamp)?;
                 }
             }
+
+            writer.write_all(payload_bytes)?;
         }
 
         Ok(())
@@ -6,8 +6,15 @@ use crate::macros::from_error;
 pub enum ChunkDecodeError {
     IO(io::Error),
     InvalidChunkType(u8),
-    InvalidMessageTypeID(u8),
-    MissingPreviousChunkHeader(u32),
+    InvalidMessageTypeID {
+        message_type_id: u8,
+        chunk_stream_id: u32,
+        offset: u64,
+    },
+    MissingPreviousChunkHeader {
+        chunk_stream_id: u32,
+        offset: u64,
+    },
     TooManyPartialChunks,
     TooManyPreviousChunkHeaders,
     PartialChunkTooLarge(usize),
@@ -40,11 +47,23 @@ impl fmt::Display for ChunkDecodeError {
             Self::TooManyPartialChunks => write!(f, "too many partial chunks"),
             Self::TooManyPreviousChunkHeaders => write!(f, "too many previous chunk headers"),
             Self::PartialChunkTooLarge(size) => write!(f, "partial chunk too large: {}", size),
-            Self::MissingPreviousChunkHeader(chunk_stream_id) => {
-                write!(f, "missing previous chunk header: {}", chunk_stream_id)
+            Self::MissingPreviousChunkHeader { chunk_stream_id, offset } => {
+                write!(
+                    f,
+                    "missing previous chunk header: chunk stream id: {}, offset: {}",
+                    chunk_stream_id, offset
+                )
             }
-            Self::InvalidMessageTypeID(message_type_id) => {
-                write!(f, "invalid message type id: {}", message_type_id)
+            Self::InvalidMessageTypeID {
+                message_type_id,
+                chunk_stream_id,
+                offset,
+            } => {
+                write!(
+                    f,
+                    "invalid message type id: {}, chunk stream id: {}, offset: {}",
+                    message_type_id, chunk_stream_id, offset
+                )
             }
             Self::InvalidChunkType(chunk_type) => {
                 write!(f, "invalid chunk type: {}", chunk_type)
@@ -4,7 +4,7 @@ mod encoder;
 mod errors;
 
 pub use self::decoder::ChunkDecoder;
-pub use self::define::{CHUNK_SIZE, Chunk, DefinedChunkStreamID};
+pub use self::define::{CHUNK_SIZE, Chunk, ChunkBasicHeader, ChunkMessageHeader, DefinedChunkStreamID};
 pub use self::encoder::ChunkEncoder;
 pub use self::errors::{ChunkDecodeError, ChunkEncodeError};
 
@@ -11,11 +11,24 @@ fn test_decoder_error_display() {
     let error = ChunkDecodeError::InvalidChunkType(123);
     assert_eq!(format!("{}", error), "invalid chunk type: 123");
 
-    let error = ChunkDecodeError::InvalidMessageTypeID(123);
-    assert_eq!(format!("{}", error), "invalid message type id: 123");
-
-    let error = ChunkDecodeError::MissingPreviousChunkHeader(123);
-    assert_eq!(format!("{}", error), "missing previous chunk header: 123");
+    let error = ChunkDecodeError::InvalidMessageTypeID {
+        message_type_id: 123,
+        chunk_stream_id: 3,
+        offset: 16,
+    };
+    assert_eq!(
+        format!("{}", error),
+        "invalid message type id: 123, chunk stream id: 3, offset: 16"
+    );
+
+    let error = ChunkDecodeError::MissingPreviousChunkHeader {
+        chunk_stream_id: 123,
+        offset: 16,
+    };
+    assert_eq!(
+        format!("{}", error),
+        "missing previous chunk header: chunk stream id: 123, offset: 16"
+    );
 
     let error = ChunkDecodeError::TooManyPartialChunks;
     assert_eq!(format!("{}", error), "too many partial chunks");
@@ -57,6 +70,182 @@ fn test_decoder_chunk_type0_single_sized() {
     assert_eq!(chunk.payload.len(), 128);
 }
 
+#[test]
+fn test_decoder_single_chunk_zero_copy() {
+    let mut buf = BytesMut::new();
+
+    #[rustfmt::skip]
+    buf.extend_from_slice(&[
+        3, // chunk type 0, chunk stream id 3
+        0x00, 0x00, 0x00, // timestamp
+        0x00, 0x00, 0x80, // message length (128) (max chunk size is set to 128)
+        0x09, // message type id (video)
+        0x00, 0x01, 0x00, 0x00, // message stream id
+    ]);
+    let header_len = buf.len();
+
+    for i in 0..128 {
+        (&mut buf).writer().write_u8(i as u8).unwrap();
+    }
+
+    let payload_ptr = buf[header_len..].as_ptr();
+
+    let mut unpacker = ChunkDecoder::default();
+    let chunk = unpacker.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert_eq!(chunk.payload.len(), 128);
+    assert_eq!(
+        chunk.payload.as_ptr(),
+        payload_ptr,
+        "expected the payload to share the memory of the buffer"
+    );
+}
+
+#[test]
+fn test_decoder_multi_chunk_single_allocation() {
+    let mut buf = BytesMut::new();
+
+    #[rustfmt::skip]
+    buf.extend_from_slice(&[
+        3, // chunk type 0, chunk stream id 3
+        0x00, 0x00, 0x00, // timestamp
+        0x00, 0x01, 0x80, // message length (384) (max chunk size is set to 128)
+        0x09, // message type id (video)
+        0x00, 0x01, 0x00, 0x00, // message stream id
+    ]);
+
+    for i in 0..384 {
+        if i != 0 && i % 128 == 0 {
+            buf.put_u8(0xC3); // chunk type 3, chunk stream id 3
+        }
+
+        (&mut buf).writer().write_u8(i as u8).unwrap();
+    }
+
+    let mut unpacker = ChunkDecoder::default();
+    let chunk = unpacker.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert!(buf.is_empty());
+    assert_eq!(chunk.payload.len(), 384);
+    assert!(chunk.payload.iter().enumerate().all(|(i, b)| *b == i as u8));
+
+    // Growing the buffer chunk by chunk would have left it with more capacity
+    // than the message needs.
+    let payload = chunk.payload.try_into_mut().expect("payload should be uniquely owned");
+    assert_eq!(payload.capacity(), 384, "expected the payload to be allocated once");
+}
+
+#[test]
+fn test_decoder_returns_complete_messages() {
+    let mut unpacker = ChunkDecoder::default();
+    let mut buf = BytesMut::new();
+
+    #[rustfmt::skip]
+    buf.extend_from_slice(&[
+        3, // chunk type 0, chunk stream id 3
+        0x00, 0x00, 0x00, // timestamp
+        0x00, 0x01, 0x80, // message length (384) (max chunk size is set to 128)
+        0x09, // message type id (video)
+        0x00, 0x01, 0x00, 0x00, // message stream id
+    ]);
+    buf.extend_from_slice(&[0; 128]);
+
+    // The first chunk is consumed, but the message is not complete yet.
+    assert!(unpacker.read_chunk(&mut buf).expect("read chunk").is_none());
+    assert!(buf.is_empty());
+
+    // A message on another chunk stream is returned while the first one is incomplete.
+    #[rustfmt::skip]
+    buf.extend_from_slice(&[
+        4, // chunk type 0, chunk stream id 4
+        0x00, 0x00, 0x00, // timestamp
+        0x00, 0x00, 0x04, // message length (4)
+        0x08, // message type id (audio)
+        0x00, 0x01, 0x00, 0x00, // message stream id
+        1, 2, 3, 4,
+    ]);
+    buf.put_u8(0xC3); // chunk type 3, chunk stream id 3
+    buf.extend_from_slice(&[1; 128]);
+
+    let chunk = unpacker.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert_eq!(chunk.basic_header.chunk_stream_id, 4);
+    assert_eq!(chunk.payload.as_ref(), &[1, 2, 3, 4]);
+
+    // The second chunk of the first message is consumed without being returned.
+    assert!(unpacker.read_chunk(&mut buf).expect("read chunk").is_none());
+    assert!(buf.is_empty());
+
+    buf.put_u8(0xC3); // chunk type 3, chunk stream id 3
+    buf.extend_from_slice(&[2; 128]);
+
+    let chunk = unpacker.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert_eq!(chunk.basic_header.chunk_stream_id, 3);
+    assert_eq!(chunk.message_header.msg_length, 384);
+    assert_eq!(chunk.payload.len(), 384);
+    assert!(chunk.payload[..128].iter().all(|b| *b == 0));
+    assert!(chunk.payload[128..256].iter().all(|b| *b == 1));
+    assert!(chunk.payload[256..].iter().all(|b| *b == 2));
+
+    assert!(unpacker.read_chunk(&mut buf).expect("read chunk").is_none());
+}
+
+#[test]
+fn test_decoder_raw_headers() {
+    #[rustfmt::skip]
+    let type0_header = [
+        3, // chunk type 0, chunk stream id 3
+        0x00, 0x00, 0x00, // timestamp
+        0x00, 0x01, 0x00, // message length (256) (max chunk size is set to 128)
+        0x09, // message type id (video)
+        0x00, 0x01, 0x00, 0x00, // message stream id
+    ];
+    let type3_header = [0xC3]; // chunk type 3, chunk stream id 3
+
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(&type0_header);
+    buf.extend_from_slice(&[0xAA; 128]);
+    buf.extend_from_slice(&type3_header);
+    buf.extend_from_slice(&[0xBB; 128]);
+    let input = buf.clone();
+
+    // Disabled by default
+    let mut unpacker = ChunkDecoder::default();
+    let chunk = unpacker.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert!(chunk.raw_headers.is_empty());
+
+    let mut buf = input.clone();
+    let mut unpacker = ChunkDecoder::default().with_raw_headers(true);
+    let chunk = unpacker.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert_eq!(chunk.payload.len(), 256);
+    assert_eq!(chunk.raw_headers.len(), 2);
+    assert_eq!(chunk.raw_headers[0].as_ref(), &type0_header);
+    assert_eq!(chunk.raw_headers[1].as_ref(), &type3_header);
+
+    // The headers and payloads put back together are the original input
+    let mut reconstructed = Vec::new();
+    reconstructed.extend_from_slice(&chunk.raw_headers[0]);
+    reconstructed.extend_from_slice(&chunk.payload[..128]);
+    reconstructed.extend_from_slice(&chunk.raw_headers[1]);
+    reconstructed.extend_from_slice(&chunk.payload[128..]);
+    assert_eq!(reconstructed, input.as_ref());
+
+    // A single chunk message has a single header
+    #[rustfmt::skip]
+    let single_header = [
+        3, // chunk type 0, chunk stream id 3
+        0x00, 0x00, 0x00, // timestamp
+        0x00, 0x00, 0x04, // message length (4)
+        0x09, // message type id (video)
+        0x00, 0x01, 0x00, 0x00, // message stream id
+    ];
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(&single_header);
+    buf.extend_from_slice(&[1, 2, 3, 4]);
+
+    let chunk = unpacker.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert_eq!(chunk.raw_headers.len(), 1);
+    assert_eq!(chunk.raw_headers[0].as_ref(), &single_header);
+    assert_eq!(chunk.payload.as_ref(), &[1, 2, 3, 4]);
+}
+
 #[test]
 fn test_decoder_chunk_type0_double_sized() {
     let mut buf = BytesMut::new();
@@ -335,6 +524,84 @@ fn test_read_extended_csid_ext2() {
     assert_eq!(chunk.basic_header.chunk_stream_id, 64 + 10 + 256 * 13);
 }
 
+#[test]
+fn test_read_extended_csid_max() {
+    let mut buf = BytesMut::new();
+
+    #[rustfmt::skip]
+    buf.extend_from_slice(&[
+        1,    // chunk type 0, chunk stream id 1
+        0xFF, // extended chunk stream id
+        0xFF, // extended chunk stream id 2
+        0x00, 0x00, 0x00, // timestamp
+        0x00, 0x00, 0x04, // message length (4)
+        0x09, // message type id (video)
+        0x00, 0x01, 0x00, 0x00, // message stream id
+        1, 2, 3, 4, // payload
+        (3 << 6) | 1, // chunk type 3, chunk stream id 1
+        0xFF, // extended chunk stream id
+        0xFF, // extended chunk stream id 2
+        5, 6, 7, 8, // payload
+    ]);
+
+    let mut unpacker = ChunkDecoder::default();
+
+    let chunk = unpacker.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert_eq!(chunk.basic_header.chunk_stream_id, 65599);
+    assert_eq!(chunk.payload.as_ref(), &[1, 2, 3, 4]);
+
+    // The previous header is found for the maximum id
+    let chunk = unpacker.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert_eq!(chunk.basic_header.chunk_stream_id, 65599);
+    assert_eq!(chunk.message_header.msg_length, 4);
+    assert_eq!(chunk.payload.as_ref(), &[5, 6, 7, 8]);
+}
+
+#[test]
+fn test_read_extended_csid_sparse() {
+    let mut buf = BytesMut::new();
+
+    let mut unpacker = ChunkDecoder::default();
+
+    // Ids spread across the whole 2 byte range only count towards the limit once each
+    for i in 0..100u32 {
+        let csid = 65599 - i * 655;
+        let extended = csid - 64;
+
+        #[rustfmt::skip]
+        buf.extend_from_slice(&[
+            1, // chunk type 0, chunk stream id 1
+            (extended & 0xFF) as u8, // extended chunk stream id
+            (extended >> 8) as u8, // extended chunk stream id 2
+            0x00, 0x00, 0x00, // timestamp
+            0x00, 0x00, 0x00, // message length (0)
+            0x09, // message type id (video)
+            0x00, 0x01, 0x00, 0x00, // message stream id
+        ]);
+
+        let chunk = unpacker
+            .read_chunk(&mut buf)
+            .unwrap_or_else(|_| panic!("chunk failed {}", csid))
+            .expect("chunk");
+        assert_eq!(chunk.basic_header.chunk_stream_id, csid);
+    }
+
+    // The limit is only hit by the 101st chunk stream
+    #[rustfmt::skip]
+    buf.extend_from_slice(&[
+        3, // chunk type 0, chunk stream id 3
+        0x00, 0x00, 0x00, // timestamp
+        0x00, 0x00, 0x00, // message length (0)
+        0x09, // message type id (video)
+        0x00, 0x01, 0x00, 0x00, // message stream id
+    ]);
+
+    assert!(matches!(
+        unpacker.read_chunk(&mut buf),
+        Err(ChunkDecodeError::TooManyPreviousChunkHeaders)
+    ));
+}
+
 #[test]
 fn test_decoder_error_no_previous_chunk() {
     let mut buf = BytesMut::new();
@@ -348,11 +615,50 @@ fn test_decoder_error_no_previous_chunk() {
     let mut unpacker = ChunkDecoder::default();
     let err = unpacker.read_chunk(&mut buf).unwrap_err();
     match err {
-        ChunkDecodeError::MissingPreviousChunkHeader(3) => {}
+        ChunkDecodeError::MissingPreviousChunkHeader {
+            chunk_stream_id: 3,
+            offset: 0,
+        } => {}
         _ => panic!("Unexpected error: {:?}", err),
     }
 }
 
+#[test]
+fn test_decoder_error_no_previous_chunk_context() {
+    let mut buf = BytesMut::new();
+
+    #[rustfmt::skip]
+    buf.extend_from_slice(&[
+        3, // chunk type 0, chunk stream id 3
+        0x00, 0x00, 0x00, // timestamp
+        0x00, 0x00, 0x04, // message length (4)
+        0x09, // message type id (video)
+        0x00, 0x01, 0x00, 0x00, // message stream id
+        0x00, 0x01, 0x02, 0x03, // payload
+        (1 << 6) | 5, // chunk type 1, chunk stream id 5
+        0x00, 0x00, 0x00, // timestamp delta
+        0x00, 0x00, 0x04, // message length (4)
+        0x09, // message type id (video)
+    ]);
+
+    let mut unpacker = ChunkDecoder::default();
+    unpacker.read_chunk(&mut buf).unwrap().expect("chunk");
+
+    let err = unpacker.read_chunk(&mut buf).unwrap_err();
+    match err {
+        ChunkDecodeError::MissingPreviousChunkHeader {
+            chunk_stream_id: 5,
+            offset: 16,
+        } => {}
+        _ => panic!("Unexpected error: {:?}", err),
+    }
+
+    assert_eq!(
+        err.to_string(),
+        "missing previous chunk header: chunk stream id: 5, offset: 16"
+    );
+}
+
 #[test]
 fn test_decoder_error_partial_chunk_too_large() {
     let mut buf = BytesMut::new();
@@ -397,7 +703,11 @@ fn test_decoder_error_invalid_message_type_id() {
     let err = unpacker.read_chunk(&mut buf).unwrap_err();
 
     match err {
-        ChunkDecodeError::InvalidMessageTypeID(0xFF) => {}
+        ChunkDecodeError::InvalidMessageTypeID {
+            message_type_id: 0xFF,
+            chunk_stream_id: 3,
+            offset: 0,
+        } => {}
         _ => panic!("Unexpected error: {:?}", err),
     }
 }
@@ -534,3 +844,76 @@ fn test_decoder_larger_chunk_size() {
         assert_eq!(chunk.payload[i], i as u8);
     }
 }
+
+#[test]
+fn test_decoder_read_all_chunks() {
+    let mut buf = BytesMut::new();
+
+    for i in 0..3u8 {
+        #[rustfmt::skip]
+        buf.extend_from_slice(&[
+            3 + i, // chunk type 0, chunk stream id 3 + i
+            0x00, 0x00, i, // timestamp
+            0x00, 0x00, 0x04, // message length (4)
+            0x09, // message type id (video)
+            0x00, 0x01, 0x00, 0x00, // message stream id
+        ]);
+        buf.extend_from_slice(&[i; 4]);
+    }
+
+    // A partial chunk at the end of the buffer
+    #[rustfmt::skip]
+    buf.extend_from_slice(&[
+        6, // chunk type 0, chunk stream id 6
+        0x00, 0x00, 0x00, // timestamp
+    ]);
+
+    let mut unpacker = ChunkDecoder::default();
+    let chunks = unpacker.read_all_chunks(&mut buf).expect("read chunks");
+
+    assert_eq!(chunks.len(), 3);
+    for (i, chunk) in chunks.iter().enumerate() {
+        assert_eq!(chunk.basic_header.chunk_stream_id, 3 + i as u32);
+        assert_eq!(chunk.message_header.msg_type_id as u8, 0x09);
+        assert_eq!(chunk.message_header.timestamp, i as u32);
+        assert_eq!(chunk.payload.as_ref(), &[i as u8; 4]);
+    }
+
+    // The partial chunk is left in the buffer
+    assert_eq!(buf.len(), 4);
+    assert!(unpacker.read_all_chunks(&mut buf).expect("read chunks").is_empty());
+}
+
+#[test]
+fn test_decoder_read_all_chunks_error() {
+    let mut buf = BytesMut::new();
+
+    #[rustfmt::skip]
+    buf.extend_from_slice(&[
+        3, // chunk type 0, chunk stream id 3
+        0x00, 0x00, 0x00, // timestamp
+        0x00, 0x00, 0x04, // message length (4)
+        0x09, // message type id (video)
+        0x00, 0x01, 0x00, 0x00, // message stream id
+        0x00, 0x01, 0x02, 0x03, // payload
+        3, // chunk type 0, chunk stream id 3
+        0x00, 0x00, 0x00, // timestamp
+        0x00, 0x00, 0x04, // message length (4)
+        0xFF, // message type id (invalid)
+        0x00, 0x01, 0x00, 0x00, // message stream id
+    ]);
+
+    let mut unpacker = ChunkDecoder::default();
+
+    let err = unpacker.read_all_chunks(&mut buf).unwrap_err();
+
+    match err {
+        // The first chunk was read, so the offset is that of the second chunk
+        ChunkDecodeError::InvalidMessageTypeID {
+            message_type_id: 0xFF,
+            chunk_stream_id: 3,
+            offset: 16,
+        } => {}
+        _ => panic!("Unexpected error: {:?}", err),
+    }
+}
@@ -1,8 +1,8 @@
 use std::io;
 
-use bytes::Bytes;
+use bytes::{BufMut, Bytes, BytesMut};
 
-use crate::chunk::{Chunk, ChunkEncodeError, ChunkEncoder};
+use crate::chunk::{Chunk, ChunkDecoder, ChunkEncodeError, ChunkEncoder, DefinedChunkStreamID};
 use crate::messages::MessageTypeID;
 
 #[test]
@@ -203,3 +203,86 @@ fn test_encoder_extended_csid_ext() {
         ]
     );
 }
+
+#[test]
+fn test_encoder_relay_chunk() {
+    let payload = (0..300).map(|i| i as u8).collect::<Vec<_>>();
+    let chunk = Chunk::new(3, 1000, MessageTypeID::Video, 1, Bytes::from(payload.clone()));
+
+    let mut buf = BytesMut::new();
+    ChunkEncoder::default().write_chunk(&mut (&mut buf).writer(), chunk).unwrap();
+
+    let mut decoder = ChunkDecoder::default();
+    let mut decoded = decoder.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert!(buf.is_empty());
+
+    // Relay the decoded chunk on another chunk stream with a bigger chunk size
+    decoded.basic_header.chunk_stream_id = 10;
+
+    let mut encoder = ChunkEncoder::default();
+    encoder.set_chunk_size(4096);
+    encoder.write_chunk_ref(&mut (&mut buf).writer(), &decoded).unwrap();
+
+    // 1 byte basic header + 11 bytes message header + unfragmented payload
+    assert_eq!(buf.len(), 1 + 11 + payload.len());
+
+    let mut decoder = ChunkDecoder::default();
+    assert!(decoder.update_max_chunk_size(4096));
+    let relayed = decoder.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert!(buf.is_empty());
+
+    assert_eq!(relayed.basic_header.chunk_stream_id, 10);
+    assert_eq!(relayed.message_header.timestamp, 1000);
+    assert_eq!(relayed.message_header.msg_length, payload.len() as u32);
+    assert_eq!(relayed.message_header.msg_type_id, MessageTypeID::Video);
+    assert_eq!(relayed.message_header.msg_stream_id, 1);
+    assert_eq!(relayed.payload, decoded.payload);
+    assert_eq!(relayed.payload.as_ref(), payload.as_slice());
+}
+
+#[test]
+fn test_encoder_write_message_default_chunk_stream_id() {
+    let mut buf = BytesMut::new();
+    ChunkEncoder::default()
+        .write_message(
+            &mut (&mut buf).writer(),
+            1000,
+            MessageTypeID::Video,
+            1,
+            Bytes::from(vec![0x17, 0x01]),
+        )
+        .unwrap();
+
+    let mut decoder = ChunkDecoder::default();
+    let chunk = decoder.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert!(buf.is_empty());
+
+    assert_eq!(chunk.basic_header.chunk_stream_id, 5);
+    assert_eq!(chunk.message_header.timestamp, 1000);
+    assert_eq!(chunk.message_header.msg_type_id, MessageTypeID::Video);
+    assert_eq!(chunk.message_header.msg_stream_id, 1);
+    assert_eq!(chunk.payload.as_ref(), &[0x17, 0x01]);
+
+    let cases = [
+        (MessageTypeID::SetChunkSize, DefinedChunkStreamID::ProtocolControl),
+        (MessageTypeID::UserControlEvent, DefinedChunkStreamID::ProtocolControl),
+        (MessageTypeID::CommandAMF0, DefinedChunkStreamID::Command),
+        (MessageTypeID::Audio, DefinedChunkStreamID::Audio),
+        (MessageTypeID::Video, DefinedChunkStreamID::Video),
+        (MessageTypeID::DataAMF0, DefinedChunkStreamID::Data),
+    ];
+
+    for (msg_type_id, expected) in cases {
+        assert_eq!(
+            DefinedChunkStreamID::for_message_type(msg_type_id),
+            expected,
+            "{msg_type_id:?}"
+        );
+    }
+
+    assert_eq!(DefinedChunkStreamID::ProtocolControl as u32, 2);
+    assert_eq!(DefinedChunkStreamID::Command as u32, 3);
+    assert_eq!(DefinedChunkStreamID::Audio as u32, 4);
+    assert_eq!(DefinedChunkStreamID::Video as u32, 5);
+    assert_eq!(DefinedChunkStreamID::Data as u32, 6);
+}
@@ -1,2 +1,3 @@
 mod decoder;
 mod encoder;
+mod roundtrip;
@@ -0,0 +1,106 @@
+use bytes::{Bytes, BytesMut};
+use proptest::prelude::*;
+
+use crate::chunk::define::{INIT_CHUNK_SIZE, MAX_CHUNK_SIZE};
+use crate::chunk::{Chunk, ChunkDecoder, ChunkEncoder};
+use crate::messages::MessageTypeID;
+
+fn msg_type_id() -> impl Strategy<Value = MessageTypeID> {
+    prop::sample::select(vec![
+        MessageTypeID::Audio,
+        MessageTypeID::Video,
+        MessageTypeID::DataAMF0,
+        MessageTypeID::CommandAMF0,
+        MessageTypeID::SetChunkSize,
+        MessageTypeID::Abort,
+        MessageTypeID::Acknowledgement,
+        MessageTypeID::UserControlEvent,
+        MessageTypeID::WindowAcknowledgementSize,
+        MessageTypeID::SetPeerBandwidth,
+        MessageTypeID::DataAMF3,
+        MessageTypeID::SharedObjAMF3,
+        MessageTypeID::CommandAMF3,
+        MessageTypeID::SharedObjAMF0,
+        MessageTypeID::Aggregate,
+    ])
+}
+
+fn timestamp() -> impl Strategy<Value = u32> {
+    prop_oneof![
+        0..0xFFFFFFu32,
+        // Around 0xFFFFFF, where the extended timestamp starts being used
+        0xFFFFF0..=0x100000Fu32,
+        any::<u32>(),
+    ]
+}
+
+fn chunk_stream_id() -> impl Strategy<Value = u32> {
+    prop_oneof![
+        // 1 byte basic header
+        2..64u32,
+        // 2 byte basic header
+        64..319u32,
+        // 3 byte basic header
+        319..=65599u32,
+    ]
+}
+
+fn chunk_size() -> impl Strategy<Value = usize> {
+    prop_oneof![Just(INIT_CHUNK_SIZE), INIT_CHUNK_SIZE..=4096, Just(MAX_CHUNK_SIZE)]
+}
+
+fn chunk() -> impl Strategy<Value = Chunk> {
+    (
+        chunk_stream_id(),
+        timestamp(),
+        msg_type_id(),
+        any::<u32>(),
+        prop::collection::vec(any::<u8>(), 0..2048),
+    )
+        .prop_map(|(chunk_stream_id, timestamp, msg_type_id, msg_stream_id, payload)| {
+            Chunk::new(chunk_stream_id, timestamp, msg_type_id, msg_stream_id, Bytes::from(payload))
+        })
+}
+
+proptest! {
+    /// Messages written by the encoder are read back identically by the
+    /// decoder, no matter how the encoded data is split when it is received.
+    #[test]
+    fn test_encoder_decoder_roundtrip(
+        chunk_size in chunk_size(),
+        chunks in prop::collection::vec(chunk(), 1..8),
+        read_size in 1..=1024usize,
+    ) {
+        let mut encoder = ChunkEncoder::default();
+        encoder.set_chunk_size(chunk_size);
+
+        let mut decoder = ChunkDecoder::default();
+        prop_assert!(decoder.update_max_chunk_size(chunk_size));
+
+        let mut encoded = Vec::new();
+        for chunk in &chunks {
+            encoder.write_chunk_ref(&mut encoded, chunk).expect("write chunk");
+        }
+
+        let mut buf = BytesMut::new();
+        let mut decoded = Vec::new();
+        for data in encoded.chunks(read_size) {
+            buf.extend_from_slice(data);
+            while let Some(chunk) = decoder.read_chunk(&mut buf).expect("read chunk") {
+                decoded.push(chunk);
+            }
+        }
+
+        prop_assert!(buf.is_empty());
+        prop_assert_eq!(decoded.len(), chunks.len());
+
+        for (decoded, chunk) in decoded.iter().zip(&chunks) {
+            prop_assert_eq!(decoded.basic_header.chunk_stream_id, chunk.basic_header.chunk_stream_id);
+            prop_assert_eq!(decoded.message_header.timestamp, chunk.message_header.timestamp);
+            prop_assert_eq!(decoded.message_header.msg_length, chunk.message_header.msg_length);
+            prop_assert_eq!(decoded.message_header.msg_type_id, chunk.message_header.msg_type_id);
+            prop_assert_eq!(decoded.message_header.msg_stream_id, chunk.message_header.msg_stream_id);
+            prop_assert_eq!(&decoded.payload, &chunk.payload);
+        }
+    }
+}
@@ -10,7 +10,16 @@ mod session;
 mod user_control_messages;
 
 pub use channels::{ChannelData, DataConsumer, DataProducer, PublishConsumer, PublishProducer, PublishRequest, UniqueID};
-pub use session::{Session, SessionError};
+pub use chunk::{
+    Chunk, ChunkBasicHeader, ChunkDecodeError, ChunkDecoder, ChunkEncodeError, ChunkEncoder, ChunkMessageHeader,
+    DefinedChunkStreamID,
+};
+pub use messages::{EnhancedVideoHeader, EnhancedVideoPacketType, MessageTypeID, VideoFourCC};
+pub use netstream::{NetStreamError, NetStreamWriter, StatusLevel};
+pub use protocol_control_messages::{
+    ProtocolControlMessageError, ProtocolControlMessageReader, ProtocolControlMessagesWriter,
+};
+pub use session::{ServerConnection, ServerEvent, Session, SessionError};
 
 #[cfg(test)]
 mod tests;
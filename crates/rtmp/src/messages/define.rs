@@ -1,7 +1,13 @@
+use std::io::{self, Read};
+
+use byteorder::ReadBytesExt;
 use bytes::Bytes;
 use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
 use scuffle_amf0::Amf0Value;
 
+use super::errors::MessageError;
+
 #[derive(Debug)]
 pub enum RtmpMessageData<'a> {
     Amf0Command {
@@ -21,9 +27,13 @@ pub enum RtmpMessageData<'a> {
     },
     VideoData {
         data: Bytes,
+        /// The enhanced RTMP video tag header at the start of `data`, `None` if
+        /// the message uses the legacy video tag header.
+        enhanced_header: Option<EnhancedVideoHeader>,
     },
 }
 
+/// The type of an RTMP message, as sent in the chunk message header.
 #[derive(Debug, PartialEq, Eq, Clone, Copy, FromPrimitive)]
 #[repr(u8)]
 pub enum MessageTypeID {
@@ -43,3 +53,101 @@ pub enum MessageTypeID {
     CommandAMF0 = 20,
     Aggregate = 22,
 }
+
+/// The codec of an enhanced RTMP video message, identified by its FourCC.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum VideoFourCC {
+    /// HEVC (H.265), `hvc1`.
+    Hevc,
+    /// AV1, `av01`.
+    Av1,
+    /// VP9, `vp09`.
+    Vp9,
+    /// Any other FourCC.
+    Unknown([u8; 4]),
+}
+
+impl From<[u8; 4]> for VideoFourCC {
+    fn from(value: [u8; 4]) -> Self {
+        match &value {
+            b"hvc1" => Self::Hevc,
+            b"av01" => Self::Av1,
+            b"vp09" => Self::Vp9,
+            _ => Self::Unknown(value),
+        }
+    }
+}
+
+impl From<VideoFourCC> for [u8; 4] {
+    fn from(value: VideoFourCC) -> Self {
+        match value {
+            VideoFourCC::Hevc => *b"hvc1",
+            VideoFourCC::Av1 => *b"av01",
+            VideoFourCC::Vp9 => *b"vp09",
+            VideoFourCC::Unknown(fourcc) => fourcc,
+        }
+    }
+}
+
+/// The packet type of an enhanced RTMP video message.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, FromPrimitive)]
+#[repr(u8)]
+pub enum EnhancedVideoPacketType {
+    /// The payload is the decoder configuration record.
+    SequenceStart = 0,
+    /// The payload is the composition time offset followed by coded frames.
+    CodedFrames = 1,
+    /// The end of the sequence, there is no payload.
+    SequenceEnd = 2,
+    /// The payload is coded frames, the composition time offset is implied to be zero.
+    CodedFramesX = 3,
+    /// The payload is AMF encoded metadata.
+    Metadata = 4,
+    /// The payload is an MPEG-2 TS sequence start.
+    Mpeg2TsSequenceStart = 5,
+}
+
+/// The extended video tag header used by enhanced RTMP (E-RTMP) to signal
+/// codecs by FourCC.
+///
+/// <https://github.com/veovera/enhanced-rtmp/blob/main/docs/enhanced/enhanced-rtmp-v1.pdf>
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct EnhancedVideoHeader {
+    /// The frame type (3 bits), `1` is a keyframe and `2` is an interframe.
+    pub frame_type: u8,
+    /// The packet type.
+    pub packet_type: EnhancedVideoPacketType,
+    /// The codec of the video data.
+    pub fourcc: VideoFourCC,
+}
+
+impl EnhancedVideoHeader {
+    /// The size of the header in bytes, the packet payload starts after it.
+    pub const SIZE: usize = 5;
+
+    /// Parse the enhanced video tag header from the payload of a video message.
+    ///
+    /// Returns `None` if the message uses the legacy video tag header, which is
+    /// the case when the high bit of the first byte is not set.
+    pub fn parse(data: &[u8]) -> Result<Option<Self>, MessageError> {
+        let mut cursor = io::Cursor::new(data);
+        let byte = cursor.read_u8()?;
+
+        if byte & 0b1000_0000 == 0 {
+            return Ok(None);
+        }
+
+        let frame_type = (byte >> 4) & 0b0111;
+        let packet_type = EnhancedVideoPacketType::from_u8(byte & 0b0000_1111)
+            .ok_or(MessageError::UnknownEnhancedVideoPacketType(byte & 0b0000_1111))?;
+
+        let mut fourcc = [0; 4];
+        cursor.read_exact(&mut fourcc)?;
+
+        Ok(Some(Self {
+            frame_type,
+            packet_type,
+            fourcc: VideoFourCC::from(fourcc),
+        }))
+    }
+}
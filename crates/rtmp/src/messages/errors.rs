@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{fmt, io};
 
 use scuffle_amf0::Amf0ReadError;
 
@@ -9,10 +9,13 @@ use crate::protocol_control_messages::ProtocolControlMessageError;
 pub enum MessageError {
     Amf0Read(Amf0ReadError),
     ProtocolControlMessage(ProtocolControlMessageError),
+    UnknownEnhancedVideoPacketType(u8),
+    Io(io::Error),
 }
 
 from_error!(MessageError, Self::Amf0Read, Amf0ReadError);
 from_error!(MessageError, Self::ProtocolControlMessage, ProtocolControlMessageError);
+from_error!(MessageError, Self::Io, io::Error);
 
 impl fmt::Display for MessageError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -21,6 +24,10 @@ impl fmt::Display for MessageError {
             Self::ProtocolControlMessage(error) => {
                 write!(f, "protocol control message error: {}", error)
             }
+            Self::UnknownEnhancedVideoPacketType(packet_type) => {
+                write!(f, "unknown enhanced video packet type: {}", packet_type)
+            }
+            Self::Io(error) => write!(f, "io error: {}", error),
         }
     }
 }
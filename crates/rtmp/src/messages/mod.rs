@@ -2,7 +2,7 @@ mod define;
 mod errors;
 mod parser;
 
-pub use self::define::{MessageTypeID, RtmpMessageData};
+pub use self::define::{EnhancedVideoHeader, EnhancedVideoPacketType, MessageTypeID, RtmpMessageData, VideoFourCC};
 pub use self::errors::MessageError;
 pub use self::parser::MessageParser;
 
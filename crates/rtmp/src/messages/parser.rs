@@ -1,6 +1,6 @@
 use scuffle_amf0::{Amf0Decoder, Amf0Marker};
 
-use super::define::{MessageTypeID, RtmpMessageData};
+use super::define::{EnhancedVideoHeader, MessageTypeID, RtmpMessageData};
 use super::errors::MessageError;
 use crate::chunk::Chunk;
 use crate::protocol_control_messages::ProtocolControlMessageReader;
@@ -36,6 +36,9 @@ impl MessageParser {
             // Data Messages - VIDEO
             MessageTypeID::Video => Ok(Some(RtmpMessageData::VideoData {
                 data: chunk.payload.clone(),
+                // The data is forwarded unchanged, so a header that cannot be parsed is
+                // treated like a legacy one instead of failing the message.
+                enhanced_header: EnhancedVideoHeader::parse(&chunk.payload).ok().flatten(),
             })),
             // Protocol Control Messages
             MessageTypeID::SetChunkSize => {
@@ -3,7 +3,9 @@ use std::borrow::Cow;
 use bytes::Bytes;
 use scuffle_amf0::{Amf0Encoder, Amf0Marker, Amf0ReadError, Amf0Value};
 
-use super::{MessageError, MessageParser, MessageTypeID, RtmpMessageData};
+use super::{
+    EnhancedVideoHeader, EnhancedVideoPacketType, MessageError, MessageParser, MessageTypeID, RtmpMessageData, VideoFourCC,
+};
 use crate::chunk::{Chunk, ChunkEncodeError};
 use crate::protocol_control_messages::ProtocolControlMessageError;
 
@@ -18,6 +20,12 @@ fn test_error_display() {
         error.to_string(),
         "protocol control message error: chunk encode error: unknown read state"
     );
+
+    let error = MessageError::UnknownEnhancedVideoPacketType(15);
+    assert_eq!(error.to_string(), "unknown enhanced video packet type: 15");
+
+    let error = MessageError::Io(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+    assert_eq!(error.to_string(), "io error: unexpected end of file");
 }
 
 #[test]
@@ -68,13 +76,78 @@ fn test_parse_video_packet() {
 
     let message = MessageParser::parse(&chunk).expect("no errors").expect("message");
     match message {
-        RtmpMessageData::VideoData { data } => {
+        RtmpMessageData::VideoData { data, enhanced_header } => {
             assert_eq!(data, vec![0x00, 0x00, 0x00, 0x00]);
+            assert_eq!(enhanced_header, None);
         }
         _ => unreachable!("wrong message type"),
     }
 }
 
+#[test]
+fn test_parse_enhanced_hevc_sequence_start() {
+    let mut payload = vec![0b1001_0000]; // enhanced, keyframe, sequence start
+    payload.extend_from_slice(b"hvc1");
+    payload.extend_from_slice(&[0x01, 0x01, 0x60, 0x00, 0x00, 0x00]); // start of the decoder configuration record
+
+    let chunk = Chunk::new(0, 0, MessageTypeID::Video, 0, payload.into());
+
+    let message = MessageParser::parse(&chunk).expect("no errors").expect("message");
+    let RtmpMessageData::VideoData { data, enhanced_header } = message else {
+        unreachable!("wrong message type");
+    };
+
+    let header = enhanced_header.expect("enhanced header");
+    assert_eq!(
+        header,
+        EnhancedVideoHeader {
+            frame_type: 1,
+            packet_type: EnhancedVideoPacketType::SequenceStart,
+            fourcc: VideoFourCC::Hevc,
+        }
+    );
+    assert_eq!(&data[EnhancedVideoHeader::SIZE..], &[0x01, 0x01, 0x60, 0x00, 0x00, 0x00]);
+}
+
+#[test]
+fn test_parse_enhanced_video_header() {
+    let header = EnhancedVideoHeader::parse(b"\xa1av01")
+        .expect("no errors")
+        .expect("enhanced header");
+    assert_eq!(
+        header,
+        EnhancedVideoHeader {
+            frame_type: 2,
+            packet_type: EnhancedVideoPacketType::CodedFrames,
+            fourcc: VideoFourCC::Av1,
+        }
+    );
+
+    let header = EnhancedVideoHeader::parse(b"\x92vp09")
+        .expect("no errors")
+        .expect("enhanced header");
+    assert_eq!(header.packet_type, EnhancedVideoPacketType::SequenceEnd);
+    assert_eq!(header.fourcc, VideoFourCC::Vp9);
+
+    let header = EnhancedVideoHeader::parse(b"\x93avc1")
+        .expect("no errors")
+        .expect("enhanced header");
+    assert_eq!(header.packet_type, EnhancedVideoPacketType::CodedFramesX);
+    assert_eq!(header.fourcc, VideoFourCC::Unknown(*b"avc1"));
+    assert_eq!(<[u8; 4]>::from(header.fourcc), *b"avc1");
+    assert_eq!(<[u8; 4]>::from(VideoFourCC::Hevc), *b"hvc1");
+
+    // legacy avc keyframe
+    assert!(EnhancedVideoHeader::parse(&[0x17, 0x00]).expect("no errors").is_none());
+
+    assert!(matches!(
+        EnhancedVideoHeader::parse(b"\x9fhvc1"),
+        Err(MessageError::UnknownEnhancedVideoPacketType(15))
+    ));
+    assert!(matches!(EnhancedVideoHeader::parse(b"\x90hv"), Err(MessageError::Io(_))));
+    assert!(matches!(EnhancedVideoHeader::parse(&[]), Err(MessageError::Io(_))));
+}
+
 #[test]
 fn test_parse_set_chunk_size() {
     let chunk = Chunk::new(0, 0, MessageTypeID::SetChunkSize, 0, vec![0x00, 0xFF, 0xFF, 0xFF].into());
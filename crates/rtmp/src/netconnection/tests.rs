@@ -65,6 +65,38 @@ fn test_netconnection_connect_response() {
     ); // info object
 }
 
+#[test]
+fn test_netconnection_connect_response_fmsver() {
+    let encoder = ChunkEncoder::default();
+    let mut buf = BytesMut::new();
+
+    NetConnection::write_connect_response(
+        &encoder,
+        &mut (&mut buf).writer(),
+        1.0,
+        "FMS/3,5,7,7009",
+        239.0,
+        "NetConnection.Connect.Success",
+        "status",
+        "Connection Succeeded.",
+        0.0,
+    )
+    .unwrap();
+
+    let mut decoder = ChunkDecoder::default();
+    let chunk = decoder.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+
+    let mut amf0_reader = Amf0Decoder::new(&chunk.payload);
+    let values = amf0_reader.decode_all().unwrap();
+
+    assert_eq!(values.len(), 4);
+    let Amf0Value::Object(properties) = &values[2] else {
+        panic!("expected an object, got {:?}", values[2]);
+    };
+    assert!(properties.contains(&("fmsVer".into(), Amf0Value::String("FMS/3,5,7,7009".into()))));
+    assert!(properties.contains(&("capabilities".into(), Amf0Value::Number(239.0))));
+}
+
 #[test]
 fn test_netconnection_create_stream_response() {
     let encoder = ChunkEncoder::default();
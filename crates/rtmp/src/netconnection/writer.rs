@@ -4,17 +4,14 @@ use bytes::Bytes;
 use scuffle_amf0::{Amf0Encoder, Amf0Value};
 
 use super::errors::NetConnectionError;
-use crate::chunk::{Chunk, ChunkEncoder, DefinedChunkStreamID};
+use crate::chunk::ChunkEncoder;
 use crate::messages::MessageTypeID;
 
 pub struct NetConnection;
 
 impl NetConnection {
     fn write_chunk(encoder: &ChunkEncoder, amf0: Bytes, writer: &mut impl io::Write) -> Result<(), NetConnectionError> {
-        encoder.write_chunk(
-            writer,
-            Chunk::new(DefinedChunkStreamID::Command as u32, 0, MessageTypeID::CommandAMF0, 0, amf0),
-        )?;
+        encoder.write_message(writer, 0, MessageTypeID::CommandAMF0, 0, amf0)?;
 
         Ok(())
     }
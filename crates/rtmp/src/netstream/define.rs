@@ -0,0 +1,56 @@
+use scuffle_amf0::Amf0Value;
+
+/// The level of an `onStatus` message.
+/// Defined in the RTMP specification, section 7.2.2.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum StatusLevel {
+    Status,
+    Warning,
+    Error,
+}
+
+impl StatusLevel {
+    /// The value of the `level` property of the info object.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Status => "status",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// A command sent by a publisher when it stops publishing a stream.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum UnpublishCommand {
+    /// `deleteStream`, with the id of the stream to delete from the first argument.
+    DeleteStream { stream_id: u32 },
+    /// `closeStream`, with the id of the message stream it was sent on.
+    CloseStream { stream_id: u32 },
+    /// `FCUnpublish`, with the name of the stream from the first argument if present.
+    FcUnpublish { stream_name: Option<String> },
+}
+
+impl UnpublishCommand {
+    /// Creates a `deleteStream` command from the arguments of the amf0 command
+    /// message.
+    pub fn delete_stream(others: &[Amf0Value<'_>]) -> Self {
+        Self::DeleteStream {
+            stream_id: match others.first() {
+                Some(Amf0Value::Number(stream_id)) => *stream_id as u32,
+                _ => 0,
+            },
+        }
+    }
+
+    /// Creates an `FCUnpublish` command from the arguments of the amf0 command
+    /// message.
+    pub fn fc_unpublish(others: &[Amf0Value<'_>]) -> Self {
+        Self::FcUnpublish {
+            stream_name: match others.first() {
+                Some(Amf0Value::String(stream_name)) => Some(stream_name.to_string()),
+                _ => None,
+            },
+        }
+    }
+}
@@ -5,6 +5,7 @@ use scuffle_amf0::Amf0WriteError;
 use crate::chunk::ChunkEncodeError;
 use crate::macros::from_error;
 
+/// An error writing a NetStream message.
 #[derive(Debug)]
 pub enum NetStreamError {
     Amf0Write(Amf0WriteError),
@@ -1,6 +1,8 @@
+mod define;
 mod errors;
 mod writer;
 
+pub use self::define::{StatusLevel, UnpublishCommand};
 pub use self::errors::NetStreamError;
 pub use self::writer::NetStreamWriter;
 
@@ -1,8 +1,9 @@
-use bytes::{BufMut, BytesMut};
-use scuffle_amf0::{Amf0Decoder, Amf0Value, Amf0WriteError};
+use bytes::{BufMut, Bytes, BytesMut};
+use scuffle_amf0::{Amf0Decoder, Amf0Encoder, Amf0Value, Amf0WriteError};
 
-use crate::chunk::{ChunkDecoder, ChunkEncodeError, ChunkEncoder};
-use crate::netstream::{NetStreamError, NetStreamWriter};
+use crate::chunk::{Chunk, ChunkDecoder, ChunkEncodeError, ChunkEncoder};
+use crate::messages::{MessageParser, MessageTypeID, RtmpMessageData};
+use crate::netstream::{NetStreamError, NetStreamWriter, StatusLevel, UnpublishCommand};
 
 #[test]
 fn test_error_display() {
@@ -18,7 +19,15 @@ fn test_netstream_write_on_status() {
     let encoder = ChunkEncoder::default();
     let mut buf = BytesMut::new();
 
-    NetStreamWriter::write_on_status(&encoder, &mut (&mut buf).writer(), 1.0, "status", "idk", "description").unwrap();
+    NetStreamWriter::write_on_status(
+        &encoder,
+        &mut (&mut buf).writer(),
+        1.0,
+        StatusLevel::Status,
+        "idk",
+        "description",
+    )
+    .unwrap();
 
     let mut decoder = ChunkDecoder::default();
 
@@ -46,3 +55,117 @@ fn test_netstream_write_on_status() {
         )
     ); // info object
 }
+
+#[test]
+fn test_netstream_write_on_status_levels() {
+    let encoder = ChunkEncoder::default();
+
+    for (level, expected) in [
+        (StatusLevel::Status, "status"),
+        (StatusLevel::Warning, "warning"),
+        (StatusLevel::Error, "error"),
+    ] {
+        assert_eq!(level.as_str(), expected);
+
+        let mut buf = BytesMut::new();
+        NetStreamWriter::write_on_status(&encoder, &mut (&mut buf).writer(), 1.0, level, "idk", "description").unwrap();
+
+        let mut decoder = ChunkDecoder::default();
+        let chunk = decoder.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+
+        let mut amf0_reader = Amf0Decoder::new(&chunk.payload);
+        let values = amf0_reader.decode_all().unwrap();
+
+        assert_eq!(values[3].get_str("level"), Some(expected));
+    }
+}
+
+/// Decodes an amf0 command message with the given argument and creates an
+/// unpublish command from its arguments.
+fn unpublish_command(
+    command_name: &str,
+    argument: Option<Amf0Value>,
+    create: impl FnOnce(&[Amf0Value<'_>]) -> UnpublishCommand,
+) -> UnpublishCommand {
+    let mut amf0_writer = Vec::new();
+    Amf0Encoder::encode_string(&mut amf0_writer, command_name).unwrap();
+    Amf0Encoder::encode_number(&mut amf0_writer, 4.0).unwrap();
+    Amf0Encoder::encode_null(&mut amf0_writer).unwrap();
+    if let Some(argument) = &argument {
+        Amf0Encoder::encode(&mut amf0_writer, argument).unwrap();
+    }
+
+    let chunk = Chunk::new(3, 0, MessageTypeID::CommandAMF0, 0, Bytes::from(amf0_writer));
+    let message = MessageParser::parse(&chunk).expect("no errors").expect("message");
+    let RtmpMessageData::Amf0Command { others, .. } = message else {
+        unreachable!("wrong message type");
+    };
+
+    create(&others)
+}
+
+#[test]
+fn test_create_unpublish_commands() {
+    assert_eq!(
+        unpublish_command("deleteStream", Some(Amf0Value::Number(1.0)), UnpublishCommand::delete_stream),
+        UnpublishCommand::DeleteStream { stream_id: 1 }
+    );
+    assert_eq!(
+        unpublish_command("deleteStream", None, UnpublishCommand::delete_stream),
+        UnpublishCommand::DeleteStream { stream_id: 0 }
+    );
+    assert_eq!(
+        unpublish_command(
+            "FCUnpublish",
+            Some(Amf0Value::String("xyz".into())),
+            UnpublishCommand::fc_unpublish
+        ),
+        UnpublishCommand::FcUnpublish {
+            stream_name: Some("xyz".to_string())
+        }
+    );
+    assert_eq!(
+        unpublish_command("FCUnpublish", None, UnpublishCommand::fc_unpublish),
+        UnpublishCommand::FcUnpublish { stream_name: None }
+    );
+}
+
+#[test]
+fn test_netstream_write_on_fc_unpublish() {
+    let encoder = ChunkEncoder::default();
+    let mut buf = BytesMut::new();
+
+    NetStreamWriter::write_on_fc_unpublish(&encoder, &mut (&mut buf).writer(), 4.0).unwrap();
+
+    let mut decoder = ChunkDecoder::default();
+    let chunk = decoder.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert_eq!(chunk.message_header.msg_type_id, MessageTypeID::CommandAMF0);
+
+    let values = Amf0Decoder::new(&chunk.payload).decode_all().unwrap();
+    assert_eq!(values.len(), 4);
+    assert_eq!(values[0], Amf0Value::String("onFCUnpublish".into()));
+    assert_eq!(values[1], Amf0Value::Number(4.0));
+    assert_eq!(values[2], Amf0Value::Null);
+    assert_eq!(values[3].get_str("level"), Some("status"));
+    assert_eq!(values[3].get_str("code"), Some("NetStream.Unpublish.Success"));
+}
+
+#[test]
+fn test_netstream_write_unpublish_success() {
+    let encoder = ChunkEncoder::default();
+    let mut buf = BytesMut::new();
+
+    NetStreamWriter::write_unpublish_success(&encoder, &mut (&mut buf).writer(), 4.0).unwrap();
+
+    let mut decoder = ChunkDecoder::default();
+    let chunk = decoder.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert_eq!(chunk.message_header.msg_type_id, MessageTypeID::CommandAMF0);
+
+    let values = Amf0Decoder::new(&chunk.payload).decode_all().unwrap();
+    assert_eq!(values.len(), 4);
+    assert_eq!(values[0], Amf0Value::String("onStatus".into()));
+    assert_eq!(values[1], Amf0Value::Number(4.0));
+    assert_eq!(values[2], Amf0Value::Null);
+    assert_eq!(values[3].get_str("level"), Some("status"));
+    assert_eq!(values[3].get_str("code"), Some("NetStream.Unpublish.Success"));
+}
@@ -3,45 +3,41 @@ use std::io;
 use bytes::Bytes;
 use scuffle_amf0::{Amf0Encoder, Amf0Value};
 
+use super::define::StatusLevel;
 use super::errors::NetStreamError;
-use crate::chunk::{Chunk, ChunkEncoder, DefinedChunkStreamID};
+use crate::chunk::ChunkEncoder;
 use crate::messages::MessageTypeID;
 
+/// Writes NetStream command messages.
 pub struct NetStreamWriter {}
 
 impl NetStreamWriter {
     fn write_chunk(encoder: &ChunkEncoder, amf0_writer: Bytes, writer: &mut impl io::Write) -> Result<(), NetStreamError> {
-        encoder.write_chunk(
-            writer,
-            Chunk::new(
-                DefinedChunkStreamID::Command as u32,
-                0,
-                MessageTypeID::CommandAMF0,
-                0,
-                amf0_writer,
-            ),
-        )?;
+        encoder.write_message(writer, 0, MessageTypeID::CommandAMF0, 0, amf0_writer)?;
 
         Ok(())
     }
 
-    pub fn write_on_status(
+    /// Writes a status command, an `onStatus` message or a response with the
+    /// same arguments such as `onFCUnpublish`.
+    fn write_status(
         encoder: &ChunkEncoder,
         writer: &mut impl io::Write,
+        command_name: &str,
         transaction_id: f64,
-        level: &str,
+        level: StatusLevel,
         code: &str,
         description: &str,
     ) -> Result<(), NetStreamError> {
         let mut amf0_writer = Vec::new();
 
-        Amf0Encoder::encode_string(&mut amf0_writer, "onStatus")?;
+        Amf0Encoder::encode_string(&mut amf0_writer, command_name)?;
         Amf0Encoder::encode_number(&mut amf0_writer, transaction_id)?;
         Amf0Encoder::encode_null(&mut amf0_writer)?;
         Amf0Encoder::encode_object(
             &mut amf0_writer,
             &[
-                ("level".into(), Amf0Value::String(level.into())),
+                ("level".into(), Amf0Value::String(level.as_str().into())),
                 ("code".into(), Amf0Value::String(code.into())),
                 ("description".into(), Amf0Value::String(description.into())),
             ],
@@ -49,4 +45,51 @@ impl NetStreamWriter {
 
         Self::write_chunk(encoder, Bytes::from(amf0_writer), writer)
     }
+
+    /// Writes an `onStatus` message with the given level, code and
+    /// description.
+    pub fn write_on_status(
+        encoder: &ChunkEncoder,
+        writer: &mut impl io::Write,
+        transaction_id: f64,
+        level: StatusLevel,
+        code: &str,
+        description: &str,
+    ) -> Result<(), NetStreamError> {
+        Self::write_status(encoder, writer, "onStatus", transaction_id, level, code, description)
+    }
+
+    /// Writes the `NetStream.Unpublish.Success` status, the response to an
+    /// [`UnpublishCommand`](super::UnpublishCommand) that stopped a published stream.
+    pub fn write_unpublish_success(
+        encoder: &ChunkEncoder,
+        writer: &mut impl io::Write,
+        transaction_id: f64,
+    ) -> Result<(), NetStreamError> {
+        Self::write_on_status(
+            encoder,
+            writer,
+            transaction_id,
+            StatusLevel::Status,
+            "NetStream.Unpublish.Success",
+            "Stop publishing.",
+        )
+    }
+
+    /// Writes the `onFCUnpublish` response to an `FCUnpublish` command.
+    pub fn write_on_fc_unpublish(
+        encoder: &ChunkEncoder,
+        writer: &mut impl io::Write,
+        transaction_id: f64,
+    ) -> Result<(), NetStreamError> {
+        Self::write_status(
+            encoder,
+            writer,
+            "onFCUnpublish",
+            transaction_id,
+            StatusLevel::Status,
+            "NetStream.Unpublish.Success",
+            "Stop publishing.",
+        )
+    }
 }
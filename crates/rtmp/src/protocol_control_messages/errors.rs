@@ -3,6 +3,7 @@ use std::io;
 use crate::chunk::ChunkEncodeError;
 use crate::macros::from_error;
 
+/// An error reading or writing a protocol control message.
 #[derive(Debug)]
 pub enum ProtocolControlMessageError {
     IO(io::Error),
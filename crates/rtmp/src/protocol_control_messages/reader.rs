@@ -4,6 +4,7 @@ use byteorder::{BigEndian, ReadBytesExt};
 
 use super::errors::ProtocolControlMessageError;
 
+/// Reads the payload of protocol control messages.
 pub struct ProtocolControlMessageReader;
 
 impl ProtocolControlMessageReader {
@@ -12,4 +13,29 @@ impl ProtocolControlMessageReader {
         let chunk_size = cursor.read_u32::<BigEndian>()?;
         Ok(chunk_size)
     }
+
+    pub fn read_abort(data: &[u8]) -> Result<u32, ProtocolControlMessageError> {
+        let mut cursor = Cursor::new(data);
+        let chunk_stream_id = cursor.read_u32::<BigEndian>()?;
+        Ok(chunk_stream_id)
+    }
+
+    pub fn read_acknowledgement(data: &[u8]) -> Result<u32, ProtocolControlMessageError> {
+        let mut cursor = Cursor::new(data);
+        let sequence_number = cursor.read_u32::<BigEndian>()?;
+        Ok(sequence_number)
+    }
+
+    pub fn read_window_acknowledgement_size(data: &[u8]) -> Result<u32, ProtocolControlMessageError> {
+        let mut cursor = Cursor::new(data);
+        let window_size = cursor.read_u32::<BigEndian>()?;
+        Ok(window_size)
+    }
+
+    pub fn read_set_peer_bandwidth(data: &[u8]) -> Result<(u32, u8), ProtocolControlMessageError> {
+        let mut cursor = Cursor::new(data);
+        let window_size = cursor.read_u32::<BigEndian>()?;
+        let limit_type = cursor.read_u8()?;
+        Ok((window_size, limit_type))
+    }
 }
@@ -1,6 +1,7 @@
 use bytes::{BufMut, BytesMut};
 
 use crate::chunk::{ChunkDecoder, ChunkEncodeError, ChunkEncoder};
+use crate::messages::MessageTypeID;
 use crate::protocol_control_messages::{
     ProtocolControlMessageError, ProtocolControlMessageReader, ProtocolControlMessagesWriter,
 };
@@ -68,3 +69,89 @@ fn test_writer_set_peer_bandwidth() {
     assert_eq!(chunk.message_header.msg_stream_id, 0);
     assert_eq!(chunk.payload, vec![0x00, 0x00, 0x00, 0x01, 0x02]);
 }
+
+#[test]
+fn test_writer_acknowledgement() {
+    let encoder = ChunkEncoder::default();
+    let mut buf = BytesMut::new();
+
+    ProtocolControlMessagesWriter::write_acknowledgement(&encoder, &mut (&mut buf).writer(), 1).unwrap();
+
+    let mut decoder = ChunkDecoder::default();
+
+    let chunk = decoder.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert_eq!(chunk.basic_header.chunk_stream_id, 0x02);
+    assert_eq!(chunk.message_header.msg_type_id as u8, 0x03);
+    assert_eq!(chunk.message_header.msg_stream_id, 0);
+    assert_eq!(chunk.payload, vec![0x00, 0x00, 0x00, 0x01]);
+}
+
+#[test]
+fn test_writer_abort() {
+    let encoder = ChunkEncoder::default();
+    let mut buf = BytesMut::new();
+
+    ProtocolControlMessagesWriter::write_abort(&encoder, &mut (&mut buf).writer(), 3).unwrap();
+
+    let mut decoder = ChunkDecoder::default();
+
+    let chunk = decoder.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert_eq!(chunk.basic_header.chunk_stream_id, 0x02);
+    assert_eq!(chunk.message_header.msg_type_id as u8, 0x02);
+    assert_eq!(chunk.message_header.msg_stream_id, 0);
+    assert_eq!(chunk.payload, vec![0x00, 0x00, 0x00, 0x03]);
+}
+
+#[test]
+fn test_round_trip() {
+    let encoder = ChunkEncoder::default();
+    let mut buf = BytesMut::new();
+
+    ProtocolControlMessagesWriter::write_set_chunk_size(&encoder, &mut (&mut buf).writer(), 4096).unwrap();
+    ProtocolControlMessagesWriter::write_window_acknowledgement_size(&encoder, &mut (&mut buf).writer(), 2500000).unwrap();
+    ProtocolControlMessagesWriter::write_set_peer_bandwidth(&encoder, &mut (&mut buf).writer(), 2500000, 2).unwrap();
+    ProtocolControlMessagesWriter::write_acknowledgement(&encoder, &mut (&mut buf).writer(), 1234).unwrap();
+    ProtocolControlMessagesWriter::write_abort(&encoder, &mut (&mut buf).writer(), 5).unwrap();
+
+    let mut decoder = ChunkDecoder::default();
+
+    let chunk = decoder.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert_eq!(chunk.message_header.msg_type_id, MessageTypeID::SetChunkSize);
+    assert_eq!(
+        ProtocolControlMessageReader::read_set_chunk_size(&chunk.payload).unwrap(),
+        4096
+    );
+
+    let chunk = decoder.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert_eq!(chunk.message_header.msg_type_id, MessageTypeID::WindowAcknowledgementSize);
+    assert_eq!(
+        ProtocolControlMessageReader::read_window_acknowledgement_size(&chunk.payload).unwrap(),
+        2500000
+    );
+
+    let chunk = decoder.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert_eq!(chunk.message_header.msg_type_id, MessageTypeID::SetPeerBandwidth);
+    assert_eq!(
+        ProtocolControlMessageReader::read_set_peer_bandwidth(&chunk.payload).unwrap(),
+        (2500000, 2)
+    );
+
+    let chunk = decoder.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert_eq!(chunk.message_header.msg_type_id, MessageTypeID::Acknowledgement);
+    assert_eq!(
+        ProtocolControlMessageReader::read_acknowledgement(&chunk.payload).unwrap(),
+        1234
+    );
+
+    let chunk = decoder.read_chunk(&mut buf).expect("read chunk").expect("chunk");
+    assert_eq!(chunk.message_header.msg_type_id, MessageTypeID::Abort);
+    assert_eq!(ProtocolControlMessageReader::read_abort(&chunk.payload).unwrap(), 5);
+
+    assert!(decoder.read_chunk(&mut buf).expect("read chunk").is_none());
+}
+
+#[test]
+fn test_reader_not_enough_data() {
+    assert!(ProtocolControlMessageReader::read_set_peer_bandwidth(&[0x00, 0x00, 0x00, 0x01]).is_err());
+    assert!(ProtocolControlMessageReader::read_acknowledgement(&[0x00, 0x00]).is_err());
+}
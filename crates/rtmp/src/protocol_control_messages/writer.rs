@@ -7,6 +7,8 @@ use super::errors::ProtocolControlMessageError;
 use crate::chunk::{Chunk, ChunkEncoder};
 use crate::messages::MessageTypeID;
 
+/// Writes protocol control messages, which are always sent on chunk stream
+/// id 2.
 pub struct ProtocolControlMessagesWriter;
 
 impl ProtocolControlMessagesWriter {
@@ -74,4 +76,36 @@ impl ProtocolControlMessagesWriter {
 
         Ok(())
     }
+
+    pub fn write_acknowledgement(
+        encoder: &ChunkEncoder,
+        writer: &mut impl io::Write,
+        sequence_number: u32,
+    ) -> Result<(), ProtocolControlMessageError> {
+        encoder.write_message(
+            writer,
+            0, // timestamps are ignored
+            MessageTypeID::Acknowledgement,
+            0, // message stream id is ignored
+            Bytes::from(sequence_number.to_be_bytes().to_vec()),
+        )?;
+
+        Ok(())
+    }
+
+    pub fn write_abort(
+        encoder: &ChunkEncoder,
+        writer: &mut impl io::Write,
+        chunk_stream_id: u32,
+    ) -> Result<(), ProtocolControlMessageError> {
+        encoder.write_message(
+            writer,
+            0, // timestamps are ignored
+            MessageTypeID::Abort,
+            0, // message stream id is ignored
+            Bytes::from(chunk_stream_id.to_be_bytes().to_vec()),
+        )?;
+
+        Ok(())
+    }
 }
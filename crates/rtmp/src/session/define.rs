@@ -1,3 +1,26 @@
+use crate::channels::{ChannelData, UniqueID};
+
+/// Events reported by a [`ServerConnection`](super::ServerConnection).
+#[derive(Debug, Clone)]
+pub enum ServerEvent {
+    /// The client connected to an app.
+    Connected {
+        /// The app name from the url, for example `live` in `rtmp://localhost:1935/live/xyz`
+        app_name: String,
+    },
+    /// The client started publishing a stream.
+    Publishing {
+        /// The app name the client connected to
+        app_name: String,
+        /// The stream key the client is publishing to, for example `xyz` in `rtmp://localhost:1935/live/xyz`
+        stream_key: String,
+        /// The unique id the publish request was accepted with
+        uid: UniqueID,
+    },
+    /// The client sent audio, video or metadata for the published stream.
+    Media(ChannelData),
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 
 /// RTMP Commands are defined in the RTMP specification
@@ -16,6 +39,8 @@ pub(super) enum RtmpCommand {
     CloseStream,
     /// NetStream.releaseStream
     ReleaseStream,
+    /// FCUnpublish, sent by publishers before deleting the stream
+    FcUnpublish,
     /// Unknown command
     Unknown(String),
 }
@@ -30,6 +55,7 @@ impl From<&str> for RtmpCommand {
             "play" => Self::Play,
             "closeStream" => Self::CloseStream,
             "releaseStream" => Self::ReleaseStream,
+            "FCUnpublish" => Self::FcUnpublish,
             _ => Self::Unknown(command.to_string()),
         }
     }
@@ -83,3 +83,13 @@ impl fmt::Display for SessionError {
         }
     }
 }
+
+impl std::error::Error for SessionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Timeout(error) => Some(error),
+            _ => None,
+        }
+    }
+}
@@ -1,8 +1,11 @@
 mod define;
 mod errors;
+mod server_connection;
 mod server_session;
 
+pub use self::define::ServerEvent;
 pub use self::errors::SessionError;
+pub use self::server_connection::ServerConnection;
 pub use self::server_session::Session;
 
 #[cfg(test)]
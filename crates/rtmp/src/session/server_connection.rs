@@ -0,0 +1,129 @@
+use futures::FutureExt;
+use tokio::sync::{mpsc, oneshot};
+
+use super::define::ServerEvent;
+use super::errors::SessionError;
+use super::server_session::{DEFAULT_CAPABILITIES, DEFAULT_FMSVER, Session};
+use crate::channels::{PublishRequest, UniqueID};
+
+/// How many media messages can be buffered before the session waits for them
+/// to be handled.
+const MEDIA_CHANNEL_SIZE: usize = 128;
+
+/// A connection from a client publishing to the server, such as OBS or ffmpeg.
+///
+/// This drives a [`Session`] to completion, from the handshake through the
+/// connect, createStream and publish commands, and reports what happens as
+/// [`ServerEvent`]s. Every publish request is accepted with a new
+/// [`UniqueID`], use a [`Session`] directly to decide which requests to
+/// accept.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use scuffle_rtmp::{ServerConnection, ServerEvent};
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let listener = tokio::net::TcpListener::bind("127.0.0.1:1935").await?;
+/// let (stream, _) = listener.accept().await?;
+///
+/// ServerConnection::new(stream)
+///     .run(|event| match event {
+///         ServerEvent::Connected { app_name } => println!("connected to {app_name}"),
+///         ServerEvent::Publishing { stream_key, .. } => println!("publishing to {stream_key}"),
+///         ServerEvent::Media(data) => println!("media at {}", data.timestamp()),
+///     })
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ServerConnection<S> {
+    io: S,
+    fmsver: String,
+    capabilities: f64,
+}
+
+impl<S> ServerConnection<S> {
+    /// Create a new connection from a client stream
+    pub fn new(io: S) -> Self {
+        Self {
+            io,
+            fmsver: DEFAULT_FMSVER.to_string(),
+            capabilities: DEFAULT_CAPABILITIES,
+        }
+    }
+
+    /// Set the server version and capabilities announced to the client,
+    /// see [`Session::with_fmsver`].
+    pub fn with_fmsver(mut self, fmsver: impl Into<String>, capabilities: f64) -> Self {
+        self.fmsver = fmsver.into();
+        self.capabilities = capabilities;
+        self
+    }
+}
+
+impl<S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin> ServerConnection<S> {
+    /// Run the connection to completion, calling `on_event` for every event.
+    ///
+    /// The result is the same as [`Session::run`], true if the client stopped
+    /// publishing before disconnecting.
+    pub async fn run(self, mut on_event: impl FnMut(ServerEvent)) -> Result<bool, SessionError> {
+        let (data_producer, mut data_consumer) = mpsc::channel(MEDIA_CHANNEL_SIZE);
+        let (publish_producer, mut publish_consumer) = mpsc::channel(1);
+        let (connect_notifier, connected) = oneshot::channel();
+
+        let mut session = Session::new(self.io, data_producer, publish_producer)
+            .with_connect_notifier(connect_notifier)
+            .with_fmsver(self.fmsver, self.capabilities);
+
+        let result = {
+            let mut run = std::pin::pin!(session.run().fuse());
+            let mut connected = connected.fuse();
+
+            // The session waits for us to accept publish requests and take its media,
+            // so we handle those while it runs. Biased so events are reported in the
+            // order they happened.
+            loop {
+                futures::select_biased! {
+                    app_name = connected => {
+                        if let Ok(app_name) = app_name {
+                            on_event(ServerEvent::Connected { app_name });
+                        }
+                    }
+                    request = publish_consumer.recv().fuse() => {
+                        if let Some(request) = request {
+                            on_publish_request(request, &mut on_event);
+                        }
+                    }
+                    data = data_consumer.recv().fuse() => {
+                        if let Some(data) = data {
+                            on_event(ServerEvent::Media(data));
+                        }
+                    }
+                    result = run => break result,
+                }
+            }
+        };
+
+        // Report any media that was sent right before the session ended
+        drop(session);
+        while let Some(data) = data_consumer.recv().await {
+            on_event(ServerEvent::Media(data));
+        }
+
+        result
+    }
+}
+
+fn on_publish_request(request: PublishRequest, on_event: &mut impl FnMut(ServerEvent)) {
+    let uid = UniqueID::new_v4();
+
+    // If the session is gone, it is not publishing
+    if request.response.send(uid).is_ok() {
+        on_event(ServerEvent::Publishing {
+            app_name: request.app_name,
+            stream_key: request.stream_name,
+            uid,
+        });
+    }
+}
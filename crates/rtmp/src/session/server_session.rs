@@ -15,11 +15,17 @@ use crate::chunk::{CHUNK_SIZE, ChunkDecoder, ChunkEncoder};
 use crate::handshake::{HandshakeServer, ServerHandshakeState};
 use crate::messages::{MessageParser, RtmpMessageData};
 use crate::netconnection::NetConnection;
-use crate::netstream::NetStreamWriter;
+use crate::netstream::{NetStreamWriter, StatusLevel, UnpublishCommand};
 use crate::protocol_control_messages::ProtocolControlMessagesWriter;
 use crate::user_control_messages::EventMessagesWriter;
 use crate::{PublishProducer, handshake};
 
+/// The server version announced in the connect response by default.
+/// This value is used by other media servers as well, so clients accept it.
+pub(crate) const DEFAULT_FMSVER: &str = "FMS/3,0,1,123";
+/// The server capabilities announced in the connect response by default.
+pub(crate) const DEFAULT_CAPABILITIES: f64 = 31.0;
+
 pub struct Session<S> {
     /// When you connect via rtmp, you specify the app name in the url
     /// For example: rtmp://localhost:1935/live/xyz
@@ -67,6 +73,14 @@ pub struct Session<S> {
     /// when the publisher connects and tries to publish a stream, we need to
     /// send a publish request to the server
     publish_request_producer: PublishProducer,
+
+    /// Notified with the app name once the client has connected
+    connect_notifier: Option<oneshot::Sender<String>>,
+
+    /// The server version announced to the client in the connect response
+    fmsver: String,
+    /// The server capabilities announced to the client in the connect response
+    capabilities: f64,
 }
 
 impl<S> Session<S> {
@@ -84,9 +98,31 @@ impl<S> Session<S> {
             stream_id: 0,
             is_publishing: false,
             publish_request_producer,
+            connect_notifier: None,
+            fmsver: DEFAULT_FMSVER.to_string(),
+            capabilities: DEFAULT_CAPABILITIES,
         }
     }
 
+    /// Set the server version and capabilities announced to the client in
+    /// the connect response.
+    ///
+    /// Defaults to `FMS/3,0,1,123` and `31`, which is what most clients
+    /// expect. Some clients behave differently depending on these values, so
+    /// this can be used to mimic a specific server, for example
+    /// `FMS/3,5,7,7009`.
+    pub fn with_fmsver(mut self, fmsver: impl Into<String>, capabilities: f64) -> Self {
+        self.fmsver = fmsver.into();
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Notify the sender with the app name once the client has connected
+    pub(crate) fn with_connect_notifier(mut self, notifier: oneshot::Sender<String>) -> Self {
+        self.connect_notifier = Some(notifier);
+        self
+    }
+
     pub fn uid(&self) -> Option<UniqueID> {
         self.uid
     }
@@ -242,7 +278,11 @@ impl<S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin> Session<S> {
             RtmpMessageData::AudioData { data } => {
                 self.on_data(stream_id, ChannelData::Audio { timestamp, data }).await?;
             }
-            RtmpMessageData::VideoData { data } => {
+            RtmpMessageData::VideoData { data, enhanced_header } => {
+                if let Some(header) = enhanced_header {
+                    tracing::trace!(fourcc = ?header.fourcc, packet_type = ?header.packet_type, "enhanced video message");
+                }
+
                 self.on_data(stream_id, ChannelData::Video { timestamp, data }).await?;
             }
             RtmpMessageData::AmfData { data } => {
@@ -290,10 +330,11 @@ impl<S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin> Session<S> {
         command_object: Amf0Value<'_>,
         others: Vec<Amf0Value<'_>>,
     ) -> Result<(), SessionError> {
-        let cmd = RtmpCommand::from(match command_name {
-            Amf0Value::String(ref s) => s,
+        let command_name = match &command_name {
+            Amf0Value::String(s) => s.as_ref(),
             _ => "",
-        });
+        };
+        let cmd = RtmpCommand::from(command_name);
 
         let transaction_id = match transaction_id {
             Amf0Value::Number(number) => number,
@@ -313,7 +354,13 @@ impl<S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin> Session<S> {
                 self.on_command_create_stream(transaction_id, stream_id, &obj, others).await?;
             }
             RtmpCommand::DeleteStream => {
-                self.on_command_delete_stream(transaction_id, stream_id, &obj, others).await?;
+                self.on_command_unpublish(transaction_id, UnpublishCommand::delete_stream(&others))?;
+            }
+            RtmpCommand::CloseStream => {
+                self.on_command_unpublish(transaction_id, UnpublishCommand::CloseStream { stream_id })?;
+            }
+            RtmpCommand::FcUnpublish => {
+                self.on_command_unpublish(transaction_id, UnpublishCommand::fc_unpublish(&others))?;
             }
             RtmpCommand::Play => {
                 return Err(SessionError::PlayNotSupported);
@@ -321,7 +368,7 @@ impl<S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin> Session<S> {
             RtmpCommand::Publish => {
                 self.on_command_publish(transaction_id, stream_id, &obj, others).await?;
             }
-            RtmpCommand::CloseStream | RtmpCommand::ReleaseStream => {
+            RtmpCommand::ReleaseStream => {
                 // Not sure what this is for
             }
             RtmpCommand::Unknown(_) => {}
@@ -373,6 +420,11 @@ impl<S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin> Session<S> {
 
         self.app_name = Some(app_name.to_string());
 
+        if let Some(notifier) = self.connect_notifier.take() {
+            // The receiver may have been dropped, in which case nobody is interested
+            notifier.send(app_name.to_string()).ok();
+        }
+
         // The only AMF encoding supported by this server is AMF0
         // So we ignore the objectEncoding value sent by the client
         // and always use AMF0
@@ -386,8 +438,8 @@ impl<S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin> Session<S> {
             &self.chunk_encoder,
             &mut self.write_buf,
             transaction_id,
-            "FMS/3,0,1,123", // flash version (this value is used by other media servers as well)
-            31.0,            // No idea what this is, but it is used by other media servers as well
+            &self.fmsver,
+            self.capabilities,
             "NetConnection.Connect.Success",
             "status", // Again not sure what this is but other media servers use it.
             "Connection Succeeded.",
@@ -414,35 +466,49 @@ impl<S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin> Session<S> {
         Ok(())
     }
 
-    /// A delete stream message is unrelated to the NetConnection close method.
-    /// Delete stream is basically a way to tell the server that you are done
-    /// publishing or playing a stream. The server will then remove the stream
-    /// from its list of streams.
-    async fn on_command_delete_stream(
-        &mut self,
-        transaction_id: f64,
-        _stream_id: u32,
-        _command_obj: &[(Cow<'_, str>, Amf0Value<'_>)],
-        others: Vec<Amf0Value<'_>>,
-    ) -> Result<(), SessionError> {
-        let stream_id = match others.first() {
-            Some(Amf0Value::Number(stream_id)) => *stream_id,
-            _ => 0.0,
-        } as u32;
+    /// on_command_unpublish is called when we receive a `deleteStream`,
+    /// `closeStream` or `FCUnpublish` command. These are sent by a publisher
+    /// that is done publishing, a delete stream message is unrelated to the
+    /// NetConnection close method. If the command stops the published stream,
+    /// we respond with `NetStream.Unpublish.Success`. `FCUnpublish` is always
+    /// answered with `onFCUnpublish` instead.
+    fn on_command_unpublish(&mut self, transaction_id: f64, command: UnpublishCommand) -> Result<(), SessionError> {
+        let unpublished = match &command {
+            UnpublishCommand::DeleteStream { stream_id } | UnpublishCommand::CloseStream { stream_id } => {
+                self.is_publishing && self.stream_id == *stream_id
+            }
+            // Only a single stream can be published per session, so the stream name does not need to match
+            UnpublishCommand::FcUnpublish { stream_name } => {
+                tracing::debug!(stream_name = ?stream_name, "FCUnpublish");
+                self.is_publishing
+            }
+        };
 
-        if self.stream_id == stream_id && self.is_publishing {
+        if unpublished {
             self.stream_id = 0;
             self.is_publishing = false;
         }
 
-        NetStreamWriter::write_on_status(
-            &self.chunk_encoder,
-            &mut self.write_buf,
-            transaction_id,
-            "status",
-            "NetStream.DeleteStream.Suceess",
-            "",
-        )?;
+        match command {
+            UnpublishCommand::FcUnpublish { .. } => {
+                NetStreamWriter::write_on_fc_unpublish(&self.chunk_encoder, &mut self.write_buf, transaction_id)?;
+            }
+            _ if unpublished => {
+                NetStreamWriter::write_unpublish_success(&self.chunk_encoder, &mut self.write_buf, transaction_id)?;
+            }
+            _ => {}
+        }
+
+        if let UnpublishCommand::DeleteStream { .. } = command {
+            NetStreamWriter::write_on_status(
+                &self.chunk_encoder,
+                &mut self.write_buf,
+                transaction_id,
+                StatusLevel::Status,
+                "NetStream.DeleteStream.Suceess",
+                "",
+            )?;
+        }
 
         Ok(())
     }
@@ -480,10 +546,12 @@ impl<S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin> Session<S> {
             .await
             .is_err()
         {
+            self.send_publish_denied(transaction_id).await?;
             return Err(SessionError::PublishRequestDenied);
         }
 
         let Ok(uid) = waiter.await else {
+            self.send_publish_denied(transaction_id).await?;
             return Err(SessionError::PublishRequestDenied);
         };
 
@@ -498,7 +566,7 @@ impl<S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin> Session<S> {
             &self.chunk_encoder,
             &mut self.write_buf,
             transaction_id,
-            "status",
+            StatusLevel::Status,
             "NetStream.Publish.Start",
             "",
         )?;
@@ -506,6 +574,21 @@ impl<S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin> Session<S> {
         Ok(())
     }
 
+    /// Tells the client that its publish request was denied. The session is
+    /// closed afterwards, so the status is flushed immediately.
+    async fn send_publish_denied(&mut self, transaction_id: f64) -> Result<(), SessionError> {
+        NetStreamWriter::write_on_status(
+            &self.chunk_encoder,
+            &mut self.write_buf,
+            transaction_id,
+            StatusLevel::Error,
+            "NetStream.Publish.BadName",
+            "Publish request denied.",
+        )?;
+
+        self.flush().await
+    }
+
     async fn flush(&mut self) -> Result<(), SessionError> {
         if !self.write_buf.is_empty() {
             self.io
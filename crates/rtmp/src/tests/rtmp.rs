@@ -5,8 +5,8 @@ use scuffle_future_ext::FutureExt;
 use tokio::process::Command;
 use tokio::sync::mpsc;
 
-use crate::Session;
 use crate::channels::{ChannelData, UniqueID};
+use crate::{ServerConnection, ServerEvent, Session};
 
 #[tokio::test]
 #[cfg(not(valgrind))] // test is time-sensitive, consider refactoring?
@@ -205,3 +205,76 @@ async fn test_basic_rtmp_unclean() {
             .expect("failed to handle ffmpeg connection")
     );
 }
+
+#[tokio::test]
+#[cfg(not(valgrind))] // test is time-sensitive, consider refactoring?
+async fn test_server_connection() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("failed to bind");
+    let addr = listener.local_addr().unwrap();
+
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../assets");
+
+    let _ffmpeg = Command::new("ffmpeg")
+        .args([
+            "-re",
+            "-i",
+            dir.join("avc_aac.mp4").to_str().expect("failed to get path"),
+            "-r",
+            "30",
+            "-t",
+            "1", // just for the test so it doesn't take too long
+            "-c",
+            "copy",
+            "-f",
+            "flv",
+            &format!("rtmp://{}:{}/live/stream-key", addr.ip(), addr.port()),
+        ])
+        .stdout(std::process::Stdio::inherit())
+        .stderr(std::process::Stdio::inherit())
+        .spawn()
+        .expect("failed to execute ffmpeg");
+
+    let (ffmpeg_stream, _) = listener
+        .accept()
+        .with_timeout(Duration::from_millis(1000))
+        .await
+        .expect("timedout")
+        .expect("failed to accept");
+
+    let mut events = Vec::new();
+    let result = ServerConnection::new(ffmpeg_stream)
+        .run(|event| events.push(event))
+        .with_timeout(Duration::from_secs(10))
+        .await
+        .expect("timedout")
+        .expect("failed to handle ffmpeg connection");
+    assert!(result, "expected ffmpeg to stop publishing cleanly");
+
+    let mut events = events.into_iter();
+
+    assert!(matches!(
+        events.next(),
+        Some(ServerEvent::Connected { app_name }) if app_name == "live"
+    ));
+    assert!(matches!(
+        events.next(),
+        Some(ServerEvent::Publishing { app_name, stream_key, .. }) if app_name == "live" && stream_key == "stream-key"
+    ));
+
+    let mut got_video = false;
+    let mut got_audio = false;
+    let mut got_metadata = false;
+
+    for event in events {
+        match event {
+            ServerEvent::Media(ChannelData::Video { .. }) => got_video = true,
+            ServerEvent::Media(ChannelData::Audio { .. }) => got_audio = true,
+            ServerEvent::Media(ChannelData::Metadata { .. }) => got_metadata = true,
+            event => panic!("unexpected event: {event:?}"),
+        }
+    }
+
+    assert!(got_video);
+    assert!(got_audio);
+    assert!(got_metadata);
+}
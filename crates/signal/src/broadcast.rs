@@ -0,0 +1,137 @@
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::{SignalHandler, SignalKind};
+
+/// Broadcasts the signals received by a [`SignalHandler`] to many
+/// subscribers.
+///
+/// Created with [`SignalHandler::into_broadcast`]. A background task waits for
+/// signals on the handler and sends every one of them to all subscribers, so
+/// many independent components can each observe shutdown.
+///
+/// Subscribers only receive the signals delivered after they subscribed,
+/// signals delivered before are missed. A subscriber that falls more than the
+/// channel capacity behind skips the oldest signals it missed.
+///
+/// Dropping the `SignalBroadcast` stops the background task and ends every
+/// subscription.
+#[derive(Debug)]
+pub struct SignalBroadcast {
+    sender: broadcast::Sender<SignalKind>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl SignalBroadcast {
+    /// Subscribe to the signals delivered from now on.
+    ///
+    /// The stream ends when the `SignalBroadcast` is dropped.
+    pub fn subscribe(&self) -> impl Stream<Item = SignalKind> + Send + Unpin + 'static {
+        // Lagging behind is the only error, the skipped signals are lost.
+        BroadcastStream::new(self.sender.subscribe()).filter_map(Result::ok)
+    }
+}
+
+impl Drop for SignalBroadcast {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl SignalHandler {
+    /// Spawn a task that waits for signals and broadcasts each one to every
+    /// subscriber of the returned [`SignalBroadcast`].
+    ///
+    /// `capacity` is the number of signals kept for subscribers that have not
+    /// received them yet, it must be greater than zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero, or if called outside of a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use scuffle_signal::{SignalHandler, SignalKind};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let signals = SignalHandler::with_signals([SignalKind::Interrupt, SignalKind::Terminate]).into_broadcast(16);
+    ///
+    /// tokio::spawn({
+    ///     let mut signals = signals.subscribe();
+    ///     async move {
+    ///         while let Some(signal) = signals.next().await {
+    ///             println!("worker received {signal:?}");
+    ///         }
+    ///     }
+    /// });
+    ///
+    /// let signal = signals.subscribe().next().await;
+    /// println!("received {signal:?}");
+    /// # });
+    /// ```
+    pub fn into_broadcast(mut self, capacity: usize) -> SignalBroadcast {
+        let (sender, _) = broadcast::channel(capacity);
+
+        let task = tokio::spawn({
+            let sender = sender.clone();
+            async move {
+                loop {
+                    let signal = self.recv().await;
+                    // Nobody is subscribed, the signal is dropped
+                    sender.send(signal).ok();
+                }
+            }
+        });
+
+        SignalBroadcast { sender, task }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(coverage_nightly, test), coverage(off))]
+mod test {
+    use std::time::Duration;
+
+    use futures::StreamExt;
+    use scuffle_future_ext::FutureExt;
+
+    use crate::test::raise_signal;
+    use crate::{SignalHandler, SignalKind};
+
+    #[tokio::test]
+    #[cfg(not(valgrind))] // test is time-sensitive
+    async fn subscribe() {
+        let signals = SignalHandler::with_signals([SignalKind::Interrupt]).into_broadcast(16);
+        let mut first = signals.subscribe();
+        let mut second = signals.subscribe();
+
+        #[cfg(windows)]
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        raise_signal(SignalKind::Interrupt).await;
+
+        let recv = first.next().with_timeout(Duration::from_millis(500)).await.unwrap();
+        assert_eq!(recv, Some(SignalKind::Interrupt));
+
+        let recv = second.next().with_timeout(Duration::from_millis(500)).await.unwrap();
+        assert_eq!(recv, Some(SignalKind::Interrupt));
+
+        // Subscribers do not receive signals delivered before they subscribed
+        let mut late = signals.subscribe();
+        assert!(late.next().with_timeout(Duration::from_millis(100)).await.is_err());
+
+        drop(signals);
+
+        let recv = first.next().with_timeout(Duration::from_millis(500)).await.unwrap();
+        assert_eq!(recv, None);
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn zero_capacity() {
+        SignalHandler::new().into_broadcast(0);
+    }
+}
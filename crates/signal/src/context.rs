@@ -0,0 +1,385 @@
+use std::time::Duration;
+
+use scuffle_context::ContextFutExt;
+
+use crate::{SignalHandler, SignalKind};
+
+/// Spawn a task that cancels the given [`scuffle_context::Handler`] when an
+/// interrupt or terminate signal is received.
+///
+/// See [`cancel_on_signals`] for details.
+///
+/// # Panics
+///
+/// Panics if called outside of a Tokio runtime.
+pub fn cancel_on_signal(handler: scuffle_context::Handler) -> tokio::task::JoinHandle<Option<SignalKind>> {
+    cancel_on_signals(handler, [SignalKind::Interrupt, SignalKind::Terminate])
+}
+
+/// Spawn a task that cancels the given [`scuffle_context::Handler`] when any
+/// of the given signals is received.
+///
+/// The signals are registered before this function returns, so a signal
+/// raised right after calling it will not be missed.
+///
+/// The task resolves to the signal that caused the cancellation. If the
+/// handler is cancelled by something else first, the task stops listening and
+/// resolves to `None`.
+///
+/// # Panics
+///
+/// Panics if called outside of a Tokio runtime.
+pub fn cancel_on_signals<T: Into<SignalKind>>(
+    handler: scuffle_context::Handler,
+    signals: impl IntoIterator<Item = T>,
+) -> tokio::task::JoinHandle<Option<SignalKind>> {
+    let mut signals = SignalHandler::with_signals(signals);
+    let ctx = handler.context();
+
+    tokio::spawn(async move {
+        let signal = signals.recv().with_context(ctx).await?;
+        handler.cancel();
+        Some(signal)
+    })
+}
+
+/// Wait for a signal, cancel the given [`scuffle_context::Handler`] and wait
+/// for every context derived from it to be dropped.
+///
+/// This is the shutdown behavior of the `bootstrap` feature's signal service,
+/// without the rest of the bootstrap machinery. It can be awaited at the end of
+/// `main` after spawning tasks with contexts from the handler.
+///
+/// Returns the signal that caused the shutdown. If the handler is cancelled by
+/// something else first, this still waits for the shutdown and returns `None`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use scuffle_signal::{SignalHandler, SignalKind, run_until_shutdown};
+///
+/// # tokio_test::block_on(async {
+/// let (ctx, handler) = scuffle_context::Context::new();
+/// let signals = SignalHandler::with_signals([SignalKind::Interrupt, SignalKind::Terminate]);
+///
+/// tokio::spawn(async move {
+///     // Runs until a signal is received
+///     ctx.done().await;
+/// });
+///
+/// let signal = run_until_shutdown(signals, handler).await;
+/// println!("shut down after {signal:?}");
+/// # });
+/// ```
+pub async fn run_until_shutdown(mut signals: SignalHandler, handler: scuffle_context::Handler) -> Option<SignalKind> {
+    let signal = start_shutdown(&mut signals, &handler).await;
+    handler.shutdown().await;
+    signal
+}
+
+/// Wait for a signal or for the handler to be cancelled elsewhere, whichever
+/// comes first, and cancel the handler.
+async fn start_shutdown(signals: &mut SignalHandler, handler: &scuffle_context::Handler) -> Option<SignalKind> {
+    let signal = signals.recv().with_context(handler.context()).await;
+
+    #[cfg(feature = "tracing")]
+    match signal {
+        Some(signal) => tracing::info!(signal = ?signal, "shutting down"),
+        None => tracing::info!("context cancelled, shutting down"),
+    }
+
+    handler.cancel();
+    signal
+}
+
+impl SignalHandler {
+    /// Spawn a task that waits for the first signal and returns a
+    /// [`scuffle_context::Context`] that is cancelled when it is received.
+    ///
+    /// This is the recommended way to shut down an application on the first
+    /// interrupt or terminate signal. The context can be cloned cheaply and
+    /// passed to every task that should stop on shutdown.
+    ///
+    /// The task resolves to the signal that cancelled the context. Only the
+    /// first signal is handled, any signal received after it is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use scuffle_signal::{SignalHandler, SignalKind};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let (ctx, signal) = SignalHandler::with_signals([SignalKind::Interrupt, SignalKind::Terminate]).into_context();
+    ///
+    /// tokio::spawn({
+    ///     let ctx = ctx.clone();
+    ///     async move {
+    ///         // Runs until a signal is received
+    ///         ctx.done().await;
+    ///     }
+    /// });
+    ///
+    /// println!("received {:?}", signal.await.unwrap());
+    /// # });
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub fn into_context(mut self) -> (scuffle_context::Context, tokio::task::JoinHandle<SignalKind>) {
+        let (ctx, handler) = scuffle_context::Context::new();
+
+        let task = tokio::spawn(async move {
+            let signal = self.recv().await;
+            handler.cancel();
+            signal
+        });
+
+        (ctx, task)
+    }
+
+    /// Wait for a signal, cancel the given [`scuffle_context::Handler`] and wait
+    /// for every context derived from it to be dropped, unless the shutdown is
+    /// forced.
+    ///
+    /// After the first signal, the shutdown is forced by a second signal or when
+    /// the grace period elapses, so the application can exit without waiting
+    /// for tasks that are stuck. A grace period of `None` waits until every
+    /// context is dropped or a second signal is received. This is the shutdown
+    /// behavior of the `bootstrap` feature's signal service.
+    ///
+    /// If the handler is cancelled by something else first, this still waits
+    /// for the shutdown, and the first signal received forces it.
+    ///
+    /// Returns `true` if the shutdown completed gracefully, and `false` if it
+    /// was forced.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use scuffle_signal::{SignalHandler, SignalKind};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let (ctx, handler) = scuffle_context::Context::new();
+    /// let signals = SignalHandler::with_signals([SignalKind::Interrupt, SignalKind::Terminate]);
+    ///
+    /// tokio::spawn(async move {
+    ///     // Runs until a signal is received
+    ///     ctx.done().await;
+    /// });
+    ///
+    /// if !signals.shutdown_on_signal_with_grace(handler, Some(Duration::from_secs(30))).await {
+    ///     std::process::exit(1);
+    /// }
+    /// # });
+    /// ```
+    pub async fn shutdown_on_signal_with_grace(
+        mut self,
+        handler: scuffle_context::Handler,
+        grace_period: Option<Duration>,
+    ) -> bool {
+        start_shutdown(&mut self, &handler).await;
+
+        let grace_period = async {
+            match grace_period {
+                Some(grace_period) => tokio::time::sleep(grace_period).await,
+                None => std::future::pending().await,
+            }
+        };
+
+        tokio::select! {
+            _ = handler.shutdown() => true,
+            _signal = self.recv() => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(signal = ?_signal, "received signal, shutting down immediately");
+                false
+            },
+            _ = grace_period => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("grace period elapsed, shutting down immediately");
+                false
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(coverage_nightly, test), coverage(off))]
+mod test {
+    use std::time::Duration;
+
+    use scuffle_future_ext::FutureExt;
+
+    use super::{cancel_on_signal, run_until_shutdown};
+    use crate::test::raise_signal;
+    use crate::{SignalHandler, SignalKind};
+
+    #[tokio::test]
+    #[cfg(not(valgrind))] // test is time-sensitive
+    async fn cancel_on_terminate() {
+        let (ctx, handler) = scuffle_context::Context::new();
+
+        let task = cancel_on_signal(handler.clone());
+
+        #[cfg(windows)]
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        assert!(!ctx.is_done());
+
+        raise_signal(SignalKind::Terminate).await;
+
+        let signal = task.with_timeout(Duration::from_millis(500)).await.unwrap().unwrap();
+        assert_eq!(signal, Some(SignalKind::Terminate));
+
+        assert!(ctx.is_done());
+        assert!(handler.is_done());
+        ctx.done().with_timeout(Duration::from_millis(100)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn cancelled_elsewhere() {
+        let (ctx, handler) = scuffle_context::Context::new();
+
+        let task = cancel_on_signal(handler.clone());
+
+        handler.cancel();
+
+        let signal = task.with_timeout(Duration::from_millis(500)).await.unwrap().unwrap();
+        assert_eq!(signal, None);
+
+        // The task does not keep the context alive
+        drop(ctx);
+        handler.shutdown().with_timeout(Duration::from_millis(100)).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[cfg(not(valgrind))] // test is time-sensitive
+    async fn into_context() {
+        let (ctx, task) = SignalHandler::with_signals([SignalKind::Interrupt, SignalKind::Terminate]).into_context();
+        let ctx2 = ctx.clone();
+
+        #[cfg(windows)]
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        assert!(!ctx.is_done());
+
+        raise_signal(SignalKind::Interrupt).await;
+
+        ctx.done().with_timeout(Duration::from_millis(500)).await.unwrap();
+        assert!(ctx2.is_done());
+
+        let signal = task.with_timeout(Duration::from_millis(500)).await.unwrap().unwrap();
+        assert_eq!(signal, SignalKind::Interrupt);
+    }
+
+    #[tokio::test]
+    #[cfg(not(valgrind))] // test is time-sensitive
+    async fn run_until_shutdown_on_signal() {
+        let (ctx, handler) = scuffle_context::Context::new();
+        let signals = SignalHandler::with_signals([SignalKind::Interrupt]);
+
+        let task = tokio::spawn(run_until_shutdown(signals, handler.clone()));
+
+        #[cfg(windows)]
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        assert!(!ctx.is_done());
+
+        raise_signal(SignalKind::Interrupt).await;
+
+        ctx.done().with_timeout(Duration::from_millis(500)).await.unwrap();
+        assert!(handler.is_done());
+
+        // The shutdown waits for the context to be dropped
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!task.is_finished());
+        drop(ctx);
+
+        let signal = task.with_timeout(Duration::from_millis(500)).await.unwrap().unwrap();
+        assert_eq!(signal, Some(SignalKind::Interrupt));
+    }
+
+    #[tokio::test]
+    #[cfg(not(valgrind))] // test is time-sensitive
+    async fn shutdown_on_signal_with_grace_graceful() {
+        let (ctx, handler) = scuffle_context::Context::new();
+        let signals = SignalHandler::with_signals([SignalKind::Interrupt]);
+
+        let task = tokio::spawn(signals.shutdown_on_signal_with_grace(handler.clone(), Some(Duration::from_secs(5))));
+
+        #[cfg(windows)]
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        raise_signal(SignalKind::Interrupt).await;
+
+        ctx.done().with_timeout(Duration::from_millis(500)).await.unwrap();
+        assert!(handler.is_done());
+
+        // The shutdown waits for the context to be dropped
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!task.is_finished());
+        drop(ctx);
+
+        let graceful = task.with_timeout(Duration::from_millis(500)).await.unwrap().unwrap();
+        assert!(graceful);
+    }
+
+    #[tokio::test]
+    #[cfg(not(valgrind))] // test is time-sensitive
+    async fn shutdown_on_signal_with_grace_second_signal() {
+        let (ctx, handler) = scuffle_context::Context::new();
+        let signals = SignalHandler::with_signals([SignalKind::Interrupt]);
+
+        let task = tokio::spawn(signals.shutdown_on_signal_with_grace(handler.clone(), None));
+
+        #[cfg(windows)]
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        raise_signal(SignalKind::Interrupt).await;
+        ctx.done().with_timeout(Duration::from_millis(500)).await.unwrap();
+
+        // Without a grace period the shutdown waits until it is forced
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!task.is_finished());
+
+        raise_signal(SignalKind::Interrupt).await;
+
+        let graceful = task.with_timeout(Duration::from_millis(500)).await.unwrap().unwrap();
+        assert!(!graceful);
+        drop(ctx);
+    }
+
+    #[tokio::test]
+    #[cfg(not(valgrind))] // test is time-sensitive
+    async fn shutdown_on_signal_with_grace_grace_period() {
+        let (ctx, handler) = scuffle_context::Context::new();
+        let signals = SignalHandler::with_signals([SignalKind::Interrupt]);
+
+        let task = tokio::spawn(signals.shutdown_on_signal_with_grace(handler.clone(), Some(Duration::from_millis(100))));
+
+        #[cfg(windows)]
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        raise_signal(SignalKind::Interrupt).await;
+
+        let graceful = task.with_timeout(Duration::from_millis(1000)).await.unwrap().unwrap();
+        assert!(!graceful);
+        assert!(ctx.is_done());
+    }
+
+    #[tokio::test]
+    async fn run_until_shutdown_cancelled_elsewhere() {
+        let (ctx, handler) = scuffle_context::Context::new();
+        drop(ctx);
+
+        handler.cancel();
+
+        let signal = run_until_shutdown(SignalHandler::new(), handler)
+            .with_timeout(Duration::from_millis(500))
+            .await
+            .unwrap();
+        assert_eq!(signal, None);
+    }
+}
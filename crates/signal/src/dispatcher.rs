@@ -0,0 +1,255 @@
+use crate::{SignalHandler, SignalKind};
+
+type Callback = Box<dyn FnMut() + Send>;
+
+/// A dispatch table that maps signals to callbacks.
+///
+/// Matching on the signal returned by a [`SignalHandler`] is easy to get wrong,
+/// since binding a variable in a match arm matches every signal. This provides
+/// a table of callbacks keyed by [`SignalKind`] instead.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use scuffle_signal::{SignalDispatcher, SignalHandler, SignalKind};
+///
+/// # tokio_test::block_on(async {
+/// let mut dispatcher = SignalDispatcher::new()
+///     .on(SignalKind::Interrupt, || println!("received SIGINT"))
+///     .on(SignalKind::Terminate, || println!("received SIGTERM"));
+///
+/// let mut handler = SignalHandler::new();
+///
+/// // Calls the matching callback for every signal received, forever.
+/// dispatcher.run(&mut handler).await;
+/// # });
+/// ```
+#[must_use = "signal dispatchers must be run to dispatch signals"]
+#[derive(Default)]
+pub struct SignalDispatcher {
+    callbacks: Vec<(SignalKind, Callback)>,
+}
+
+impl std::fmt::Debug for SignalDispatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignalDispatcher")
+            .field("signals", &self.callbacks.iter().map(|(kind, _)| kind).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl SignalDispatcher {
+    /// Create a new `SignalDispatcher` with no callbacks.
+    pub const fn new() -> Self {
+        Self { callbacks: Vec::new() }
+    }
+
+    /// Register a callback for the given signal.
+    ///
+    /// If a callback is already registered for the signal, it is replaced.
+    pub fn on(mut self, kind: impl Into<SignalKind>, callback: impl FnMut() + Send + 'static) -> Self {
+        self.add_callback(kind, callback);
+        self
+    }
+
+    /// Register a callback for the given signal.
+    ///
+    /// If a callback is already registered for the signal, it is replaced.
+    pub fn add_callback(&mut self, kind: impl Into<SignalKind>, callback: impl FnMut() + Send + 'static) -> &mut Self {
+        let kind = kind.into();
+        let callback = Box::new(callback);
+
+        match self.callbacks.iter_mut().find(|(k, _)| k == &kind) {
+            Some((_, existing)) => *existing = callback,
+            None => self.callbacks.push((kind, callback)),
+        }
+
+        self
+    }
+
+    /// Returns the signals that have a callback registered.
+    pub fn signals(&self) -> impl Iterator<Item = SignalKind> + '_ {
+        self.callbacks.iter().map(|(kind, _)| *kind)
+    }
+
+    /// Call the callback registered for the given signal.
+    ///
+    /// Returns `false` if there is no callback registered for the signal.
+    pub fn dispatch(&mut self, kind: SignalKind) -> bool {
+        match self.callbacks.iter_mut().find(|(k, _)| k == &kind) {
+            Some((_, callback)) => {
+                callback();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Wait for signals on the handler and call the matching callback for each
+    /// one received.
+    ///
+    /// Every signal with a registered callback is added to the handler. This
+    /// never returns, use a timeout or a context to stop it.
+    pub async fn run(&mut self, handler: &mut SignalHandler) {
+        for (kind, _) in &self.callbacks {
+            handler.add_signal(*kind);
+        }
+
+        loop {
+            let kind = handler.recv().await;
+            self.dispatch(kind);
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(coverage_nightly, test), coverage(off))]
+mod test {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use scuffle_future_ext::FutureExt;
+
+    use super::SignalDispatcher;
+    use crate::test::raise_signal;
+    use crate::{SignalHandler, SignalKind};
+
+    fn counter() -> (Arc<AtomicUsize>, impl FnMut() + Send + 'static) {
+        let count = Arc::new(AtomicUsize::new(0));
+        let callback = {
+            let count = count.clone();
+            move || {
+                count.fetch_add(1, Ordering::SeqCst);
+            }
+        };
+
+        (count, callback)
+    }
+
+    #[test]
+    fn dispatch() {
+        let (interrupts, on_interrupt) = counter();
+        let (terminates, on_terminate) = counter();
+
+        let mut dispatcher = SignalDispatcher::new().on(SignalKind::Interrupt, on_interrupt);
+        dispatcher.add_callback(SignalKind::Terminate, on_terminate);
+
+        assert_eq!(
+            dispatcher.signals().collect::<Vec<_>>(),
+            vec![SignalKind::Interrupt, SignalKind::Terminate]
+        );
+
+        assert!(dispatcher.dispatch(SignalKind::Interrupt));
+        assert_eq!(interrupts.load(Ordering::SeqCst), 1);
+        assert_eq!(terminates.load(Ordering::SeqCst), 0);
+
+        assert!(dispatcher.dispatch(SignalKind::Terminate));
+        assert_eq!(interrupts.load(Ordering::SeqCst), 1);
+        assert_eq!(terminates.load(Ordering::SeqCst), 1);
+
+        // Replacing a callback
+        let (replaced, on_replaced) = counter();
+        dispatcher.add_callback(SignalKind::Interrupt, on_replaced);
+        assert_eq!(dispatcher.signals().count(), 2);
+
+        assert!(dispatcher.dispatch(SignalKind::Interrupt));
+        assert_eq!(interrupts.load(Ordering::SeqCst), 1);
+        assert_eq!(replaced.load(Ordering::SeqCst), 1);
+
+        let mut dispatcher = SignalDispatcher::default();
+        assert!(!dispatcher.dispatch(SignalKind::Interrupt));
+    }
+
+    #[cfg(windows)]
+    #[tokio::test]
+    async fn run() {
+        use crate::WindowsSignalKind;
+
+        let (ctrl_c, on_ctrl_c) = counter();
+        let (ctrl_break, on_ctrl_break) = counter();
+
+        let mut dispatcher = SignalDispatcher::new()
+            .on(WindowsSignalKind::CtrlC, on_ctrl_c)
+            .on(WindowsSignalKind::CtrlBreak, on_ctrl_break);
+        let mut handler = SignalHandler::new();
+
+        // Register the signals with the handler
+        assert!(
+            dispatcher
+                .run(&mut handler)
+                .with_timeout(Duration::from_millis(100))
+                .await
+                .is_err()
+        );
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        raise_signal(SignalKind::Windows(WindowsSignalKind::CtrlC)).await;
+        assert!(
+            dispatcher
+                .run(&mut handler)
+                .with_timeout(Duration::from_millis(500))
+                .await
+                .is_err()
+        );
+        assert_eq!(ctrl_c.load(Ordering::SeqCst), 1);
+        assert_eq!(ctrl_break.load(Ordering::SeqCst), 0);
+
+        raise_signal(SignalKind::Windows(WindowsSignalKind::CtrlBreak)).await;
+        assert!(
+            dispatcher
+                .run(&mut handler)
+                .with_timeout(Duration::from_millis(500))
+                .await
+                .is_err()
+        );
+        assert_eq!(ctrl_c.load(Ordering::SeqCst), 1);
+        assert_eq!(ctrl_break.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(all(not(valgrind), unix))] // test is time-sensitive
+    #[tokio::test]
+    async fn run() {
+        use crate::UnixSignalKind;
+
+        let (usr1, on_usr1) = counter();
+        let (usr2, on_usr2) = counter();
+
+        let mut dispatcher = SignalDispatcher::new()
+            .on(UnixSignalKind::user_defined1(), on_usr1)
+            .on(UnixSignalKind::user_defined2(), on_usr2);
+        let mut handler = SignalHandler::new();
+
+        // Register the signals with the handler
+        assert!(
+            dispatcher
+                .run(&mut handler)
+                .with_timeout(Duration::from_millis(100))
+                .await
+                .is_err()
+        );
+
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined1())).await;
+        assert!(
+            dispatcher
+                .run(&mut handler)
+                .with_timeout(Duration::from_millis(500))
+                .await
+                .is_err()
+        );
+        assert_eq!(usr1.load(Ordering::SeqCst), 1);
+        assert_eq!(usr2.load(Ordering::SeqCst), 0);
+
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined2())).await;
+        assert!(
+            dispatcher
+                .run(&mut handler)
+                .with_timeout(Duration::from_millis(500))
+                .await
+                .is_err()
+        );
+        assert_eq!(usr1.load(Ordering::SeqCst), 1);
+        assert_eq!(usr2.load(Ordering::SeqCst), 1);
+    }
+}
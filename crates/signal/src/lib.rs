@@ -28,17 +28,16 @@
 //! let signal = handler.await;
 //!
 //! // Handle the signal
-//! let interrupt = SignalKind::interrupt();
-//! let terminate = SignalKind::terminate();
 //! match signal {
-//!     interrupt => {
+//!     scuffle_signal::SignalKind::Interrupt => {
 //!         // Handle SIGINT
 //!         println!("received SIGINT");
 //!     },
-//!     terminate => {
+//!     scuffle_signal::SignalKind::Terminate => {
 //!         // Handle SIGTERM
 //!         println!("received SIGTERM");
 //!     },
+//!     _ => {},
 //! }
 //! # });
 //! # }
@@ -61,6 +60,7 @@
 
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::Instant;
 
 #[cfg(unix)]
 use tokio::signal::unix;
@@ -73,6 +73,20 @@ mod bootstrap;
 #[cfg(feature = "bootstrap")]
 pub use bootstrap::{SignalConfig, SignalSvc};
 
+#[cfg(feature = "broadcast")]
+mod broadcast;
+#[cfg(feature = "context")]
+mod context;
+mod dispatcher;
+#[cfg(feature = "serde")]
+mod serde;
+
+#[cfg(feature = "broadcast")]
+pub use broadcast::SignalBroadcast;
+#[cfg(feature = "context")]
+pub use context::{cancel_on_signal, cancel_on_signals, run_until_shutdown};
+pub use dispatcher::SignalDispatcher;
+
 /// The type of signal to listen for.
 #[derive(Debug, Clone, Copy, Eq)]
 pub enum SignalKind {
@@ -119,6 +133,360 @@ impl PartialEq for SignalKind {
     }
 }
 
+impl std::hash::Hash for SignalKind {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.normalized().hash(state);
+    }
+}
+
+impl PartialOrd for SignalKind {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Signals are ordered by their platform specific value, so that the order is
+/// consistent with [`PartialEq`].
+impl Ord for SignalKind {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.normalized().cmp(&other.normalized())
+    }
+}
+
+impl SignalKind {
+    /// Returns the raw signal number, such as `SIGINT` for
+    /// [`SignalKind::Interrupt`].
+    ///
+    /// This mirrors [`UnixSignalKind::as_raw_value`], and can be passed to
+    /// functions like `libc::sigaction` or `libc::raise`.
+    #[cfg(unix)]
+    pub const fn as_raw_value(&self) -> std::os::raw::c_int {
+        match self {
+            Self::Interrupt => UnixSignalKind::interrupt().as_raw_value(),
+            Self::Terminate => UnixSignalKind::terminate().as_raw_value(),
+            Self::Unix(kind) => kind.as_raw_value(),
+        }
+    }
+
+    /// Returns the Win32 `CTRL_*_EVENT` constant of the event, such as
+    /// `CTRL_C_EVENT` for [`SignalKind::Interrupt`].
+    ///
+    /// See [`WindowsSignalKind::as_raw_value`].
+    #[cfg(windows)]
+    pub const fn as_raw_value(&self) -> u32 {
+        self.normalized().as_raw_value()
+    }
+
+    /// Returns the platform specific value this signal compares equal to, used to
+    /// keep [`Hash`](std::hash::Hash) and [`Ord`] consistent with [`PartialEq`].
+    #[cfg(unix)]
+    fn normalized(&self) -> std::os::raw::c_int {
+        self.as_raw_value()
+    }
+
+    /// Returns the platform specific value this signal compares equal to, used to
+    /// keep [`Hash`](std::hash::Hash) and [`Ord`] consistent with [`PartialEq`].
+    #[cfg(windows)]
+    const fn normalized(&self) -> WindowsSignalKind {
+        match self {
+            Self::Interrupt => WindowsSignalKind::CtrlC,
+            Self::Terminate => WindowsSignalKind::CtrlClose,
+            Self::Windows(kind) => *kind,
+        }
+    }
+
+    /// Returns the short name of the signal, such as `SIGINT` on Unix or
+    /// `Ctrl-C` on Windows.
+    ///
+    /// This is useful for structured logging fields. Realtime signals on Linux
+    /// do not have a fixed name, so all of them are named `SIGRT`, while
+    /// [`Display`](std::fmt::Display) includes the offset, such as
+    /// `SIGRTMIN+2`. Signals that are not known are named `UNKNOWN`.
+    #[cfg(unix)]
+    pub fn name(&self) -> &'static str {
+        let raw = self.normalized();
+        match unix_signal_name(raw) {
+            Some(name) => name,
+            None if unix_realtime_offset(raw).is_some() => "SIGRT",
+            None => "UNKNOWN",
+        }
+    }
+
+    /// Returns the name of the console event, such as `Ctrl-C` for
+    /// [`SignalKind::Interrupt`] or `Ctrl-Close` for
+    /// [`SignalKind::Terminate`].
+    ///
+    /// This is useful for structured logging fields. Every
+    /// [`WindowsSignalKind`] has a fixed name, the same as
+    /// [`Display`](std::fmt::Display).
+    #[cfg(windows)]
+    pub fn name(&self) -> &'static str {
+        self.normalized().name()
+    }
+
+    /// Returns the interrupt signal, `SIGINT` on Unix and `Ctrl-C` on Windows.
+    ///
+    /// This is the same as [`SignalKind::Interrupt`], and compares equal to the
+    /// platform specific signal kind. Use the variant when matching on a
+    /// received signal.
+    pub const fn interrupt() -> Self {
+        Self::Interrupt
+    }
+
+    /// Returns the terminate signal, `SIGTERM` on Unix and `Ctrl-Close` on Windows.
+    ///
+    /// This is the same as [`SignalKind::Terminate`], and compares equal to the
+    /// platform specific signal kind. Use the variant when matching on a
+    /// received signal.
+    pub const fn terminate() -> Self {
+        Self::Terminate
+    }
+
+    /// Returns every signal that can be registered on the current platform.
+    ///
+    /// On Unix these are the signals with a named constructor on
+    /// [`UnixSignalKind`], on Windows these are all the
+    /// [`WindowsSignalKind`] console events. [`SignalKind::Interrupt`] and
+    /// [`SignalKind::Terminate`] are always included.
+    ///
+    /// This is useful to validate user provided signals, for example from a
+    /// config file, before registering them.
+    pub fn all_supported() -> Vec<SignalKind> {
+        #[cfg(unix)]
+        let kinds = [
+            UnixSignalKind::alarm(),
+            UnixSignalKind::child(),
+            UnixSignalKind::hangup(),
+            #[cfg(any(
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "macos",
+                target_os = "netbsd",
+                target_os = "openbsd",
+                target_os = "illumos"
+            ))]
+            UnixSignalKind::info(),
+            UnixSignalKind::interrupt(),
+            UnixSignalKind::io(),
+            UnixSignalKind::pipe(),
+            UnixSignalKind::quit(),
+            UnixSignalKind::terminate(),
+            UnixSignalKind::user_defined1(),
+            UnixSignalKind::user_defined2(),
+            UnixSignalKind::window_change(),
+        ];
+
+        #[cfg(windows)]
+        let kinds = [
+            WindowsSignalKind::CtrlBreak,
+            WindowsSignalKind::CtrlC,
+            WindowsSignalKind::CtrlClose,
+            WindowsSignalKind::CtrlLogoff,
+            WindowsSignalKind::CtrlShutdown,
+        ];
+
+        kinds.into_iter().map(Self::from).collect()
+    }
+}
+
+#[cfg(unix)]
+impl std::fmt::Display for SignalKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let raw = self.normalized();
+        match (unix_signal_name(raw), unix_realtime_offset(raw)) {
+            (Some(name), _) => f.write_str(name),
+            (None, Some(0)) => f.write_str("SIGRTMIN"),
+            (None, Some(offset)) => write!(f, "SIGRTMIN+{offset}"),
+            (None, None) => write!(f, "signal {raw}"),
+        }
+    }
+}
+
+#[cfg(windows)]
+impl std::fmt::Display for SignalKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.normalized(), f)
+    }
+}
+
+/// The error returned when parsing a [`SignalKind`] from a name that is not
+/// known, or that names a signal which cannot be listened for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSignalKindError {
+    name: String,
+    unsupported: bool,
+}
+
+impl ParseSignalKindError {
+    fn unknown(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            unsupported: false,
+        }
+    }
+
+    /// Returns the name that could not be parsed.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns `true` if the name is a known signal that cannot be listened
+    /// for, such as `SIGKILL`.
+    pub fn is_unsupported(&self) -> bool {
+        self.unsupported
+    }
+}
+
+impl std::fmt::Display for ParseSignalKindError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.unsupported {
+            write!(f, "signal cannot be listened for: {:?}", self.name)
+        } else {
+            write!(f, "unknown signal name: {:?}", self.name)
+        }
+    }
+}
+
+impl std::error::Error for ParseSignalKindError {}
+
+/// Parses a signal from its name, as returned by [`SignalKind::name`] and
+/// [`Display`](std::fmt::Display).
+///
+/// On Unix this accepts the canonical signal names, such as `SIGINT` or
+/// `SIGUSR1`, and `SIGRTMIN+n` for realtime signals on Linux. `SIGKILL` and
+/// `SIGSTOP` are rejected, since no handler can be registered for them. On
+/// Windows this accepts the event names, such as `Ctrl-C`, as well as the
+/// [`WindowsSignalKind`] variant names, such as `CtrlC`.
+impl std::str::FromStr for SignalKind {
+    type Err = ParseSignalKindError;
+
+    #[cfg(unix)]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let raw = UNIX_SIGNAL_NAMES
+            .iter()
+            .find(|(_, name)| *name == s)
+            .map(|(raw, _)| *raw)
+            .or_else(|| unix_realtime_from_name(s))
+            .ok_or_else(|| ParseSignalKindError::unknown(s))?;
+
+        if raw == libc::SIGKILL || raw == libc::SIGSTOP {
+            return Err(ParseSignalKindError {
+                name: s.to_string(),
+                unsupported: true,
+            });
+        }
+
+        Ok(Self::from(UnixSignalKind::from_raw(raw)))
+    }
+
+    #[cfg(windows)]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let kind = match s {
+            "Ctrl-Break" | "CtrlBreak" => WindowsSignalKind::CtrlBreak,
+            "Ctrl-C" | "CtrlC" => WindowsSignalKind::CtrlC,
+            "Ctrl-Close" | "CtrlClose" => WindowsSignalKind::CtrlClose,
+            "Ctrl-Logoff" | "CtrlLogoff" => WindowsSignalKind::CtrlLogoff,
+            "Ctrl-Shutdown" | "CtrlShutdown" => WindowsSignalKind::CtrlShutdown,
+            _ => return Err(ParseSignalKindError::unknown(s)),
+        };
+
+        Ok(Self::from(kind))
+    }
+}
+
+/// The standard Unix signals and their canonical names.
+///
+/// Some signals share a value on some platforms, the first name is used when
+/// formatting.
+#[cfg(unix)]
+const UNIX_SIGNAL_NAMES: &[(std::os::raw::c_int, &str)] = &[
+    (libc::SIGHUP, "SIGHUP"),
+    (libc::SIGINT, "SIGINT"),
+    (libc::SIGQUIT, "SIGQUIT"),
+    (libc::SIGILL, "SIGILL"),
+    (libc::SIGTRAP, "SIGTRAP"),
+    (libc::SIGABRT, "SIGABRT"),
+    (libc::SIGBUS, "SIGBUS"),
+    (libc::SIGFPE, "SIGFPE"),
+    (libc::SIGKILL, "SIGKILL"),
+    (libc::SIGUSR1, "SIGUSR1"),
+    (libc::SIGSEGV, "SIGSEGV"),
+    (libc::SIGUSR2, "SIGUSR2"),
+    (libc::SIGPIPE, "SIGPIPE"),
+    (libc::SIGALRM, "SIGALRM"),
+    (libc::SIGTERM, "SIGTERM"),
+    (libc::SIGCHLD, "SIGCHLD"),
+    (libc::SIGCONT, "SIGCONT"),
+    (libc::SIGSTOP, "SIGSTOP"),
+    (libc::SIGTSTP, "SIGTSTP"),
+    (libc::SIGTTIN, "SIGTTIN"),
+    (libc::SIGTTOU, "SIGTTOU"),
+    (libc::SIGURG, "SIGURG"),
+    (libc::SIGXCPU, "SIGXCPU"),
+    (libc::SIGXFSZ, "SIGXFSZ"),
+    (libc::SIGVTALRM, "SIGVTALRM"),
+    (libc::SIGPROF, "SIGPROF"),
+    (libc::SIGWINCH, "SIGWINCH"),
+    (libc::SIGIO, "SIGIO"),
+    (libc::SIGSYS, "SIGSYS"),
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    (libc::SIGPWR, "SIGPWR"),
+    #[cfg(any(
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "illumos"
+    ))]
+    (libc::SIGINFO, "SIGINFO"),
+];
+
+/// Returns the canonical name of a standard Unix signal.
+#[cfg(unix)]
+fn unix_signal_name(raw: std::os::raw::c_int) -> Option<&'static str> {
+    UNIX_SIGNAL_NAMES
+        .iter()
+        .find(|(value, _)| *value == raw)
+        .map(|(_, name)| *name)
+}
+
+/// Returns the offset from `SIGRTMIN` if the signal is a realtime signal.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn unix_realtime_offset(raw: std::os::raw::c_int) -> Option<std::os::raw::c_int> {
+    (libc::SIGRTMIN()..=libc::SIGRTMAX())
+        .contains(&raw)
+        .then(|| raw - libc::SIGRTMIN())
+}
+
+/// Realtime signals are only named on Linux.
+#[cfg(all(unix, not(any(target_os = "linux", target_os = "android"))))]
+fn unix_realtime_offset(_raw: std::os::raw::c_int) -> Option<std::os::raw::c_int> {
+    None
+}
+
+/// Parses a realtime signal named `SIGRTMIN` or `SIGRTMIN+n`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn unix_realtime_from_name(name: &str) -> Option<std::os::raw::c_int> {
+    let offset = match name.strip_prefix("SIGRTMIN")? {
+        "" => 0,
+        offset => offset
+            .strip_prefix('+')
+            .filter(|offset| offset.starts_with(|c: char| c.is_ascii_digit()))?
+            .parse::<u16>()
+            .ok()?,
+    };
+
+    let raw = libc::SIGRTMIN().checked_add(offset.into())?;
+    (raw <= libc::SIGRTMAX()).then_some(raw)
+}
+
+/// Realtime signals are only named on Linux.
+#[cfg(all(unix, not(any(target_os = "linux", target_os = "android"))))]
+fn unix_realtime_from_name(_name: &str) -> Option<std::os::raw::c_int> {
+    None
+}
+
 #[cfg(unix)]
 impl From<UnixSignalKind> for SignalKind {
     fn from(value: UnixSignalKind) -> Self {
@@ -141,35 +509,176 @@ impl PartialEq<UnixSignalKind> for SignalKind {
     }
 }
 
+#[cfg(unix)]
+impl PartialEq<SignalKind> for UnixSignalKind {
+    fn eq(&self, other: &SignalKind) -> bool {
+        other == self
+    }
+}
+
 /// Represents Windows-specific signal kinds.
+///
+/// Windows does not have signals in the Unix sense, instead these are console
+/// control events delivered to every process attached to the console. Not all
+/// of them can be sent to a specific child process group:
+///
+/// - `CtrlC` and `CtrlBreak` are the only events that can be generated with
+///   `GenerateConsoleCtrlEvent`. `CtrlC` is always delivered to the whole
+///   console, it cannot target a process group, and processes created with
+///   `CREATE_NEW_PROCESS_GROUP` ignore it by default. `CtrlBreak` can target a
+///   single process group, which makes it the only reliable way to signal a
+///   child process.
+/// - `CtrlClose`, `CtrlLogoff` and `CtrlShutdown` are generated by the system
+///   and delivered to every process attached to the console. `CtrlLogoff` and
+///   `CtrlShutdown` are only delivered to services, interactive applications
+///   will never receive them.
+///
+/// Only `CtrlC` and `CtrlClose` map to [`SignalKind::Interrupt`] and
+/// [`SignalKind::Terminate`] respectively, every other event is represented as
+/// [`SignalKind::Windows`]. In particular, listening for
+/// [`SignalKind::Interrupt`] will **not** catch `CtrlBreak`, it must be listened
+/// for explicitly.
+///
+/// See <https://learn.microsoft.com/en-us/windows/console/console-control-handlers>
 #[cfg(windows)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum WindowsSignalKind {
     /// Represents the `Ctrl-Break` signal.
+    ///
+    /// This is not an alias for [`SignalKind::Interrupt`].
     CtrlBreak,
     /// Represents the `Ctrl-C` signal.
+    ///
+    /// This is equivalent to [`SignalKind::Interrupt`].
     CtrlC,
     /// Represents the `Ctrl-Close` signal.
+    ///
+    /// This is equivalent to [`SignalKind::Terminate`].
     CtrlClose,
     /// Represents the `Ctrl-Logoff` signal.
+    ///
+    /// Only delivered to services.
     CtrlLogoff,
     /// Represents the `Ctrl-Shutdown` signal.
+    ///
+    /// Only delivered to services.
     CtrlShutdown,
 }
 
 #[cfg(windows)]
-impl From<WindowsSignalKind> for SignalKind {
-    fn from(value: WindowsSignalKind) -> Self {
+impl WindowsSignalKind {
+    /// Returns `true` if this event can be sent to a single process group with
+    /// `GenerateConsoleCtrlEvent`, rather than only to the whole console.
+    pub const fn is_group_deliverable(&self) -> bool {
+        matches!(self, Self::CtrlBreak)
+    }
+
+    /// Returns the Win32 `CTRL_*_EVENT` constant to pass to
+    /// `GenerateConsoleCtrlEvent` for this event.
+    ///
+    /// Returns `None` for `CtrlClose`, `CtrlLogoff` and `CtrlShutdown`, which
+    /// can only be generated by the system.
+    pub const fn as_ctrl_event(&self) -> Option<u32> {
+        match self {
+            Self::CtrlC | Self::CtrlBreak => Some(self.as_raw_value()),
+            Self::CtrlClose | Self::CtrlLogoff | Self::CtrlShutdown => None,
+        }
+    }
+
+    /// Returns the Win32 `CTRL_*_EVENT` constant of the event, as received by a
+    /// console control handler.
+    ///
+    /// Unlike [`WindowsSignalKind::as_ctrl_event`], this returns the constant
+    /// for every event, including the ones that can only be generated by the
+    /// system. This is the inverse of the [`TryFrom<u32>`] conversion.
+    pub const fn as_raw_value(&self) -> u32 {
+        match self {
+            Self::CtrlC => CTRL_C_EVENT,
+            Self::CtrlBreak => CTRL_BREAK_EVENT,
+            Self::CtrlClose => CTRL_CLOSE_EVENT,
+            Self::CtrlLogoff => CTRL_LOGOFF_EVENT,
+            Self::CtrlShutdown => CTRL_SHUTDOWN_EVENT,
+        }
+    }
+
+    /// Returns the name of the event, such as `Ctrl-C`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::CtrlBreak => "Ctrl-Break",
+            Self::CtrlC => "Ctrl-C",
+            Self::CtrlClose => "Ctrl-Close",
+            Self::CtrlLogoff => "Ctrl-Logoff",
+            Self::CtrlShutdown => "Ctrl-Shutdown",
+        }
+    }
+}
+
+#[cfg(windows)]
+impl std::fmt::Display for WindowsSignalKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(windows)]
+const CTRL_C_EVENT: u32 = 0;
+#[cfg(windows)]
+const CTRL_BREAK_EVENT: u32 = 1;
+#[cfg(windows)]
+const CTRL_CLOSE_EVENT: u32 = 2;
+#[cfg(windows)]
+const CTRL_LOGOFF_EVENT: u32 = 5;
+#[cfg(windows)]
+const CTRL_SHUTDOWN_EVENT: u32 = 6;
+
+/// Converts a Win32 `CTRL_*_EVENT` constant, as received by a console control
+/// handler, into a [`WindowsSignalKind`].
+///
+/// Unknown values are returned as the error.
+#[cfg(windows)]
+impl TryFrom<u32> for WindowsSignalKind {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
         match value {
+            CTRL_C_EVENT => Ok(Self::CtrlC),
+            CTRL_BREAK_EVENT => Ok(Self::CtrlBreak),
+            CTRL_CLOSE_EVENT => Ok(Self::CtrlClose),
+            CTRL_LOGOFF_EVENT => Ok(Self::CtrlLogoff),
+            CTRL_SHUTDOWN_EVENT => Ok(Self::CtrlShutdown),
+            value => Err(value),
+        }
+    }
+}
+
+#[cfg(windows)]
+impl SignalKind {
+    /// Create a [`SignalKind`] from a [`WindowsSignalKind`].
+    ///
+    /// `CtrlC` and `CtrlClose` are normalized to [`SignalKind::Interrupt`] and
+    /// [`SignalKind::Terminate`], every other event is kept as
+    /// [`SignalKind::Windows`]. This is the same mapping as the
+    /// [`From<WindowsSignalKind>`](From) implementation, but usable in const
+    /// contexts.
+    ///
+    /// See [`WindowsSignalKind`] for which events are deliverable to a child
+    /// process group.
+    pub const fn windows(kind: WindowsSignalKind) -> Self {
+        match kind {
             WindowsSignalKind::CtrlC => Self::Interrupt,
             WindowsSignalKind::CtrlClose => Self::Terminate,
-            WindowsSignalKind::CtrlBreak => Self::Windows(value),
-            WindowsSignalKind::CtrlLogoff => Self::Windows(value),
-            WindowsSignalKind::CtrlShutdown => Self::Windows(value),
+            kind => Self::Windows(kind),
         }
     }
 }
 
+#[cfg(windows)]
+impl From<WindowsSignalKind> for SignalKind {
+    fn from(value: WindowsSignalKind) -> Self {
+        Self::windows(value)
+    }
+}
+
 #[cfg(windows)]
 impl PartialEq<WindowsSignalKind> for SignalKind {
     fn eq(&self, other: &WindowsSignalKind) -> bool {
@@ -181,6 +690,13 @@ impl PartialEq<WindowsSignalKind> for SignalKind {
     }
 }
 
+#[cfg(windows)]
+impl PartialEq<SignalKind> for WindowsSignalKind {
+    fn eq(&self, other: &SignalKind) -> bool {
+        other == self
+    }
+}
+
 #[cfg(windows)]
 #[derive(Debug)]
 enum WindowsSignalValue {
@@ -281,6 +797,17 @@ impl SignalKind {
 /// After a signal is received you can poll the handler again to wait for
 /// another signal. Dropping the handle will cancel the signal subscription
 ///
+/// # Delivery
+///
+/// A signal is listened for as soon as it is added to the handler, not when the
+/// handler is first polled. A signal that arrives after
+/// [`SignalHandler::add_signal`] but before the handler is polled is not lost,
+/// it is returned by the next poll.
+///
+/// Delivery is edge-triggered: multiple signals of the same kind that arrive
+/// before the handler is polled are coalesced and returned once, the same way
+/// the operating system coalesces pending POSIX signals.
+///
 /// # Example
 ///
 /// ```rust
@@ -302,17 +829,16 @@ impl SignalKind {
 /// let signal = handler.await;
 ///
 /// // Handle the signal
-/// let interrupt = SignalKind::interrupt();
-/// let terminate = SignalKind::terminate();
 /// match signal {
-///     interrupt => {
+///     scuffle_signal::SignalKind::Interrupt => {
 ///         // Handle SIGINT
 ///         println!("received SIGINT");
 ///     },
-///     terminate => {
+///     scuffle_signal::SignalKind::Terminate => {
 ///         // Handle SIGTERM
 ///         println!("received SIGTERM");
 ///     },
+///     _ => {},
 /// }
 /// # });
 /// # }
@@ -321,6 +847,32 @@ impl SignalKind {
 #[must_use = "signal handlers must be used to wait for signals"]
 pub struct SignalHandler {
     signals: Vec<(SignalKind, Signal)>,
+    history: Option<SignalHistory>,
+}
+
+/// A bounded record of the signals delivered by a [`SignalHandler`].
+#[derive(Debug)]
+struct SignalHistory {
+    capacity: usize,
+    entries: Vec<(SignalKind, Instant)>,
+}
+
+impl SignalHistory {
+    /// The most entries allocated up front, larger histories grow as signals
+    /// are received.
+    const MAX_INITIAL_CAPACITY: usize = 64;
+
+    fn record(&mut self, kind: SignalKind) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.entries.len() == self.capacity {
+            self.entries.remove(0);
+        }
+
+        self.entries.push((kind, Instant::now()));
+    }
 }
 
 impl Default for SignalHandler {
@@ -332,7 +884,10 @@ impl Default for SignalHandler {
 impl SignalHandler {
     /// Create a new `SignalHandler` with no signals.
     pub const fn new() -> Self {
-        Self { signals: Vec::new() }
+        Self {
+            signals: Vec::new(),
+            history: None,
+        }
     }
 
     /// Create a new `SignalHandler` with the given signals.
@@ -346,71 +901,403 @@ impl SignalHandler {
         handler
     }
 
+    /// Create a new `SignalHandler` with the signals that ask a process to shut
+    /// down.
+    ///
+    /// On Unix these are `SIGINT`, `SIGTERM` and `SIGQUIT`, on Windows these are
+    /// `Ctrl-C`, `Ctrl-Close` and `Ctrl-Break`. More signals can be added with
+    /// [`SignalHandler::with_signal`].
+    pub fn with_terminating_signals() -> Self {
+        #[cfg(unix)]
+        let kinds = [SignalKind::Interrupt, SignalKind::Terminate, UnixSignalKind::quit().into()];
+
+        #[cfg(windows)]
+        let kinds = [
+            SignalKind::Interrupt,
+            SignalKind::Terminate,
+            WindowsSignalKind::CtrlBreak.into(),
+        ];
+
+        Self::with_signals(kinds)
+    }
+
+    /// Record the last `capacity` signals delivered by the handler, along with
+    /// the time they were received.
+    ///
+    /// Recording is disabled by default. The recorded signals can be accessed
+    /// with [`SignalHandler::history`].
+    pub fn with_history(mut self, capacity: usize) -> Self {
+        self.history = Some(SignalHistory {
+            capacity,
+            entries: Vec::with_capacity(capacity.min(SignalHistory::MAX_INITIAL_CAPACITY)),
+        });
+        self
+    }
+
+    /// Returns the signals delivered by the handler, oldest first, along with
+    /// the time they were received.
+    ///
+    /// This is always empty unless recording was enabled with
+    /// [`SignalHandler::with_history`].
+    pub fn history(&self) -> &[(SignalKind, Instant)] {
+        self.history.as_ref().map_or(&[], |h| &h.entries)
+    }
+
     /// Add a signal to the handler.
     ///
     /// If the signal is already in the handler, it will not be added again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the signal cannot be listened for, see
+    /// [`SignalHandler::try_with_signal`].
     pub fn with_signal(mut self, kind: impl Into<SignalKind>) -> Self {
         self.add_signal(kind);
         self
     }
 
+    /// Add a signal to the handler, returning an error if the signal cannot be
+    /// listened for.
+    ///
+    /// If the signal is already in the handler, it will not be added again.
+    pub fn try_with_signal(mut self, kind: impl Into<SignalKind>) -> Result<Self, std::io::Error> {
+        self.try_add_signal(kind)?;
+        Ok(self)
+    }
+
     /// Add a signal to the handler.
     ///
+    /// The signal is listened for immediately, so it is delivered even if it
+    /// arrives before the handler is polled.
+    ///
     /// If the signal is already in the handler, it will not be added again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the signal cannot be listened for, see
+    /// [`SignalHandler::try_add_signal`].
     pub fn add_signal(&mut self, kind: impl Into<SignalKind>) -> &mut Self {
+        self.try_add_signal(kind).expect("failed to create signal")
+    }
+
+    /// Add a signal to the handler, returning an error if the signal cannot be
+    /// listened for.
+    ///
+    /// On Unix this fails for signals that cannot have a handler, such as
+    /// `SIGKILL`, or for signal numbers that are not valid on the platform.
+    /// The handler is not changed if an error is returned.
+    ///
+    /// If the signal is already in the handler, it will not be added again.
+    pub fn try_add_signal(&mut self, kind: impl Into<SignalKind>) -> Result<&mut Self, std::io::Error> {
         let kind = kind.into();
         if self.signals.iter().any(|(k, _)| k == &kind) {
-            return self;
+            return Ok(self);
         }
 
-        let signal = kind.listen().expect("failed to create signal");
+        let signal = kind.listen()?;
 
         self.signals.push((kind, signal));
 
-        self
+        Ok(self)
     }
 
-    /// Wait for a signal to be received.
-    /// This is equivilant to calling (&mut handler).await, but is more
-    /// ergonomic if you want to not take ownership of the handler.
-    pub async fn recv(&mut self) -> SignalKind {
-        self.await
+    /// Replace the subscription of a signal in the handler.
+    ///
+    /// Unlike [`SignalHandler::add_signal`], which does nothing if the signal
+    /// is already in the handler, this listens for the signal again and drops
+    /// the existing subscription. A signal that arrived but was not yet
+    /// received is discarded. The signal keeps its position in the handler,
+    /// and its entries are removed from the [history](SignalHandler::history).
+    ///
+    /// If the signal is not in the handler, it is added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the signal cannot be listened for, see
+    /// [`SignalHandler::try_replace_signal`].
+    pub fn replace_signal(&mut self, kind: impl Into<SignalKind>) -> &mut Self {
+        self.try_replace_signal(kind).expect("failed to create signal")
     }
 
-    /// Poll for a signal to be received.
-    /// Does not require pinning the handler.
-    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<SignalKind> {
-        for (kind, signal) in self.signals.iter_mut() {
-            if signal.poll_recv(cx).is_ready() {
-                return Poll::Ready(*kind);
-            }
+    /// Replace the subscription of a signal in the handler, returning an error
+    /// if the signal cannot be listened for.
+    ///
+    /// The new subscription is created before the existing one is dropped, so
+    /// the handler is not changed if an error is returned. See
+    /// [`SignalHandler::replace_signal`].
+    pub fn try_replace_signal(&mut self, kind: impl Into<SignalKind>) -> Result<&mut Self, std::io::Error> {
+        let kind = kind.into();
+        let signal = kind.listen()?;
+
+        match self.signals.iter_mut().find(|(k, _)| k == &kind) {
+            Some(entry) => *entry = (kind, signal),
+            None => self.signals.push((kind, signal)),
         }
 
-        Poll::Pending
+        if let Some(history) = &mut self.history {
+            history.entries.retain(|(k, _)| k != &kind);
+        }
+
+        Ok(self)
     }
-}
 
-impl std::future::Future for SignalHandler {
-    type Output = SignalKind;
+    /// Remove a signal from the handler.
+    ///
+    /// The subscription of the signal is dropped, so it is no longer received
+    /// by this handler. Returns `true` if the signal was in the handler.
+    ///
+    /// The process keeps the signal handler installed by tokio, so a removed
+    /// signal is ignored instead of running its default action, such as
+    /// terminating the process.
+    pub fn remove_signal(&mut self, kind: impl Into<SignalKind>) -> bool {
+        let kind = kind.into();
+        let Some(index) = self.signals.iter().position(|(k, _)| k == &kind) else {
+            return false;
+        };
 
-    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        self.poll_recv(cx)
-    }
-}
+        drop(self.signals.remove(index));
 
-#[cfg(test)]
-#[cfg_attr(coverage_nightly, coverage(off))]
-mod test {
-    use std::time::Duration;
+        true
+    }
 
-    use scuffle_future_ext::FutureExt;
+    /// Remove every signal from the handler.
+    ///
+    /// The subscriptions of the signals are dropped, leaving the handler like a
+    /// [`SignalHandler::new`] one, which is useful to rebuild the set of signals
+    /// with [`SignalHandler::add_signal`], for example after reloading a
+    /// configuration. The [history](SignalHandler::history) is kept.
+    ///
+    /// See [`SignalHandler::remove_signal`] for what happens to the removed
+    /// signals.
+    pub fn clear(&mut self) -> &mut Self {
+        self.signals.clear();
+        self
+    }
 
-    use crate::{SignalHandler, SignalKind};
+    /// Returns an iterator over the signals registered in the handler, in the
+    /// order they were added.
+    ///
+    /// Signals that compare equal, such as [`SignalKind::Interrupt`] and its
+    /// platform specific equivalent, are only registered once.
+    pub fn signals(&self) -> impl Iterator<Item = SignalKind> + '_ {
+        self.signals.iter().map(|(kind, _)| *kind)
+    }
 
-    #[cfg(windows)]
-    pub(crate) struct SignalMocker(tokio::sync::broadcast::Sender<SignalKind>);
+    /// Returns the signals registered in the handler, in the order they were
+    /// added.
+    ///
+    /// Together with [`SignalHandler::reconfigure`], this can be used to
+    /// change the signals of a running handler, for example when reloading
+    /// the configuration of a server.
+    pub fn kinds_snapshot(&self) -> Vec<SignalKind> {
+        self.signals().collect()
+    }
 
-    #[cfg(windows)]
+    /// Replace the signals of the handler with the given signals.
+    ///
+    /// Signals that are already in the handler keep their subscription, so a
+    /// signal that arrived but was not yet received is not lost. Signals that
+    /// are not in `kinds` are removed, and the new ones are listened for
+    /// before the removed ones are dropped. The [history](SignalHandler::history)
+    /// is kept.
+    ///
+    /// Returns an error if one of the new signals cannot be listened for, see
+    /// [`SignalHandler::try_add_signal`]. The handler is not changed if an error
+    /// is returned.
+    ///
+    /// ```rust
+    /// # #[cfg(unix)]
+    /// # {
+    /// # tokio_test::block_on(async {
+    /// use scuffle_signal::{SignalHandler, SignalKind, UnixSignalKind};
+    ///
+    /// let mut handler = SignalHandler::with_signals([SignalKind::Interrupt]);
+    ///
+    /// // Add a signal to the current set
+    /// let mut kinds = handler.kinds_snapshot();
+    /// kinds.push(SignalKind::Unix(UnixSignalKind::hangup()));
+    /// handler.reconfigure(kinds).expect("failed to reconfigure");
+    ///
+    /// assert_eq!(handler.kinds_snapshot().len(), 2);
+    /// # });
+    /// # }
+    /// ```
+    pub fn reconfigure<T: Into<SignalKind>>(
+        &mut self,
+        kinds: impl IntoIterator<Item = T>,
+    ) -> Result<&mut Self, std::io::Error> {
+        let mut kinds_in_order = Vec::new();
+        for kind in kinds {
+            let kind = kind.into();
+            if !kinds_in_order.contains(&kind) {
+                kinds_in_order.push(kind);
+            }
+        }
+
+        // Listen for the new signals first, so nothing is changed on error.
+        let mut added = Self::new();
+        for kind in &kinds_in_order {
+            if !self.signals.iter().any(|(k, _)| k == kind) {
+                added.try_add_signal(*kind)?;
+            }
+        }
+
+        let mut previous = std::mem::take(&mut self.signals);
+        let mut added = added.signals.into_iter();
+        for kind in kinds_in_order {
+            let entry = match previous.iter().position(|(k, _)| k == &kind) {
+                Some(index) => previous.remove(index),
+                None => added.next().expect("every new signal was listened for"),
+            };
+            self.signals.push(entry);
+        }
+
+        Ok(self)
+    }
+
+    /// Wait for a signal to be received.
+    /// This is equivilant to calling (&mut handler).await, but is more
+    /// ergonomic if you want to not take ownership of the handler.
+    pub async fn recv(&mut self) -> SignalKind {
+        self.await
+    }
+
+    /// Wait until every signal in `kinds` has been received at least once.
+    ///
+    /// Signals are received in any order, and signals that are not in `kinds`
+    /// are ignored. Each received signal is still recorded in the
+    /// [history](SignalHandler::history).
+    ///
+    /// The signals must be registered in the handler, otherwise this never
+    /// completes. An empty `kinds` completes immediately.
+    pub async fn recv_all(&mut self, kinds: &[SignalKind]) {
+        let mut remaining = Vec::with_capacity(kinds.len());
+        for kind in kinds {
+            if !remaining.contains(kind) {
+                remaining.push(*kind);
+            }
+        }
+
+        while !remaining.is_empty() {
+            let kind = self.recv().await;
+            remaining.retain(|k| k != &kind);
+        }
+    }
+
+    /// Poll for a signal to be received.
+    /// Does not require pinning the handler.
+    ///
+    /// With the `tracing` feature enabled, an info event with the name of the
+    /// signal is emitted for every signal received.
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<SignalKind> {
+        for (kind, signal) in self.signals.iter_mut() {
+            if signal.poll_recv(cx).is_ready() {
+                Self::received(&mut self.history, *kind);
+                return Poll::Ready(*kind);
+            }
+        }
+
+        Poll::Pending
+    }
+
+    /// Poll for a signal to be received, and count how many deliveries of it
+    /// are pending.
+    ///
+    /// Once a signal is received, its deliveries are consumed until none are
+    /// left, and the number of deliveries consumed is returned with it. This is
+    /// useful to tell apart a single `Ctrl-C` from the user pressing it
+    /// repeatedly, for example to force quit.
+    ///
+    /// The count is best-effort: the operating system coalesces a signal that is
+    /// raised again before the previous delivery was handled, so several signals
+    /// raised in quick succession can be counted as one. The count is always at
+    /// least `1`.
+    ///
+    /// Every delivery counted is recorded in the [history](SignalHandler::history).
+    pub fn poll_recv_counted(&mut self, cx: &mut Context<'_>) -> Poll<(SignalKind, usize)> {
+        for (kind, signal) in self.signals.iter_mut() {
+            if signal.poll_recv(cx).is_ready() {
+                Self::received(&mut self.history, *kind);
+
+                let mut count = 1;
+                while let Poll::Ready(Some(())) = signal.poll_recv(cx) {
+                    Self::received(&mut self.history, *kind);
+                    count += 1;
+                }
+
+                return Poll::Ready((*kind, count));
+            }
+        }
+
+        Poll::Pending
+    }
+
+    /// Return every signal that has already been received, without waiting.
+    ///
+    /// Every signal in the handler is polled once, so each kind is returned at
+    /// most once. This is useful as a final check during shutdown, for example
+    /// to force an exit if the user pressed `Ctrl-C` again while the
+    /// application was shutting down.
+    ///
+    /// Signals are delivered to the handler by the tokio runtime, so a signal
+    /// raised right before calling this might not be returned yet.
+    pub fn drain(&mut self) -> Vec<SignalKind> {
+        let mut cx = Context::from_waker(std::task::Waker::noop());
+        let mut drained = Vec::new();
+
+        for (kind, signal) in self.signals.iter_mut() {
+            if signal.poll_recv(&mut cx).is_ready() {
+                Self::received(&mut self.history, *kind);
+                drained.push(*kind);
+            }
+        }
+
+        drained
+    }
+
+    fn received(history: &mut Option<SignalHistory>, kind: SignalKind) {
+        if let Some(history) = history {
+            history.record(kind);
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::info!(signal = %kind, "received signal");
+    }
+}
+
+impl std::future::Future for SignalHandler {
+    type Output = SignalKind;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.poll_recv(cx)
+    }
+}
+
+/// The stream yields every signal received by the handler, and never ends.
+///
+/// A handler without any signals is always pending.
+#[cfg(feature = "stream")]
+impl futures_core::Stream for SignalHandler {
+    type Item = SignalKind;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.poll_recv(cx).map(Some)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod test {
+    use std::time::Duration;
+
+    use scuffle_future_ext::FutureExt;
+
+    use crate::{SignalHandler, SignalKind};
+
+    #[cfg(windows)]
+    pub(crate) struct SignalMocker(tokio::sync::broadcast::Sender<SignalKind>);
+
+    #[cfg(windows)]
     impl SignalMocker {
         fn new() -> Self {
             println!("new");
@@ -434,6 +1321,11 @@ mod test {
         static SIGNAL_MOCKER: SignalMocker = SignalMocker::new();
     }
 
+    /// Injects the signal into every mocked `WindowsSignalValue`.
+    ///
+    /// `GenerateConsoleCtrlEvent` can only generate `CTRL_C_EVENT` and
+    /// `CTRL_BREAK_EVENT`, so the events are mocked to cover every kind,
+    /// including logoff and shutdown.
     #[cfg(windows)]
     pub async fn raise_signal(kind: SignalKind) {
         SignalMocker::raise(kind);
@@ -443,11 +1335,7 @@ mod test {
     pub async fn raise_signal(kind: SignalKind) {
         // Safety: This is a test, and we control the process.
         unsafe {
-            libc::raise(match kind {
-                SignalKind::Interrupt => libc::SIGINT,
-                SignalKind::Terminate => libc::SIGTERM,
-                SignalKind::Unix(kind) => kind.as_raw_value(),
-            });
+            libc::raise(kind.as_raw_value());
         }
     }
 
@@ -476,6 +1364,58 @@ mod test {
         assert_eq!(recv, WindowsSignalKind::CtrlBreak, "expected CtrlBreak");
     }
 
+    #[cfg(windows)]
+    #[tokio::test]
+    async fn signal_handler_all_kinds() {
+        use crate::WindowsSignalKind;
+
+        let kinds = [
+            WindowsSignalKind::CtrlBreak,
+            WindowsSignalKind::CtrlC,
+            WindowsSignalKind::CtrlClose,
+            WindowsSignalKind::CtrlLogoff,
+            WindowsSignalKind::CtrlShutdown,
+        ];
+
+        for kind in kinds {
+            let mut handler = SignalHandler::with_signals([kind]);
+
+            // Other kinds do not resolve the handler
+            for other in kinds.into_iter().filter(|other| *other != kind) {
+                raise_signal(SignalKind::Windows(other)).await;
+            }
+            let recv = handler.recv().with_timeout(Duration::from_millis(100)).await;
+            assert!(recv.is_err(), "expected timeout for {kind:?}");
+
+            raise_signal(SignalKind::Windows(kind)).await;
+            let recv = handler
+                .recv()
+                .with_timeout(Duration::from_millis(500))
+                .await
+                .unwrap_or_else(|_| panic!("expected {kind:?}"));
+            assert_eq!(recv, kind);
+        }
+    }
+
+    #[cfg(all(windows, feature = "tracing"))]
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn tracing() {
+        use crate::WindowsSignalKind;
+
+        let mut handler = SignalHandler::with_signals([WindowsSignalKind::CtrlBreak]);
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+        raise_signal(SignalKind::Windows(WindowsSignalKind::CtrlBreak)).await;
+
+        let recv = handler.recv().with_timeout(Duration::from_millis(500)).await.unwrap();
+        assert_eq!(recv, WindowsSignalKind::CtrlBreak, "expected CtrlBreak");
+
+        assert!(logs_contain("received signal"));
+        assert!(logs_contain("signal=Ctrl-Break"));
+    }
+
     #[cfg(windows)]
     #[tokio::test]
     async fn add_signal() {
@@ -531,6 +1471,23 @@ mod test {
         assert_eq!(recv, UnixSignalKind::user_defined2(), "expected SIGUSR2");
     }
 
+    #[cfg(all(not(valgrind), unix, feature = "tracing"))] // test is time-sensitive
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn tracing() {
+        use crate::UnixSignalKind;
+
+        let mut handler = SignalHandler::with_signals([UnixSignalKind::user_defined1()]);
+
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined1())).await;
+
+        let recv = handler.recv().with_timeout(Duration::from_millis(500)).await.unwrap();
+        assert_eq!(recv, UnixSignalKind::user_defined1(), "expected SIGUSR1");
+
+        assert!(logs_contain("received signal"));
+        assert!(logs_contain("signal=SIGUSR1"));
+    }
+
     #[cfg(all(not(valgrind), unix))] // test is time-sensitive
     #[tokio::test]
     async fn add_signal() {
@@ -556,44 +1513,924 @@ mod test {
         assert_eq!(recv, UnixSignalKind::user_defined2(), "expected SIGUSR2");
     }
 
-    #[cfg(not(valgrind))] // test is time-sensitive
+    #[cfg(all(not(valgrind), unix))] // test is time-sensitive
     #[tokio::test]
-    async fn no_signals() {
-        let mut handler = SignalHandler::default();
+    async fn recv_all() {
+        use crate::UnixSignalKind;
 
-        // Expected to timeout
-        assert!(handler.recv().with_timeout(Duration::from_millis(500)).await.is_err());
+        let mut handler = SignalHandler::with_signals([
+            UnixSignalKind::user_defined1(),
+            UnixSignalKind::user_defined2(),
+            UnixSignalKind::hangup(),
+        ]);
+
+        let kinds = [
+            SignalKind::Unix(UnixSignalKind::user_defined1()),
+            SignalKind::Unix(UnixSignalKind::user_defined2()),
+        ];
+        {
+            let recv_all = handler.recv_all(&kinds);
+            tokio::pin!(recv_all);
+
+            raise_signal(SignalKind::Unix(UnixSignalKind::user_defined1())).await;
+            assert!(
+                (&mut recv_all).with_timeout(Duration::from_millis(500)).await.is_err(),
+                "expected to wait for SIGUSR2"
+            );
+
+            // Signals that are not waited for are ignored
+            raise_signal(SignalKind::Unix(UnixSignalKind::hangup())).await;
+            assert!(
+                (&mut recv_all).with_timeout(Duration::from_millis(500)).await.is_err(),
+                "expected to wait for SIGUSR2"
+            );
+
+            raise_signal(SignalKind::Unix(UnixSignalKind::user_defined2())).await;
+            (&mut recv_all)
+                .with_timeout(Duration::from_millis(500))
+                .await
+                .expect("expected all signals to be received");
+        }
+
+        // Nothing to wait for
+        handler
+            .recv_all(&[])
+            .with_timeout(Duration::from_millis(500))
+            .await
+            .expect("expected to complete immediately");
+    }
+
+    #[cfg(all(not(valgrind), unix, feature = "stream"))] // test is time-sensitive
+    #[tokio::test]
+    async fn stream() {
+        use futures::StreamExt;
+
+        use crate::UnixSignalKind;
+
+        let mut handler = SignalHandler::with_signals([UnixSignalKind::user_defined1(), UnixSignalKind::user_defined2()]);
+
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined1())).await;
+
+        let recv = handler.next().with_timeout(Duration::from_millis(500)).await.unwrap();
+        assert_eq!(recv, Some(SignalKind::Unix(UnixSignalKind::user_defined1())));
+
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined2())).await;
+
+        let recv = handler.next().with_timeout(Duration::from_millis(500)).await.unwrap();
+        assert_eq!(recv, Some(SignalKind::Unix(UnixSignalKind::user_defined2())));
+
+        // The stream does not end while waiting for more signals
+        assert!(handler.next().with_timeout(Duration::from_millis(500)).await.is_err());
+
+        // An empty handler never yields
+        let mut handler = SignalHandler::new();
+        assert!(handler.next().with_timeout(Duration::from_millis(500)).await.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn try_add_signal() {
+        use crate::UnixSignalKind;
+
+        let mut handler = SignalHandler::new()
+            .try_with_signal(UnixSignalKind::user_defined1())
+            .expect("failed to add SIGUSR1");
+
+        // Signals that cannot have a handler are rejected instead of panicking
+        assert!(handler.try_add_signal(UnixSignalKind::from_raw(libc::SIGKILL)).is_err());
+        assert!(handler.try_add_signal(UnixSignalKind::from_raw(-1)).is_err());
+        assert_eq!(handler.kinds_snapshot(), [SignalKind::Unix(UnixSignalKind::user_defined1())]);
+
+        handler
+            .try_add_signal(UnixSignalKind::user_defined2())
+            .expect("failed to add SIGUSR2")
+            .try_add_signal(UnixSignalKind::user_defined1())
+            .expect("failed to add SIGUSR1 again");
+        assert_eq!(handler.signals().count(), 2);
+
+        assert!(
+            SignalHandler::new()
+                .try_with_signal(UnixSignalKind::from_raw(libc::SIGSTOP))
+                .is_err()
+        );
+    }
+
+    #[cfg(unix)]
+    #[should_panic(expected = "failed to create signal")]
+    #[tokio::test]
+    async fn add_signal_panics() {
+        use crate::UnixSignalKind;
+
+        SignalHandler::new().add_signal(UnixSignalKind::from_raw(libc::SIGKILL));
     }
 
     #[cfg(windows)]
-    #[test]
-    fn signal_kind_eq() {
+    #[tokio::test]
+    async fn with_terminating_signals() {
         use crate::WindowsSignalKind;
 
-        assert_eq!(SignalKind::Interrupt, SignalKind::Windows(WindowsSignalKind::CtrlC));
-        assert_eq!(SignalKind::Terminate, SignalKind::Windows(WindowsSignalKind::CtrlClose));
-        assert_eq!(SignalKind::Windows(WindowsSignalKind::CtrlC), SignalKind::Interrupt);
-        assert_eq!(SignalKind::Windows(WindowsSignalKind::CtrlClose), SignalKind::Terminate);
-        assert_ne!(SignalKind::Interrupt, SignalKind::Terminate);
+        let handler = SignalHandler::with_terminating_signals().with_signal(WindowsSignalKind::CtrlShutdown);
         assert_eq!(
-            SignalKind::Windows(WindowsSignalKind::CtrlBreak),
-            SignalKind::Windows(WindowsSignalKind::CtrlBreak)
+            handler.kinds_snapshot(),
+            [
+                SignalKind::Interrupt,
+                SignalKind::Terminate,
+                SignalKind::Windows(WindowsSignalKind::CtrlBreak),
+                SignalKind::Windows(WindowsSignalKind::CtrlShutdown),
+            ]
         );
     }
 
     #[cfg(unix)]
-    #[test]
-    fn signal_kind_eq() {
+    #[tokio::test]
+    async fn with_terminating_signals() {
         use crate::UnixSignalKind;
 
-        assert_eq!(SignalKind::Interrupt, SignalKind::Unix(UnixSignalKind::interrupt()));
-        assert_eq!(SignalKind::Terminate, SignalKind::Unix(UnixSignalKind::terminate()));
-        assert_eq!(SignalKind::Unix(UnixSignalKind::interrupt()), SignalKind::Interrupt);
-        assert_eq!(SignalKind::Unix(UnixSignalKind::terminate()), SignalKind::Terminate);
-        assert_ne!(SignalKind::Interrupt, SignalKind::Terminate);
+        let handler = SignalHandler::with_terminating_signals().with_signal(UnixSignalKind::hangup());
         assert_eq!(
-            SignalKind::Unix(UnixSignalKind::user_defined1()),
-            SignalKind::Unix(UnixSignalKind::user_defined1())
+            handler.kinds_snapshot(),
+            [
+                SignalKind::Interrupt,
+                SignalKind::Terminate,
+                SignalKind::Unix(UnixSignalKind::quit()),
+                SignalKind::Unix(UnixSignalKind::hangup()),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn signals() {
+        use crate::UnixSignalKind;
+
+        let mut handler = SignalHandler::new();
+        assert_eq!(handler.signals().count(), 0);
+
+        handler
+            .add_signal(UnixSignalKind::user_defined1())
+            .add_signal(SignalKind::Interrupt)
+            .add_signal(UnixSignalKind::user_defined1())
+            .add_signal(UnixSignalKind::interrupt());
+
+        assert_eq!(
+            handler.signals().collect::<Vec<_>>(),
+            [SignalKind::Unix(UnixSignalKind::user_defined1()), SignalKind::Interrupt]
         );
     }
+
+    #[cfg(windows)]
+    #[tokio::test]
+    async fn history() {
+        use crate::WindowsSignalKind;
+
+        let mut handler =
+            SignalHandler::with_signals([WindowsSignalKind::CtrlC, WindowsSignalKind::CtrlBreak]).with_history(2);
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+        raise_signal(SignalKind::Windows(WindowsSignalKind::CtrlBreak)).await;
+        handler.recv().with_timeout(Duration::from_millis(500)).await.unwrap();
+
+        raise_signal(SignalKind::Windows(WindowsSignalKind::CtrlC)).await;
+        handler.recv().with_timeout(Duration::from_millis(500)).await.unwrap();
+
+        let history = handler.history();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].0, WindowsSignalKind::CtrlBreak, "expected CtrlBreak first");
+        assert_eq!(history[1].0, WindowsSignalKind::CtrlC, "expected CtrlC second");
+        assert!(history[0].1 <= history[1].1);
+    }
+
+    #[cfg(all(not(valgrind), unix))] // test is time-sensitive
+    #[tokio::test]
+    async fn history() {
+        use crate::UnixSignalKind;
+
+        let mut handler =
+            SignalHandler::with_signals([UnixSignalKind::user_defined1(), UnixSignalKind::user_defined2()]).with_history(2);
+
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined2())).await;
+        handler.recv().with_timeout(Duration::from_millis(500)).await.unwrap();
+
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined1())).await;
+        handler.recv().with_timeout(Duration::from_millis(500)).await.unwrap();
+
+        let history = handler.history();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].0, UnixSignalKind::user_defined2(), "expected SIGUSR2 first");
+        assert_eq!(history[1].0, UnixSignalKind::user_defined1(), "expected SIGUSR1 second");
+        assert!(history[0].1 <= history[1].1);
+
+        // The history is bounded, so the oldest entry is dropped
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined1())).await;
+        handler.recv().with_timeout(Duration::from_millis(500)).await.unwrap();
+
+        let history = handler.history();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].0, UnixSignalKind::user_defined1());
+        assert_eq!(history[1].0, UnixSignalKind::user_defined1());
+    }
+
+    #[cfg(all(not(valgrind), unix))] // test is time-sensitive
+    #[tokio::test]
+    async fn signal_before_first_poll() {
+        use crate::UnixSignalKind;
+
+        let mut handler = SignalHandler::new();
+        handler.add_signal(UnixSignalKind::user_defined1());
+
+        // Raised before the handler has ever been polled
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined1())).await;
+
+        let recv = handler.recv().with_timeout(Duration::from_millis(500)).await.unwrap();
+        assert_eq!(recv, UnixSignalKind::user_defined1(), "expected SIGUSR1");
+
+        // Signals raised before the handler is polled are coalesced
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined1())).await;
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined1())).await;
+
+        let recv = handler.recv().with_timeout(Duration::from_millis(500)).await.unwrap();
+        assert_eq!(recv, UnixSignalKind::user_defined1(), "expected SIGUSR1");
+
+        let recv = handler.recv().with_timeout(Duration::from_millis(500)).await;
+        assert!(recv.is_err(), "expected the signals to be coalesced");
+    }
+
+    #[cfg(all(not(valgrind), unix))] // test is time-sensitive
+    #[tokio::test]
+    async fn replace_signal() {
+        use crate::UnixSignalKind;
+
+        let mut handler = SignalHandler::with_signals([UnixSignalKind::user_defined1()]).with_history(10);
+        handler.replace_signal(UnixSignalKind::user_defined1());
+        assert_eq!(handler.signals.len(), 1);
+
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined1())).await;
+
+        let recv = handler.recv().with_timeout(Duration::from_millis(500)).await.unwrap();
+        assert_eq!(recv, UnixSignalKind::user_defined1(), "expected SIGUSR1");
+        assert_eq!(handler.history().len(), 1);
+
+        // Replacing a signal that is not in the handler adds it
+        handler.replace_signal(UnixSignalKind::user_defined2());
+        assert_eq!(handler.signals.len(), 2);
+
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined2())).await;
+
+        let recv = handler.recv().with_timeout(Duration::from_millis(500)).await.unwrap();
+        assert_eq!(recv, UnixSignalKind::user_defined2(), "expected SIGUSR2");
+
+        // The replaced signal keeps delivering, and only its history is reset
+        handler.replace_signal(UnixSignalKind::user_defined1());
+        assert_eq!(handler.signals[0].0, UnixSignalKind::user_defined1());
+        assert_eq!(handler.history().len(), 1);
+        assert_eq!(handler.history()[0].0, UnixSignalKind::user_defined2());
+
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined1())).await;
+
+        let recv = handler.recv().with_timeout(Duration::from_millis(500)).await.unwrap();
+        assert_eq!(recv, UnixSignalKind::user_defined1(), "expected SIGUSR1");
+
+        // The handler is not changed if the signal cannot be listened for
+        assert!(handler.try_replace_signal(UnixSignalKind::from_raw(libc::SIGKILL)).is_err());
+        assert_eq!(handler.signals.len(), 2);
+    }
+
+    #[cfg(all(not(valgrind), unix))] // test is time-sensitive
+    #[tokio::test]
+    async fn clear() {
+        use crate::UnixSignalKind;
+
+        let mut handler = SignalHandler::with_signals([UnixSignalKind::user_defined1(), UnixSignalKind::user_defined2()]);
+
+        assert!(handler.clear().kinds_snapshot().is_empty());
+
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined1())).await;
+
+        let recv = handler.recv().with_timeout(Duration::from_millis(500)).await;
+        assert!(recv.is_err(), "expected timeout");
+
+        // The handler can be reconfigured
+        handler.add_signal(UnixSignalKind::user_defined2());
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined2())).await;
+
+        let recv = handler.recv().with_timeout(Duration::from_millis(500)).await.unwrap();
+        assert_eq!(recv, UnixSignalKind::user_defined2(), "expected SIGUSR2");
+    }
+
+    #[cfg(all(not(valgrind), unix))] // test is time-sensitive
+    #[tokio::test]
+    async fn remove_signal() {
+        use crate::UnixSignalKind;
+
+        let mut handler = SignalHandler::with_signals([
+            SignalKind::Interrupt,
+            SignalKind::Unix(UnixSignalKind::user_defined1()),
+            SignalKind::Unix(UnixSignalKind::user_defined2()),
+        ]);
+
+        assert!(handler.remove_signal(UnixSignalKind::user_defined2()));
+        assert!(!handler.remove_signal(UnixSignalKind::user_defined2()));
+        assert_eq!(
+            handler.kinds_snapshot(),
+            [SignalKind::Interrupt, SignalKind::Unix(UnixSignalKind::user_defined1())]
+        );
+
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined2())).await;
+
+        let recv = handler.recv().with_timeout(Duration::from_millis(500)).await;
+        assert!(recv.is_err(), "expected the removed signal to not be received");
+
+        // The other signals keep delivering
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined1())).await;
+
+        let recv = handler.recv().with_timeout(Duration::from_millis(500)).await.unwrap();
+        assert_eq!(recv, UnixSignalKind::user_defined1(), "expected SIGUSR1");
+
+        // The platform specific kind matches the generic one
+        assert!(handler.remove_signal(UnixSignalKind::interrupt()));
+        assert_eq!(handler.kinds_snapshot(), [SignalKind::Unix(UnixSignalKind::user_defined1())]);
+    }
+
+    #[cfg(all(not(valgrind), unix))] // test is time-sensitive
+    #[tokio::test]
+    async fn poll_recv_counted() {
+        use crate::UnixSignalKind;
+
+        let mut handler =
+            SignalHandler::with_signals([UnixSignalKind::user_defined1(), UnixSignalKind::hangup()]).with_history(10);
+
+        raise_signal(SignalKind::Unix(UnixSignalKind::hangup())).await;
+        raise_signal(SignalKind::Unix(UnixSignalKind::hangup())).await;
+
+        let (kind, count) = std::future::poll_fn(|cx| handler.poll_recv_counted(cx))
+            .with_timeout(Duration::from_millis(500))
+            .await
+            .expect("signal timeout");
+
+        assert_eq!(kind, SignalKind::Unix(UnixSignalKind::hangup()));
+        // The deliveries may have been coalesced
+        assert!((1..=2).contains(&count), "unexpected count: {count}");
+        assert_eq!(handler.history().len(), count);
+
+        // Every delivery was consumed
+        let recv = handler.recv().with_timeout(Duration::from_millis(100)).await;
+        assert!(recv.is_err(), "expected timeout");
+    }
+
+    #[cfg(windows)]
+    #[tokio::test]
+    async fn poll_recv_counted() {
+        use crate::WindowsSignalKind;
+
+        let mut handler =
+            SignalHandler::with_signals([WindowsSignalKind::CtrlC, WindowsSignalKind::CtrlBreak]).with_history(10);
+
+        for _ in 0..3 {
+            raise_signal(SignalKind::Windows(WindowsSignalKind::CtrlBreak)).await;
+        }
+
+        let (kind, count) = std::future::poll_fn(|cx| handler.poll_recv_counted(cx))
+            .with_timeout(Duration::from_millis(500))
+            .await
+            .expect("signal timeout");
+
+        // The mocked events are never coalesced, so every delivery is counted
+        assert_eq!(kind, SignalKind::Windows(WindowsSignalKind::CtrlBreak));
+        assert_eq!(count, 3);
+        assert_eq!(handler.history().len(), 3);
+
+        // Every delivery was consumed
+        let recv = handler.recv().with_timeout(Duration::from_millis(100)).await;
+        assert!(recv.is_err(), "expected timeout");
+    }
+
+    #[cfg(all(not(valgrind), unix))] // test is time-sensitive
+    #[tokio::test]
+    async fn drain() {
+        use crate::UnixSignalKind;
+
+        let mut handler = SignalHandler::with_signals([
+            UnixSignalKind::user_defined1(),
+            UnixSignalKind::user_defined2(),
+            UnixSignalKind::hangup(),
+        ])
+        .with_history(10);
+
+        assert!(handler.drain().is_empty());
+
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined1())).await;
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined2())).await;
+
+        // Let the runtime deliver the signals
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(
+            handler.drain(),
+            vec![
+                SignalKind::Unix(UnixSignalKind::user_defined1()),
+                SignalKind::Unix(UnixSignalKind::user_defined2())
+            ]
+        );
+        assert_eq!(handler.history().len(), 2);
+
+        // The signals were consumed
+        assert!(handler.drain().is_empty());
+        let recv = handler.recv().with_timeout(Duration::from_millis(100)).await;
+        assert!(recv.is_err(), "expected timeout");
+    }
+
+    #[cfg(all(not(valgrind), unix))] // test is time-sensitive
+    #[tokio::test]
+    async fn reconfigure() {
+        use crate::UnixSignalKind;
+
+        let mut handler = SignalHandler::with_signals([UnixSignalKind::user_defined1(), UnixSignalKind::hangup()]);
+        assert_eq!(
+            handler.kinds_snapshot(),
+            vec![
+                SignalKind::Unix(UnixSignalKind::user_defined1()),
+                SignalKind::Unix(UnixSignalKind::hangup())
+            ]
+        );
+
+        // Arrives before the handler is reconfigured
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined1())).await;
+
+        let mut kinds = handler.kinds_snapshot();
+        kinds.retain(|kind| kind != &UnixSignalKind::hangup());
+        kinds.push(SignalKind::Unix(UnixSignalKind::user_defined2()));
+        handler.reconfigure(kinds).expect("failed to reconfigure");
+
+        assert_eq!(
+            handler.kinds_snapshot(),
+            vec![
+                SignalKind::Unix(UnixSignalKind::user_defined1()),
+                SignalKind::Unix(UnixSignalKind::user_defined2())
+            ]
+        );
+
+        raise_signal(SignalKind::Unix(UnixSignalKind::user_defined2())).await;
+
+        // Let the runtime deliver the signals
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(
+            handler.drain(),
+            vec![
+                SignalKind::Unix(UnixSignalKind::user_defined1()),
+                SignalKind::Unix(UnixSignalKind::user_defined2())
+            ]
+        );
+
+        // The handler is not changed if a signal cannot be listened for
+        let kinds = [
+            SignalKind::Unix(UnixSignalKind::hangup()),
+            SignalKind::Unix(UnixSignalKind::from_raw(libc::SIGKILL)),
+        ];
+        assert!(handler.reconfigure(kinds).is_err());
+        assert_eq!(
+            handler.kinds_snapshot(),
+            vec![
+                SignalKind::Unix(UnixSignalKind::user_defined1()),
+                SignalKind::Unix(UnixSignalKind::user_defined2())
+            ]
+        );
+    }
+
+    #[test]
+    fn history_disabled() {
+        let handler = SignalHandler::new();
+        assert!(handler.history().is_empty());
+    }
+
+    #[test]
+    fn history_large_capacity() {
+        // Only a bounded number of entries is allocated up front
+        let handler = SignalHandler::new().with_history(usize::MAX);
+        assert!(handler.history().is_empty());
+        assert!(handler.history.as_ref().unwrap().entries.capacity() < 1024);
+    }
+
+    #[cfg(not(valgrind))] // test is time-sensitive
+    #[tokio::test]
+    async fn no_signals() {
+        let mut handler = SignalHandler::default();
+
+        // Expected to timeout
+        assert!(handler.recv().with_timeout(Duration::from_millis(500)).await.is_err());
+    }
+
+    #[cfg(windows)]
+    #[tokio::test]
+    async fn ctrl_break() {
+        use crate::WindowsSignalKind;
+
+        let ctrl_break = SignalKind::windows(WindowsSignalKind::CtrlBreak);
+        assert_ne!(ctrl_break, SignalKind::Interrupt, "CtrlBreak should not be an interrupt");
+        assert_ne!(ctrl_break, SignalKind::Terminate, "CtrlBreak should not be a terminate");
+
+        let mut handler = SignalHandler::new()
+            .with_signal(SignalKind::Interrupt)
+            .with_signal(ctrl_break);
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+        raise_signal(ctrl_break).await;
+
+        let recv = handler.recv().with_timeout(Duration::from_millis(500)).await.unwrap();
+        assert_eq!(recv, WindowsSignalKind::CtrlBreak, "expected CtrlBreak");
+        assert_ne!(recv, SignalKind::Interrupt);
+
+        // Listening for interrupts alone does not catch CtrlBreak
+        let mut handler = SignalHandler::new().with_signal(SignalKind::Interrupt);
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+        raise_signal(ctrl_break).await;
+
+        let recv = handler.recv().with_timeout(Duration::from_millis(500)).await;
+        assert!(recv.is_err(), "expected timeout");
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn windows_signal_kind() {
+        use crate::WindowsSignalKind;
+
+        assert!(matches!(SignalKind::windows(WindowsSignalKind::CtrlC), SignalKind::Interrupt));
+        assert!(matches!(
+            SignalKind::windows(WindowsSignalKind::CtrlClose),
+            SignalKind::Terminate
+        ));
+        assert!(matches!(
+            SignalKind::windows(WindowsSignalKind::CtrlBreak),
+            SignalKind::Windows(WindowsSignalKind::CtrlBreak)
+        ));
+        assert!(matches!(
+            SignalKind::windows(WindowsSignalKind::CtrlLogoff),
+            SignalKind::Windows(WindowsSignalKind::CtrlLogoff)
+        ));
+        assert!(matches!(
+            SignalKind::windows(WindowsSignalKind::CtrlShutdown),
+            SignalKind::Windows(WindowsSignalKind::CtrlShutdown)
+        ));
+
+        assert!(WindowsSignalKind::CtrlBreak.is_group_deliverable());
+        assert!(!WindowsSignalKind::CtrlC.is_group_deliverable());
+        assert!(!WindowsSignalKind::CtrlClose.is_group_deliverable());
+        assert!(!WindowsSignalKind::CtrlLogoff.is_group_deliverable());
+        assert!(!WindowsSignalKind::CtrlShutdown.is_group_deliverable());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn windows_ctrl_event() {
+        use crate::WindowsSignalKind;
+
+        assert_eq!(WindowsSignalKind::CtrlC.as_ctrl_event(), Some(0));
+        assert_eq!(WindowsSignalKind::CtrlBreak.as_ctrl_event(), Some(1));
+        assert_eq!(WindowsSignalKind::CtrlClose.as_ctrl_event(), None);
+        assert_eq!(WindowsSignalKind::CtrlLogoff.as_ctrl_event(), None);
+        assert_eq!(WindowsSignalKind::CtrlShutdown.as_ctrl_event(), None);
+
+        assert_eq!(WindowsSignalKind::try_from(0), Ok(WindowsSignalKind::CtrlC));
+        assert_eq!(WindowsSignalKind::try_from(1), Ok(WindowsSignalKind::CtrlBreak));
+        assert_eq!(WindowsSignalKind::try_from(2), Ok(WindowsSignalKind::CtrlClose));
+        assert_eq!(WindowsSignalKind::try_from(5), Ok(WindowsSignalKind::CtrlLogoff));
+        assert_eq!(WindowsSignalKind::try_from(6), Ok(WindowsSignalKind::CtrlShutdown));
+        assert_eq!(WindowsSignalKind::try_from(3), Err(3));
+        assert_eq!(WindowsSignalKind::try_from(4), Err(4));
+
+        for kind in [WindowsSignalKind::CtrlC, WindowsSignalKind::CtrlBreak] {
+            assert_eq!(WindowsSignalKind::try_from(kind.as_ctrl_event().unwrap()), Ok(kind));
+        }
+
+        for kind in [
+            WindowsSignalKind::CtrlBreak,
+            WindowsSignalKind::CtrlC,
+            WindowsSignalKind::CtrlClose,
+            WindowsSignalKind::CtrlLogoff,
+            WindowsSignalKind::CtrlShutdown,
+        ] {
+            assert_eq!(WindowsSignalKind::try_from(kind.as_raw_value()), Ok(kind));
+            assert_eq!(SignalKind::from(kind).as_raw_value(), kind.as_raw_value());
+        }
+
+        assert_eq!(SignalKind::Interrupt.as_raw_value(), 0);
+        assert_eq!(SignalKind::Terminate.as_raw_value(), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn signal_kind_as_raw_value() {
+        use crate::UnixSignalKind;
+
+        assert_eq!(SignalKind::Interrupt.as_raw_value(), libc::SIGINT);
+        assert_eq!(SignalKind::Terminate.as_raw_value(), libc::SIGTERM);
+        assert_eq!(SignalKind::Unix(UnixSignalKind::hangup()).as_raw_value(), libc::SIGHUP);
+        assert_eq!(
+            SignalKind::Unix(UnixSignalKind::interrupt()).as_raw_value(),
+            SignalKind::Interrupt.as_raw_value()
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn signal_kind_eq() {
+        use crate::WindowsSignalKind;
+
+        assert_eq!(SignalKind::Interrupt, SignalKind::Windows(WindowsSignalKind::CtrlC));
+        assert_eq!(SignalKind::Terminate, SignalKind::Windows(WindowsSignalKind::CtrlClose));
+        assert_eq!(SignalKind::Windows(WindowsSignalKind::CtrlC), SignalKind::Interrupt);
+        assert_eq!(SignalKind::Windows(WindowsSignalKind::CtrlClose), SignalKind::Terminate);
+        assert_ne!(SignalKind::Interrupt, SignalKind::Terminate);
+        assert_eq!(
+            SignalKind::Windows(WindowsSignalKind::CtrlBreak),
+            SignalKind::Windows(WindowsSignalKind::CtrlBreak)
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn signal_kind_constructors() {
+        use crate::WindowsSignalKind;
+
+        let signal = SignalKind::from(WindowsSignalKind::CtrlC);
+        assert!(signal == SignalKind::interrupt());
+        assert!(signal != SignalKind::terminate());
+        assert!(WindowsSignalKind::CtrlC == SignalKind::interrupt());
+        assert!(WindowsSignalKind::CtrlClose == SignalKind::terminate());
+        assert!(WindowsSignalKind::CtrlBreak != SignalKind::interrupt());
+        assert!(SignalKind::interrupt() == WindowsSignalKind::CtrlC);
+        assert!(matches!(SignalKind::interrupt(), SignalKind::Interrupt));
+        assert!(matches!(SignalKind::terminate(), SignalKind::Terminate));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn signal_kind_eq() {
+        use crate::UnixSignalKind;
+
+        assert_eq!(SignalKind::Interrupt, SignalKind::Unix(UnixSignalKind::interrupt()));
+        assert_eq!(SignalKind::Terminate, SignalKind::Unix(UnixSignalKind::terminate()));
+        assert_eq!(SignalKind::Unix(UnixSignalKind::interrupt()), SignalKind::Interrupt);
+        assert_eq!(SignalKind::Unix(UnixSignalKind::terminate()), SignalKind::Terminate);
+        assert_ne!(SignalKind::Interrupt, SignalKind::Terminate);
+        assert_eq!(
+            SignalKind::Unix(UnixSignalKind::user_defined1()),
+            SignalKind::Unix(UnixSignalKind::user_defined1())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn signal_kind_constructors() {
+        use crate::UnixSignalKind;
+
+        let signal = SignalKind::from(UnixSignalKind::interrupt());
+        assert!(signal == SignalKind::interrupt());
+        assert!(signal != SignalKind::terminate());
+        assert!(UnixSignalKind::interrupt() == SignalKind::interrupt());
+        assert!(UnixSignalKind::terminate() == SignalKind::terminate());
+        assert!(UnixSignalKind::user_defined1() != SignalKind::interrupt());
+        assert!(SignalKind::interrupt() == UnixSignalKind::interrupt());
+        assert!(SignalKind::Unix(UnixSignalKind::interrupt()) == SignalKind::interrupt());
+        assert!(matches!(SignalKind::interrupt(), SignalKind::Interrupt));
+        assert!(matches!(SignalKind::terminate(), SignalKind::Terminate));
+
+        // The intended dispatch from the examples
+        let handled = match signal {
+            SignalKind::Interrupt => "interrupt",
+            SignalKind::Terminate => "terminate",
+            _ => "other",
+        };
+        assert_eq!(handled, "interrupt");
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn signal_kind_name() {
+        use crate::WindowsSignalKind;
+
+        assert_eq!(SignalKind::Interrupt.name(), "Ctrl-C");
+        assert_eq!(SignalKind::Terminate.name(), "Ctrl-Close");
+        assert_eq!(SignalKind::Windows(WindowsSignalKind::CtrlBreak).name(), "Ctrl-Break");
+        assert_eq!(SignalKind::Interrupt.to_string(), "Ctrl-C");
+        assert_eq!(
+            SignalKind::Windows(WindowsSignalKind::CtrlShutdown).to_string(),
+            "Ctrl-Shutdown"
+        );
+        assert_eq!(WindowsSignalKind::CtrlLogoff.to_string(), "Ctrl-Logoff");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn signal_kind_name() {
+        use crate::UnixSignalKind;
+
+        assert_eq!(SignalKind::Interrupt.name(), "SIGINT");
+        assert_eq!(SignalKind::Terminate.name(), "SIGTERM");
+        assert_eq!(SignalKind::Unix(UnixSignalKind::interrupt()).name(), "SIGINT");
+        assert_eq!(SignalKind::Unix(UnixSignalKind::hangup()).name(), "SIGHUP");
+        assert_eq!(SignalKind::Unix(UnixSignalKind::from_raw(libc::SIGKILL)).name(), "SIGKILL");
+        assert_eq!(SignalKind::Interrupt.to_string(), "SIGINT");
+        assert_eq!(SignalKind::Unix(UnixSignalKind::user_defined2()).to_string(), "SIGUSR2");
+
+        // Every supported signal has a name
+        for kind in SignalKind::all_supported() {
+            assert_ne!(kind.name(), "UNKNOWN", "{kind:?} has no name");
+        }
+
+        assert_eq!(SignalKind::Unix(UnixSignalKind::from_raw(1000)).name(), "UNKNOWN");
+        assert_eq!(SignalKind::Unix(UnixSignalKind::from_raw(1000)).to_string(), "signal 1000");
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn signal_kind_from_str() {
+        use crate::WindowsSignalKind;
+
+        assert_eq!("Ctrl-C".parse::<SignalKind>(), Ok(SignalKind::Interrupt));
+        assert_eq!("CtrlClose".parse::<SignalKind>(), Ok(SignalKind::Terminate));
+        assert_eq!(
+            "CtrlBreak".parse::<SignalKind>(),
+            Ok(SignalKind::Windows(WindowsSignalKind::CtrlBreak))
+        );
+
+        for kind in SignalKind::all_supported() {
+            assert_eq!(kind.to_string().parse::<SignalKind>(), Ok(kind));
+            assert_eq!(kind.name().parse::<SignalKind>(), Ok(kind));
+        }
+
+        let err = "SIGINT".parse::<SignalKind>().unwrap_err();
+        assert_eq!(err.name(), "SIGINT");
+        assert_eq!(err.to_string(), "unknown signal name: \"SIGINT\"");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn signal_kind_from_str() {
+        use crate::UnixSignalKind;
+
+        assert_eq!("SIGINT".parse::<SignalKind>(), Ok(SignalKind::Interrupt));
+        assert!(matches!("SIGTERM".parse::<SignalKind>(), Ok(SignalKind::Terminate)));
+        assert_eq!(
+            "SIGUSR1".parse::<SignalKind>(),
+            Ok(SignalKind::Unix(UnixSignalKind::user_defined1()))
+        );
+
+        // Signals that cannot be listened for are rejected
+        for name in ["SIGKILL", "SIGSTOP"] {
+            let err = name.parse::<SignalKind>().unwrap_err();
+            assert_eq!(err.name(), name);
+            assert!(err.is_unsupported());
+            assert_eq!(err.to_string(), format!("signal cannot be listened for: {name:?}"));
+        }
+
+        for kind in SignalKind::all_supported() {
+            assert_eq!(kind.to_string().parse::<SignalKind>(), Ok(kind));
+            assert_eq!(kind.name().parse::<SignalKind>(), Ok(kind));
+        }
+
+        for name in ["sigint", "INT", "SIGINT ", "", "SIGRTMIN-1"] {
+            let err = name.parse::<SignalKind>().unwrap_err();
+            assert_eq!(err.name(), name);
+            assert!(!err.is_unsupported());
+        }
+
+        let err = "SIGFOO".parse::<SignalKind>().unwrap_err();
+        assert_eq!(err.to_string(), "unknown signal name: \"SIGFOO\"");
+
+        // Building a handler from configuration values
+        let kinds = ["SIGHUP", "SIGUSR2"]
+            .into_iter()
+            .map(str::parse)
+            .collect::<Result<Vec<SignalKind>, _>>()
+            .expect("failed to parse signals");
+        assert_eq!(
+            kinds,
+            [
+                SignalKind::Unix(UnixSignalKind::hangup()),
+                SignalKind::Unix(UnixSignalKind::user_defined2())
+            ]
+        );
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[test]
+    fn signal_kind_name_realtime() {
+        use crate::UnixSignalKind;
+
+        let min = SignalKind::Unix(UnixSignalKind::from_raw(libc::SIGRTMIN()));
+        assert_eq!(min.name(), "SIGRT");
+        assert_eq!(min.to_string(), "SIGRTMIN");
+
+        let realtime = SignalKind::Unix(UnixSignalKind::from_raw(libc::SIGRTMIN() + 2));
+        assert_eq!(realtime.name(), "SIGRT");
+        assert_eq!(realtime.to_string(), "SIGRTMIN+2");
+
+        assert_eq!("SIGRTMIN".parse::<SignalKind>(), Ok(min));
+        assert_eq!("SIGRTMIN+2".parse::<SignalKind>(), Ok(realtime));
+        assert!("SIGRTMIN+1000".parse::<SignalKind>().is_err());
+        assert!("SIGRTMIN+".parse::<SignalKind>().is_err());
+        assert!("SIGRTMIN+-1".parse::<SignalKind>().is_err());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn all_supported() {
+        use crate::WindowsSignalKind;
+
+        let supported = SignalKind::all_supported();
+        assert_eq!(supported.len(), 5);
+        assert!(supported.contains(&SignalKind::Interrupt));
+        assert!(supported.contains(&SignalKind::Terminate));
+        assert!(supported.contains(&SignalKind::Windows(WindowsSignalKind::CtrlBreak)));
+        assert!(supported.contains(&SignalKind::Windows(WindowsSignalKind::CtrlLogoff)));
+        assert!(supported.contains(&SignalKind::Windows(WindowsSignalKind::CtrlShutdown)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn all_supported() {
+        use crate::UnixSignalKind;
+
+        let supported = SignalKind::all_supported();
+        assert!(supported.contains(&SignalKind::Interrupt));
+        assert!(supported.contains(&SignalKind::Terminate));
+        assert!(supported.contains(&SignalKind::Unix(UnixSignalKind::hangup())));
+        assert!(supported.contains(&SignalKind::Unix(UnixSignalKind::user_defined1())));
+        assert!(supported.contains(&SignalKind::Unix(UnixSignalKind::user_defined2())));
+        assert!(supported.contains(&SignalKind::Unix(UnixSignalKind::window_change())));
+
+        // Signals that cannot have a handler registered are not included
+        assert!(!supported.contains(&SignalKind::Unix(UnixSignalKind::from_raw(libc::SIGKILL))));
+        assert!(!supported.contains(&SignalKind::Unix(UnixSignalKind::from_raw(libc::SIGSTOP))));
+
+        // Interrupt and terminate are normalized, so they are only listed once
+        assert_eq!(supported.iter().filter(|kind| **kind == SignalKind::Interrupt).count(), 1);
+        assert_eq!(supported.iter().filter(|kind| **kind == SignalKind::Terminate).count(), 1);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn signal_kind_sets() {
+        use std::collections::{BTreeSet, HashSet};
+
+        use crate::WindowsSignalKind;
+
+        let kinds = [
+            SignalKind::Interrupt,
+            SignalKind::Windows(WindowsSignalKind::CtrlC),
+            SignalKind::Terminate,
+            SignalKind::Windows(WindowsSignalKind::CtrlClose),
+            SignalKind::Windows(WindowsSignalKind::CtrlBreak),
+        ];
+
+        let set = kinds.into_iter().collect::<HashSet<_>>();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&SignalKind::Windows(WindowsSignalKind::CtrlC)));
+        assert!(set.contains(&SignalKind::Terminate));
+        assert!(set.contains(&SignalKind::Windows(WindowsSignalKind::CtrlBreak)));
+        assert!(!set.contains(&SignalKind::Windows(WindowsSignalKind::CtrlLogoff)));
+
+        let set = kinds.into_iter().collect::<BTreeSet<_>>();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&SignalKind::Interrupt));
+        assert!(set.contains(&SignalKind::Windows(WindowsSignalKind::CtrlClose)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn signal_kind_sets() {
+        use std::collections::{BTreeSet, HashSet};
+
+        use crate::UnixSignalKind;
+
+        let kinds = [
+            SignalKind::Interrupt,
+            SignalKind::Unix(UnixSignalKind::interrupt()),
+            SignalKind::Terminate,
+            SignalKind::Unix(UnixSignalKind::terminate()),
+            SignalKind::Unix(UnixSignalKind::hangup()),
+            SignalKind::Unix(UnixSignalKind::hangup()),
+        ];
+
+        let set = kinds.into_iter().collect::<HashSet<_>>();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&SignalKind::Unix(UnixSignalKind::interrupt())));
+        assert!(set.contains(&SignalKind::Terminate));
+        assert!(set.contains(&SignalKind::Unix(UnixSignalKind::hangup())));
+        assert!(!set.contains(&SignalKind::Unix(UnixSignalKind::user_defined1())));
+
+        let set = kinds.into_iter().collect::<BTreeSet<_>>();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&SignalKind::Interrupt));
+        assert!(set.contains(&SignalKind::Unix(UnixSignalKind::terminate())));
+
+        // The order follows the raw signal values
+        assert!(SignalKind::Unix(UnixSignalKind::hangup()) < SignalKind::Interrupt);
+        assert!(SignalKind::Interrupt < SignalKind::Terminate);
+    }
 }
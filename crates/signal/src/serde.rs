@@ -0,0 +1,114 @@
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::SignalKind;
+#[cfg(windows)]
+use crate::WindowsSignalKind;
+
+/// Serializes the signal as its name, as returned by
+/// [`Display`](std::fmt::Display), for example `SIGUSR1` on Unix or `Ctrl-C` on
+/// Windows.
+impl Serialize for SignalKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserializes the signal from its name, accepting the same names as
+/// [`FromStr`](std::str::FromStr).
+impl<'de> Deserialize<'de> for SignalKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(SignalKindVisitor)
+    }
+}
+
+struct SignalKindVisitor;
+
+impl Visitor<'_> for SignalKindVisitor {
+    type Value = SignalKind;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a signal name")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse().map_err(E::custom)
+    }
+}
+
+/// Serializes the event as its name, for example `Ctrl-C`.
+#[cfg(windows)]
+impl Serialize for WindowsSignalKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+/// Deserializes the event from its name, accepting the same names as
+/// [`SignalKind`].
+#[cfg(windows)]
+impl<'de> Deserialize<'de> for WindowsSignalKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        SignalKind::deserialize(deserializer).map(|kind| kind.normalized())
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(all(coverage_nightly, test), coverage(off))]
+mod test {
+    use crate::SignalKind;
+
+    #[cfg(unix)]
+    #[test]
+    fn signal_kind() {
+        use crate::UnixSignalKind;
+
+        assert_eq!(serde_json::to_string(&SignalKind::Interrupt).unwrap(), r#""SIGINT""#);
+        assert_eq!(serde_json::to_string(&SignalKind::Terminate).unwrap(), r#""SIGTERM""#);
+        assert_eq!(
+            serde_json::to_string(&SignalKind::Unix(UnixSignalKind::user_defined1())).unwrap(),
+            r#""SIGUSR1""#
+        );
+
+        let kinds: Vec<SignalKind> = serde_json::from_str(r#"["SIGINT", "SIGTERM", "SIGUSR1"]"#).unwrap();
+        assert_eq!(
+            kinds,
+            [
+                SignalKind::Interrupt,
+                SignalKind::Terminate,
+                SignalKind::Unix(UnixSignalKind::user_defined1())
+            ]
+        );
+
+        let kind = SignalKind::Unix(UnixSignalKind::hangup());
+        let json = serde_json::to_string(&kind).unwrap();
+        assert_eq!(serde_json::from_str::<SignalKind>(&json).unwrap(), kind);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn signal_kind() {
+        use crate::WindowsSignalKind;
+
+        assert_eq!(serde_json::to_string(&SignalKind::Interrupt).unwrap(), r#""Ctrl-C""#);
+        assert_eq!(
+            serde_json::to_string(&WindowsSignalKind::CtrlBreak).unwrap(),
+            r#""Ctrl-Break""#
+        );
+
+        let kinds: Vec<SignalKind> = serde_json::from_str(r#"["Ctrl-C", "CtrlClose"]"#).unwrap();
+        assert_eq!(kinds, [SignalKind::Interrupt, SignalKind::Terminate]);
+
+        let kind: WindowsSignalKind = serde_json::from_str(r#""Ctrl-Shutdown""#).unwrap();
+        assert_eq!(kind, WindowsSignalKind::CtrlShutdown);
+    }
+
+    #[test]
+    fn signal_kind_unknown() {
+        let err = serde_json::from_str::<SignalKind>(r#""SIGFOO""#).unwrap_err();
+        assert!(err.to_string().contains(r#"unknown signal name: "SIGFOO""#), "{err}");
+
+        let err = serde_json::from_str::<SignalKind>("2").unwrap_err();
+        assert!(err.to_string().contains("expected a signal name"), "{err}");
+    }
+}